@@ -1,8 +1,17 @@
+use std::path::PathBuf;
 use clap::{Parser, Subcommand};
+use crate::commands::agents::AgentSort;
+use crate::commands::uninstall::Tool;
+use crate::format::{AgentStatus, Priority};
 
 #[derive(Parser)]
 #[command(name = "agent-chat", about = "File-based inter-agent communication")]
 pub struct Cli {
+    /// Project directory (or its .agent-chat/ directory) to use instead of
+    /// discovering one from the current directory; overrides AGENT_CHAT_ROOT.
+    /// Init, uninstall and doctor use it in place of the current directory
+    #[arg(long, global = true, value_name = "PATH")]
+    pub root: Option<PathBuf>,
     #[command(subcommand)]
     pub command: Command,
 }
@@ -29,6 +38,24 @@ pub enum Command {
         /// Install both Claude and Codex integrations
         #[arg(long = "both-tools")]
         both_tools: bool,
+        /// Install Continue.dev rules (.continue/rules/agent-chat.md)
+        #[arg(long = "continue")]
+        continue_: bool,
+        /// Install Gemini CLI guidance (GEMINI.md)
+        #[arg(long)]
+        gemini: bool,
+        /// Install Cursor rules (.cursor/rules/agent-chat.mdc)
+        #[arg(long)]
+        cursor: bool,
+        /// Install Windsurf rules (.windsurfrules)
+        #[arg(long)]
+        windsurf: bool,
+        /// Label this setup so its CLAUDE.md section coexists with other agent-chat setups (monorepos)
+        #[arg(long, value_name = "NAME")]
+        label: Option<String>,
+        /// Print what would be created or modified without writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Assign session identity (reads stdin JSON from hook, or use --session-id)
@@ -36,6 +63,56 @@ pub enum Command {
         /// Explicit session ID (for environments without SessionStart hooks)
         #[arg(long)]
         session_id: Option<String>,
+        /// Rename an already-registered session (posts a rename notice)
+        #[arg(long, value_name = "NEW_NAME")]
+        replace_name: Option<String>,
+        /// Name for a new session instead of a generated one (suffixed if taken)
+        #[arg(long, conflicts_with = "replace_name")]
+        name: Option<String>,
+        /// Print only `export` lines for the identity, e.g. for `eval "$(agent-chat register ... --print-env)"`
+        #[arg(long, conflicts_with = "replace_name")]
+        print_env: bool,
+    },
+
+    /// Change this session's name; past messages keep the old one
+    Rename {
+        /// New agent name
+        new_name: String,
+    },
+
+    /// Check the install (data dirs, config, hooks, CLAUDE.md) and suggest fixes
+    Doctor,
+
+    /// Remove installed hooks and guidance sections
+    Uninstall {
+        /// Remove from project files (.claude/settings.local.json, CLAUDE.md, AGENTS.md)
+        #[arg(long)]
+        project: bool,
+        /// Remove from user files (~/.claude/settings.json, ~/.claude/CLAUDE.md, ~/.codex/AGENTS.md)
+        #[arg(long)]
+        user: bool,
+        /// Remove from both project and user
+        #[arg(long)]
+        both: bool,
+        /// Also delete the .agent-chat/ directory and all message data
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// Remove a single tool's integration, leaving the others installed
+    UninstallTool {
+        /// Tool integration to remove
+        #[arg(value_enum)]
+        tool: Tool,
+        /// Remove from project files
+        #[arg(long)]
+        project: bool,
+        /// Remove from user files
+        #[arg(long)]
+        user: bool,
+        /// Remove from both project and user
+        #[arg(long)]
+        both: bool,
     },
 
     /// Install AGENTS.md guidance for Codex workflows
@@ -49,38 +126,251 @@ pub enum Command {
         /// Install to both project and user
         #[arg(long)]
         both: bool,
+        /// Print what would be created or modified without writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Post a message to the shared log
     Say {
+        /// Message text; `-` reads the message from stdin
+        message: Vec<String>,
+        /// Read the message body from a UTF-8 file
+        #[arg(long, value_name = "PATH", conflicts_with = "message")]
+        file: Option<std::path::PathBuf>,
+        /// Id of the message this replies to
+        #[arg(long)]
+        reply_to: Option<String>,
+        /// Prepend a short quote of the replied-to message
+        #[arg(long, requires = "reply_to")]
+        quote: bool,
+        /// Message priority; only messages at or above `status_block_priority` block the Stop hook
+        #[arg(long, value_enum)]
+        priority: Option<Priority>,
+        /// Tag the message with a structured status (rendered with an emoji prefix)
+        #[arg(long, value_enum)]
+        status: Option<AgentStatus>,
+        /// Send as a direct message visible only to this agent
+        #[arg(long)]
+        to: Option<String>,
+        /// Deliver after a delay instead of now (e.g. 30s, 10m, 2h)
+        #[arg(long = "in", value_name = "DURATION")]
+        delay: Option<String>,
+        /// Seconds until the message expires and is deleted (for ephemeral updates)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        ttl: Option<u64>,
+        /// Cut a message longer than `max_message_bytes` instead of rejecting it
+        #[arg(long)]
+        truncate: bool,
+        /// File the message is about, shown as `(re: <path>)` (repeatable)
+        #[arg(long = "ref", value_name = "PATH")]
+        refs: Vec<String>,
+    },
+
+    /// Reply to a message by id, threading it under the parent
+    Reply {
+        /// Id of the parent message (its timestamp filename stem)
+        id: String,
         /// Message text
         message: Vec<String>,
+        /// Prepend a short quote of the parent message
+        #[arg(long)]
+        quote: bool,
+    },
+
+    /// Send a high-priority direct message to one agent ("I'm blocked on you")
+    Notify {
+        /// Recipient's agent name
+        name: String,
+        /// Message text
+        message: Vec<String>,
+    },
+
+    /// Acknowledge a message by id with a short threaded reply
+    Ack {
+        /// Id of the message to acknowledge
+        id: String,
+    },
+
+    /// Show which agents have read a message, judged by their read cursors
+    Receipts {
+        /// Id of the message (its timestamp filename stem)
+        id: String,
     },
 
     /// Show unread messages (or all with --all)
     Read {
         /// Show all messages instead of just unread
-        #[arg(long)]
+        #[arg(long, conflicts_with = "watch")]
         all: bool,
+        /// Block until a new message from another agent arrives (exits 1 on timeout)
+        #[arg(long)]
+        watch: bool,
+        /// Seconds to wait with --watch
+        #[arg(long, requires = "watch", default_value_t = 60)]
+        timeout: u64,
+        /// Group messages into per-author sections instead of one interleaved stream
+        #[arg(long, conflicts_with = "watch")]
+        by_author: bool,
+        /// Only show messages at or above this priority (cursor still advances past all)
+        #[arg(long, value_enum)]
+        min_priority: Option<Priority>,
+        /// Messages to show on a first read (overrides `first_read_count`)
+        #[arg(long, conflicts_with_all = ["all", "watch"])]
+        count: Option<usize>,
+        /// Print messages as a JSON array
+        #[arg(long, conflicts_with_all = ["watch", "by_author"])]
+        json: bool,
+        /// Only show messages from this agent (cursor still advances past all)
+        #[arg(long, value_name = "NAME", conflicts_with = "watch")]
+        from: Option<String>,
+        /// Show messages from the last DURATION (e.g. 30m, 2h) regardless of read state; cursor untouched
+        #[arg(long, value_name = "DURATION", conflicts_with_all = ["all", "watch", "count"])]
+        since: Option<String>,
+    },
+
+    /// Show the full message history (does not advance the cursor)
+    History {
+        /// Only this agent's messages, with full timestamps
+        name: Option<String>,
+        /// Also include messages moved to the archive by `compact`
+        #[arg(long)]
+        include_archive: bool,
+    },
+
+    /// Find messages containing text (case-insensitive; does not advance the cursor)
+    Search {
+        /// Text to look for in message bodies
+        query: String,
+        /// Only messages from this agent
+        #[arg(long)]
+        author: Option<String>,
+        /// Only messages newer than this duration (e.g. 30m, 2h, 1d)
+        #[arg(long)]
+        since: Option<String>,
+    },
+
+    /// Export the whole chat as a Markdown transcript or JSON (does not advance the cursor)
+    Export {
+        /// Replace agent names with stable aliases (agent-1, agent-2, ...)
+        #[arg(long)]
+        anonymize: bool,
+        /// Redact path-like tokens matching this glob (repeatable)
+        #[arg(long, value_name = "GLOB")]
+        redact: Vec<String>,
+        /// Emit a JSON array instead of Markdown
+        #[arg(long)]
+        json: bool,
+        /// Write to this file instead of stdout
+        #[arg(long, value_name = "FILE")]
+        out: Option<std::path::PathBuf>,
+        /// Keep the "joined the chat" notices posted by register
+        #[arg(long)]
+        include_joins: bool,
+    },
+
+    /// Show messages posted between two points (message ids or timestamps)
+    Diff {
+        /// Start point: nanosecond message id or 'YYYY-MM-DD HH:MM[:SS]'
+        from: String,
+        /// End point: nanosecond message id or 'YYYY-MM-DD HH:MM[:SS]'
+        to: String,
+    },
+
+    /// Archive old messages out of the active log
+    Compact {
+        /// Archive messages older than this duration (e.g. 12h, 7d)
+        #[arg(long, default_value = "7d")]
+        older_than: String,
+        /// Delete old messages instead of archiving them
+        #[arg(long)]
+        delete: bool,
+        /// Also compact messages that a registered session has not read yet
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Reset parts of the chat state (messages, locks, focuses, cursors); sessions and config are kept
+    Clear {
+        /// Delete every message in the log
+        #[arg(long)]
+        messages: bool,
+        /// Remove all locks
+        #[arg(long)]
+        locks: bool,
+        /// Remove all focuses
+        #[arg(long)]
+        focuses: bool,
+        /// Reset every session's read cursor (mute lists are kept)
+        #[arg(long)]
+        cursors: bool,
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
 
     /// Check for unread messages (for Stop hook)
     Status,
 
+    /// Mark messages read without printing them
+    MarkRead {
+        /// Only mark messages up to and including this message id
+        #[arg(long, value_name = "MESSAGE_ID")]
+        to: Option<String>,
+    },
+
+    /// Hide messages from an agent for this session (no name: list muted agents)
+    Mute {
+        /// Agent name to mute
+        name: Option<String>,
+    },
+
+    /// Show messages from a muted agent again
+    Unmute {
+        /// Agent name to unmute
+        name: String,
+    },
+
     /// Acquire an advisory file lock
     Lock {
         /// Glob pattern to lock
         glob: String,
+        /// Lock lifetime in seconds (overrides lock_ttl_secs from config.toml)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        ttl: Option<u64>,
+        /// Refresh a lock you already hold, even after it expired
+        #[arg(long)]
+        renew: bool,
+        /// On conflict, wait up to this many seconds for the lock to be released or expire
+        #[arg(long, value_name = "SECS", conflicts_with = "renew")]
+        wait: Option<u64>,
+        /// Print the files the pattern matches instead of locking it
+        #[arg(long, conflicts_with_all = ["ttl", "renew", "wait"])]
+        list_files: bool,
     },
 
     /// Release an advisory file lock
     Unlock {
         /// Glob pattern to unlock
-        glob: String,
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        glob: Option<String>,
+        /// Release every lock held by this session
+        #[arg(long)]
+        all: bool,
+        /// Remove the lock even if another session holds it (for stale locks)
+        #[arg(long, requires = "glob")]
+        force: bool,
     },
 
     /// List active locks
-    Locks,
+    Locks {
+        /// Stable tab-separated output: owner, session_id, remaining_secs, glob
+        #[arg(long, conflicts_with = "json")]
+        porcelain: bool,
+        /// Print locks as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Check if a file is locked (PreToolUse hook, reads stdin JSON)
     CheckLock,
@@ -96,6 +386,9 @@ pub enum Command {
         /// Install to user (~/.claude/CLAUDE.md)
         #[arg(long)]
         user: bool,
+        /// Print what would be created or modified without writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Declare your focus area (or clear with --clear)
@@ -105,10 +398,59 @@ pub enum Command {
         /// Clear your current focus
         #[arg(long)]
         clear: bool,
+        /// Focus lifetime in seconds (overrides focus_ttl_secs from config.toml)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "clear")]
+        ttl: Option<u64>,
     },
 
     /// List active agent focuses
-    Focuses,
+    Focuses {
+        /// Stable tab-separated output: owner, session_id, remaining_secs, focus
+        #[arg(long, conflicts_with = "json")]
+        porcelain: bool,
+        /// Print focuses as JSON
+        #[arg(long)]
+        json: bool,
+        /// Show each agent's past focus areas with timestamps
+        #[arg(long, conflicts_with_all = ["porcelain", "json"])]
+        history: bool,
+    },
+
+    /// Summarize activity: agents, messages per agent, locks, and focuses
+    Stats {
+        /// Print the summary as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show each agent's status, focus, and locks in one view
+    Board {
+        /// Print the board as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List registered agents
+    Agents {
+        /// Stable tab-separated output: session_id, name
+        #[arg(long)]
+        porcelain: bool,
+        /// Roster order
+        #[arg(long, value_enum, default_value = "name")]
+        sort: AgentSort,
+    },
+
+    /// List chat rooms (the active one is marked with *)
+    Rooms,
+
+    /// Create, remove, or switch chat rooms
+    Room {
+        #[command(subcommand)]
+        action: RoomAction,
+    },
+
+    /// List ready br issues with who claimed them and overlapping focuses
+    BrList,
 
     /// Claim a br issue (sets in_progress + announces)
     BrClaim {
@@ -125,3 +467,22 @@ pub enum Command {
         reason: Option<String>,
     },
 }
+
+#[derive(Subcommand)]
+pub enum RoomAction {
+    /// Create a room with its own log, cursors, locks, and focuses
+    New {
+        /// Room name (lowercase letters, digits, dashes)
+        name: String,
+    },
+    /// Delete a room and its messages
+    Rm {
+        /// Room name
+        name: String,
+    },
+    /// Make a room active for all agents in this project ("default" for the built-in room)
+    Use {
+        /// Room name
+        name: String,
+    },
+}
@@ -0,0 +1,98 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use filetime::FileTime;
+use crate::error::Result;
+use crate::storage::{cursor, focus, paths, session};
+use crate::ui;
+
+/// Agents whose cursor has not moved for this long are shown as idle.
+const IDLE_AFTER_SECS: i64 = 10 * 60;
+
+/// Roster ordering for `agents --sort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AgentSort {
+    /// Alphabetical by friendly name
+    Name,
+    /// Most recently active first (cursor mtime)
+    Activity,
+    /// Earliest registration first (session file mtime)
+    Joined,
+}
+
+struct AgentRow {
+    session_id: String,
+    name: String,
+    joined: Option<FileTime>,
+    last_seen: Option<FileTime>,
+}
+
+impl AgentRow {
+    /// True if the agent has never read, or last read over `IDLE_AFTER_SECS` ago.
+    fn is_idle(&self, now: FileTime) -> bool {
+        match self.last_seen {
+            Some(seen) => now.unix_seconds() - seen.unix_seconds() > IDLE_AFTER_SECS,
+            None => true,
+        }
+    }
+}
+
+fn mtime(path: &Path) -> Option<FileTime> {
+    fs::metadata(path)
+        .ok()
+        .map(|m| FileTime::from_last_modification_time(&m))
+}
+
+/// List registered sessions. The table annotates each agent with its active
+/// focus and marks agents idle when their read cursor is stale.
+///
+/// Porcelain format (one agent per line, tab-separated, stable across releases):
+/// `<session_id>\t<name>`
+pub fn list(root: &Path, porcelain: bool, sort: AgentSort) -> Result<()> {
+    let sessions_dir = paths::sessions_dir(root);
+    let cursors_dir = paths::cursors_dir(root);
+
+    let mut agents: Vec<AgentRow> = session::list_sessions(&sessions_dir)?
+        .into_iter()
+        .map(|(session_id, name)| AgentRow {
+            joined: mtime(&sessions_dir.join(&session_id)),
+            last_seen: mtime(&cursor::cursor_path(&cursors_dir, &session_id)),
+            session_id,
+            name,
+        })
+        .collect();
+
+    match sort {
+        AgentSort::Name => agents.sort_by(|a, b| a.name.cmp(&b.name)),
+        // Never-seen agents (no cursor) sort last
+        AgentSort::Activity => agents.sort_by_key(|a| Reverse(a.last_seen)),
+        AgentSort::Joined => agents.sort_by_key(|a| a.joined),
+    }
+
+    if porcelain {
+        for a in &agents {
+            println!("{}\t{}", a.session_id, a.name);
+        }
+        return Ok(());
+    }
+
+    if agents.is_empty() {
+        println!("{}", ui::info_line("Agents:", "No registered agents."));
+        return Ok(());
+    }
+
+    let mut focuses: HashMap<String, String> = focus::list_active(&paths::focuses_dir(root))?
+        .into_iter()
+        .map(|f| (f.session_id, f.focus))
+        .collect();
+    let now = FileTime::now();
+
+    println!("{}", ui::table_header("SESSION", "NAME", Some("FOCUS")));
+    for a in &agents {
+        let focus = focuses.remove(&a.session_id).unwrap_or_else(|| "-".to_string());
+        let idle = if a.is_idle(now) { " (idle)" } else { "" };
+        println!("{:<30} {:<15} {}{}", a.session_id, a.name, focus, idle);
+    }
+    Ok(())
+}
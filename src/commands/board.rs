@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::error::Result;
+use crate::format::{self, AgentStatus};
+use crate::storage::{focus, lockfile, log, paths, session};
+use crate::ui;
+
+/// One agent's row on the board.
+#[derive(Default)]
+struct BoardRow {
+    session_id: String,
+    name: String,
+    focus: Option<String>,
+    locks: Vec<(String, u64)>,
+    status: Option<(AgentStatus, String)>,
+}
+
+/// Latest structured `status:` message per author, scanning newest first.
+fn latest_statuses(log_dir: &Path) -> Result<HashMap<String, (AgentStatus, String)>> {
+    let mut latest = HashMap::new();
+    for (_, path) in log::list_messages(log_dir)?.iter().rev() {
+        let Ok(content) = fs::read_to_string(path) else { continue };
+        if let Some(msg) = format::parse_message(&content) {
+            if let (Some(status), None) = (msg.status, msg.to) {
+                latest
+                    .entry(msg.name.to_string())
+                    .or_insert_with(|| (status, msg.body.to_string()));
+            }
+        }
+    }
+    Ok(latest)
+}
+
+/// Index of the row for `session_id`, adding one named `name` if missing.
+fn row_for(rows: &mut Vec<BoardRow>, session_id: &str, name: &str) -> usize {
+    match rows.iter().position(|r| r.session_id == session_id) {
+        Some(i) => i,
+        None => {
+            rows.push(BoardRow {
+                session_id: session_id.to_string(),
+                name: name.to_string(),
+                ..Default::default()
+            });
+            rows.len() - 1
+        }
+    }
+}
+
+/// Combine registered sessions, active locks, and active focuses into one row
+/// per session. Lock and focus holders that never registered still appear.
+fn collect(root: &Path) -> Result<Vec<BoardRow>> {
+    let mut rows: Vec<BoardRow> = Vec::new();
+
+    for (session_id, name) in session::list_sessions(&paths::sessions_dir(root))? {
+        row_for(&mut rows, &session_id, &name);
+    }
+    for lock in lockfile::list_active(&paths::locks_dir(root))? {
+        let i = row_for(&mut rows, &lock.session_id, &lock.owner);
+        let remaining = lock.remaining_secs();
+        rows[i].locks.push((lock.glob, remaining));
+    }
+    for f in focus::list_active(&paths::focuses_dir(root))? {
+        let i = row_for(&mut rows, &f.session_id, &f.owner);
+        rows[i].focus = Some(f.focus);
+    }
+
+    let mut statuses = latest_statuses(&paths::log_dir(root))?;
+    for row in &mut rows {
+        row.status = statuses.remove(&row.name);
+        row.locks.sort();
+    }
+    rows.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.session_id.cmp(&b.session_id)));
+    Ok(rows)
+}
+
+/// Print the coordination board: each agent with its status, focus, and locks.
+pub fn show(root: &Path, as_json: bool) -> Result<()> {
+    let rows = collect(root)?;
+
+    if as_json {
+        let agents: Vec<Value> = rows
+            .iter()
+            .map(|r| {
+                json!({
+                    "session_id": r.session_id,
+                    "name": r.name,
+                    "status": r.status.as_ref().map(|(s, text)| json!({"state": s.as_str(), "text": text})),
+                    "focus": r.focus,
+                    "locks": r.locks.iter().map(|(glob, secs)| json!({"glob": glob, "remaining_secs": secs})).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json!({ "agents": agents }))?);
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("{}", ui::info_line("Board:", "No registered agents."));
+        return Ok(());
+    }
+
+    for (i, r) in rows.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{} ({})", r.name, r.session_id);
+        if let Some((status, text)) = &r.status {
+            println!("  status: {}: {}", status.label(), text);
+        }
+        println!("  focus:  {}", r.focus.as_deref().unwrap_or("-"));
+        if r.locks.is_empty() {
+            println!("  locks:  -");
+        } else {
+            let locks: Vec<String> = r
+                .locks
+                .iter()
+                .map(|(glob, secs)| format!("{} ({}s)", glob, secs))
+                .collect();
+            println!("  locks:  {}", locks.join(", "));
+        }
+    }
+    Ok(())
+}
@@ -1,41 +1,118 @@
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 use crate::error::{AgentChatError, Result};
+use crate::format;
+use crate::storage::{config, focus, log, paths};
+use crate::ui;
 
 /// Check that `br` is available on PATH. Returns a friendly error if not.
 pub fn require_br_in_path() -> Result<()> {
     let output = Command::new("br")
         .arg("--version")
         .output()
-        .map_err(|_| AgentChatError::Other(
-            "br (beads_rust) not found in PATH. Install it first: cargo install beads_rust".to_string()
-        ))?;
+        .map_err(|_| AgentChatError::BrNotFound)?;
 
     if !output.status.success() {
-        return Err(AgentChatError::Other(
-            "br (beads_rust) not found in PATH. Install it first: cargo install beads_rust".to_string()
-        ));
+        return Err(AgentChatError::BrNotFound);
     }
 
     Ok(())
 }
 
+/// Run `br <args>` and return its stdout. `args[0]` names the subcommand in
+/// errors; a failed run carries br's stderr.
+pub fn run_br(args: &[&str]) -> Result<Vec<u8>> {
+    let failed = |detail: String| AgentChatError::BrCommandFailed { command: args[0].to_string(), detail };
+    let output = Command::new("br").args(args).output().map_err(|e| failed(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(failed(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(output.stdout)
+}
+
+/// Run `br <args>` and parse its stdout as JSON.
+fn br_json(args: &[&str]) -> Result<serde_json::Value> {
+    Ok(serde_json::from_slice(&run_br(args)?)?)
+}
+
+fn issue_title(issue: &serde_json::Value) -> String {
+    issue["title"].as_str().unwrap_or("(untitled)").to_string()
+}
+
 /// Get the title of a br issue by its ID.
 pub fn get_issue_title(id: &str) -> Result<String> {
-    let output = Command::new("br")
-        .args(["show", id, "--json"])
-        .output()
-        .map_err(|e| AgentChatError::Other(format!("Failed to run br show: {}", e)))?;
+    Ok(issue_title(&br_json(&["show", id, "--json"])?))
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AgentChatError::Other(format!("br show failed: {}", stderr.trim())));
+/// Print ready beads with who has claimed each (via `br-claim` announcements
+/// not yet followed by `br-complete`, or br's own assignee) and which agents
+/// are focused on overlapping work.
+pub fn list(root: &Path) -> Result<()> {
+    require_br_in_path()?;
+
+    let ready = br_json(&["ready", "--json"])?;
+    // Accept a bare array or an object wrapping one
+    let issues = ready
+        .as_array()
+        .or_else(|| ready["issues"].as_array())
+        .cloned()
+        .unwrap_or_default();
+    if issues.is_empty() {
+        println!("No ready beads.");
+        return Ok(());
     }
 
-    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
-    let title = json["title"]
-        .as_str()
-        .unwrap_or("(untitled)")
-        .to_string();
+    let claims = active_claims(&paths::log_dir(root))?;
+    let session_id = std::env::var("AGENT_CHAT_SESSION_ID").unwrap_or_default();
+    let focuses_dir = paths::focuses_dir(root);
+    let config = config::read_config(&paths::config_path(root))?;
+
+    println!("{}", ui::table_header("BEAD", "CLAIMED BY", Some("TITLE")));
+    for issue in &issues {
+        let id = match &issue["id"] {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let title = issue_title(issue);
+        let claimed_by = claims
+            .iter()
+            .find(|(claimed, _)| *claimed == id)
+            .map(|(_, name)| name.as_str())
+            .or_else(|| issue["assignee"].as_str().filter(|a| !a.is_empty()))
+            .unwrap_or("-");
 
-    Ok(title)
+        let mut line = format!("{:<30} {:<15} {}", id, claimed_by, title);
+        if let Ok(overlaps) = focus::find_overlapping(&focuses_dir, &title, &session_id, config.focus_overlap_min_tokens) {
+            for (o, score) in &focus::rank_overlaps(overlaps, config.focus_overlap_min_score) {
+                line.push_str(&format!("  [{} focused on '{}', {}%]", o.owner, o.focus, focus::score_percent(*score)));
+            }
+        }
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// `(bead id, claimer)` for every `starting br-<id>` announcement without a
+/// later `completed br-<id>`.
+fn active_claims(log_dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut claims: Vec<(String, String)> = Vec::new();
+    for (_, path) in log::list_messages(log_dir)? {
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        let Some(msg) = format::parse_message(&content) else { continue };
+        let bead = |prefix: &str| {
+            msg.body
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.split_once(':'))
+                .map(|(id, _)| id.to_string())
+        };
+        if let Some(id) = bead("starting br-") {
+            claims.retain(|(claimed, _)| *claimed != id);
+            claims.push((id, msg.name.to_string()));
+        } else if let Some(id) = bead("completed br-") {
+            claims.retain(|(claimed, _)| *claimed != id);
+        }
+    }
+    Ok(claims)
 }
@@ -1,7 +1,8 @@
 use std::path::Path;
-use std::process::Command;
 use crate::commands::{br, say};
-use crate::error::{AgentChatError, Result};
+use crate::error::Result;
+use crate::storage::{config, paths};
+use crate::ui;
 
 pub fn run(root: &Path, id: &str, reason: Option<&str>) -> Result<()> {
     br::require_br_in_path()?;
@@ -9,18 +10,17 @@ pub fn run(root: &Path, id: &str, reason: Option<&str>) -> Result<()> {
     // Get title before closing
     let title = br::get_issue_title(id)?;
 
-    let mut cmd = Command::new("br");
-    cmd.args(["close", id]);
+    let mut args = vec!["close", id];
     if let Some(r) = reason {
-        cmd.args(["--reason", r]);
+        args.extend(["--reason", r]);
     }
+    br::run_br(&args)?;
 
-    let output = cmd.output()
-        .map_err(|e| AgentChatError::Other(format!("Failed to run br close: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AgentChatError::Other(format!("br close failed: {}", stderr.trim())));
+    // The issue is closed; a failed export only warrants a warning
+    if config::read_config(&paths::config_path(root))?.br_auto_sync {
+        if let Err(e) = br::run_br(&["sync", "--flush-only"]) {
+            eprintln!("{}", ui::warning(&format!("WARNING: {}. Run `br sync --flush-only` manually.", e)));
+        }
     }
 
     say::run(root, &format!("completed br-{}: {}", id, title))?;
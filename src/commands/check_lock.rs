@@ -1,8 +1,9 @@
 use std::path::Path;
 use serde_json::json;
+use crate::commands::lock;
 use crate::error::Result;
 use crate::hooks::stdin;
-use crate::storage::{lockfile, paths};
+use crate::storage::{config, identity, lockfile, log, paths};
 
 pub fn run(root: &Path) -> Result<()> {
     let session_id = match std::env::var("AGENT_CHAT_SESSION_ID") {
@@ -12,26 +13,105 @@ pub fn run(root: &Path) -> Result<()> {
 
     let input = stdin::read_pre_tool_use()?;
 
-    // Extract file_path from tool_input
-    let file_path = match input.tool_input.get("file_path").and_then(|v| v.as_str()) {
-        Some(p) => p,
-        None => return Ok(()), // No file path in input
-    };
+    // Edit/Write/MultiEdit name a file_path, NotebookEdit a notebook_path,
+    // and MultiEdit may carry more in its edits
+    let file_paths = input.file_paths();
+    if file_paths.is_empty() {
+        return Ok(()); // No file path in input
+    }
+
+    let _ = lock::warn_expiring(root);
 
     let locks_dir = paths::locks_dir(root);
-    if let Some(lock) = lockfile::check_file(&locks_dir, file_path, &session_id)? {
-        // Output hookSpecificOutput JSON to warn the agent
-        let warning = json!({
-            "hookSpecificOutput": {
-                "message": format!(
+    let mut conflicts = Vec::new();
+    let mut notes = Vec::new();
+    for file_path in file_paths {
+        // Lock globs are project-relative; a file outside the project is
+        // matched as given, so only absolute globs can claim it
+        let file_path = paths::project_relative(root, file_path).unwrap_or_else(|| file_path.to_string());
+        if let Some(lock) = lockfile::check_file(&locks_dir, &file_path, &session_id)? {
+            conflicts.push((file_path, lock));
+        } else if let Some(lock) = lockfile::take_takeover(&locks_dir, &file_path, &session_id)? {
+            let previous = lock.previous_owner.as_deref().unwrap_or_default();
+            notes.push(format!(
+                "NOTE: {}. {} may still have edits in flight; check with them.",
+                takeover_note(&lock),
+                previous
+            ));
+        }
+    }
+
+    if conflicts.is_empty() {
+        if !notes.is_empty() {
+            let output = json!({
+                "hookSpecificOutput": {
+                    "message": notes.join("\n")
+                }
+            });
+            print!("{}", serde_json::to_string(&output)?);
+        }
+        // Silent when no lock conflict
+        return Ok(());
+    }
+
+    let config = config::read_config(&paths::config_path(root))?;
+    let mut reasons: Vec<String> = conflicts
+        .iter()
+        .map(|(file_path, lock)| {
+            if lock.previous_session_id.as_deref() == Some(session_id.as_str()) {
+                // Our own claim lapsed and someone else now holds the pattern
+                format!("WARNING: {}. Coordinate before editing {}.", takeover_note(lock), file_path)
+            } else {
+                format!(
                     "WARNING: {} is locked by {} (pattern: {}). Coordinate before editing.",
                     file_path, lock.owner, lock.glob
                 )
             }
-        });
-        print!("{}", serde_json::to_string(&warning)?);
+        })
+        .collect();
+    reasons.extend(notes);
+    let reason = reasons.join("\n");
+
+    // Blocking mode mirrors `status`; otherwise a hookSpecificOutput advisory
+    let output = if config.check_lock_block {
+        json!({
+            "decision": "block",
+            "reason": reason
+        })
+    } else {
+        json!({
+            "hookSpecificOutput": {
+                "message": reason
+            }
+        })
+    };
+    print!("{}", serde_json::to_string(&output)?);
+
+    if config.checklock_notify_owner {
+        if let Some(name) = identity::resolve(root).ok().and_then(|id| id.name) {
+            // One notice per lock, however often the edit is retried
+            for (file_path, lock) in &conflicts {
+                if !lockfile::take_notify(&locks_dir, &lock.glob, &session_id)? {
+                    continue;
+                }
+                log::write_message(
+                    &paths::log_dir(root),
+                    &name,
+                    &format!("@{} I need to edit {} which you've locked ({})", lock.owner, file_path, lock.glob),
+                )?;
+            }
+        }
     }
-    // Silent when no lock conflict
 
     Ok(())
 }
+
+/// Describe a lock that replaced another session's expired lock.
+fn takeover_note(lock: &lockfile::LockEntry) -> String {
+    format!(
+        "{}'s lock on {} expired and was taken over by {}",
+        lock.previous_owner.as_deref().unwrap_or_default(),
+        lock.glob,
+        lock.owner
+    )
+}
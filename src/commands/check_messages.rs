@@ -1,13 +1,48 @@
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use serde_json::json;
 use crate::error::Result;
 use crate::format;
-use crate::storage::{cursor, identity, paths};
+use crate::storage::{config, cursor, identity, paths};
 
-const DEFAULT_FIRST_READ_COUNT: usize = 5;
+/// One-line summary of a backlog: `[agent-chat: 12 unread messages from 3 agents — ...]`.
+fn digest(message_paths: &[PathBuf]) -> String {
+    let authors: BTreeSet<String> = message_paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| format::parse_message(&content).map(|m| m.name.to_string()))
+        .collect();
+    let agents = if authors.len() == 1 { "agent" } else { "agents" };
+    format!(
+        "[agent-chat: {} unread messages from {} {} — run `agent-chat read` to see them]",
+        message_paths.len(),
+        authors.len(),
+        agents
+    )
+}
+
+/// Note that a digest is being shown for the backlog ending at its newest
+/// message, stored as `<message_id>` in `<session>.digest` next to the cursor.
+/// Returns false if this backlog was already digested.
+fn record_digest(cursors_dir: &Path, session_id: &str, message_paths: &[PathBuf]) -> Result<bool> {
+    let newest = message_paths
+        .last()
+        .and_then(|p| p.file_stem())
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let state_file = cursors_dir.join(format!("{}.digest", session_id));
+    if fs::read_to_string(&state_file).is_ok_and(|content| content.trim() == newest) {
+        return Ok(false);
+    }
+    fs::write(&state_file, format!("{}\n", newest))?;
+    Ok(true)
+}
 
 /// PreToolUse hook: inject unread messages into agent context via additionalContext.
-/// Advances the cursor so the same messages aren't delivered again.
+/// Advances the cursor so the same messages aren't delivered again. Backlogs
+/// over `check_messages_digest_threshold` get a one-line digest instead and
+/// stay unread for `read`; the digest is shown once per backlog, not on every call.
 pub fn run(root: &Path) -> Result<()> {
     let id = match identity::resolve(root) {
         Ok(id) => id,
@@ -21,12 +56,27 @@ pub fn run(root: &Path) -> Result<()> {
     let cursors_dir = paths::cursors_dir(root);
     let cursor_file = cursor::cursor_path(&cursors_dir, &id.session_id);
 
-    let message_paths = cursor::get_unread_messages(&log_dir, &cursor_file, DEFAULT_FIRST_READ_COUNT, exclude)?;
+    let config = config::read_config(&paths::config_path(root))?;
+    let message_paths = cursor::get_unread_messages(&log_dir, &cursor_file, config.first_read_count, exclude)?;
 
     if message_paths.is_empty() {
         return Ok(());
     }
 
+    let threshold = config.check_messages_digest_threshold;
+    if threshold > 0 && message_paths.len() > threshold {
+        if !record_digest(&cursors_dir, &id.session_id, &message_paths)? {
+            return Ok(());
+        }
+        let output = json!({
+            "hookSpecificOutput": {
+                "additionalContext": digest(&message_paths)
+            }
+        });
+        print!("{}", serde_json::to_string(&output)?);
+        return Ok(());
+    }
+
     let formatted = format::format_messages_from_paths(&message_paths);
     if formatted.is_empty() {
         return Ok(());
@@ -0,0 +1,93 @@
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use crate::error::{AgentChatError, Result};
+use crate::storage::{log, paths};
+use crate::ui;
+
+/// Which parts of `.agent-chat/` to reset.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ClearTargets {
+    pub messages: bool,
+    pub locks: bool,
+    pub focuses: bool,
+    pub cursors: bool,
+}
+
+impl ClearTargets {
+    fn labels(&self) -> Vec<&'static str> {
+        [
+            (self.messages, "messages"),
+            (self.locks, "locks"),
+            (self.focuses, "focuses"),
+            (self.cursors, "cursors"),
+        ]
+        .into_iter()
+        .filter_map(|(on, label)| on.then_some(label))
+        .collect()
+    }
+}
+
+/// Reset the selected subsystems of the active room, leaving sessions and
+/// config alone. Asks for confirmation on stderr unless `yes` is set.
+pub fn run(root: &Path, targets: ClearTargets, yes: bool) -> Result<()> {
+    let labels = targets.labels();
+    if labels.is_empty() {
+        return Err(AgentChatError::Other(
+            "Specify what to clear: --messages, --locks, --focuses, and/or --cursors.".to_string(),
+        ));
+    }
+    if !yes && !confirm(&format!("Clear {} in {}?", labels.join(", "), root.display()))? {
+        println!("Aborted; nothing was cleared.");
+        return Ok(());
+    }
+
+    if targets.messages {
+        let deleted = log::delete_all(&paths::log_dir(root))?;
+        println!("{}", ui::success_line("Cleared:", &format!("{} message(s)", deleted)));
+    }
+    if targets.locks {
+        let removed = remove_files(&paths::locks_dir(root), |_| true)?;
+        println!("{}", ui::success_line("Cleared:", &format!("{} lock(s)", removed)));
+    }
+    if targets.focuses {
+        // Focus history is a record of past work, not live state
+        let removed = remove_files(&paths::focuses_dir(root), |name| name.ends_with(".focus"))?;
+        println!("{}", ui::success_line("Cleared:", &format!("{} focus(es)", removed)));
+    }
+    if targets.cursors {
+        // Mute lists are preferences, not read state
+        let removed = remove_files(&paths::cursors_dir(root), |name| !name.ends_with(".mutes"))?;
+        println!("{}", ui::success_line("Cleared:", &format!("{} cursor file(s)", removed)));
+    }
+    Ok(())
+}
+
+/// Ask a yes/no question on stderr; anything but `y`/`yes` (or no input) is no.
+fn confirm(question: &str) -> Result<bool> {
+    eprint!("{} [y/N] > ", question);
+    io::stderr().flush()?;
+    let line = io::stdin().lock().lines().next().transpose()?.unwrap_or_default();
+    Ok(matches!(line.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Remove the regular files in `dir` that `select` picks, skipping in-flight
+/// `.tmp.` files. Returns how many were removed.
+fn remove_files(dir: &Path, select: impl Fn(&str) -> bool) -> Result<usize> {
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+    let mut removed = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(".tmp.") || !entry.file_type()?.is_file() || !select(&name) {
+            continue;
+        }
+        // Ignore ENOENT race with a concurrent unlock or clear
+        if fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
@@ -0,0 +1,53 @@
+use std::path::Path;
+use crate::error::Result;
+use crate::format;
+use crate::storage::{archive, cursor, log, paths, session};
+use crate::ui;
+
+/// Oldest read position among registered sessions that have a cursor.
+/// Messages after it are still unread for someone.
+fn oldest_cursor(root: &Path) -> Result<Option<u128>> {
+    let cursors_dir = paths::cursors_dir(root);
+    let oldest = session::list_sessions(&paths::sessions_dir(root))?
+        .iter()
+        .map(|(session_id, _)| cursor::cursor_path(&cursors_dir, session_id))
+        .filter(|path| path.exists())
+        .map(|path| cursor::position(&path))
+        .min();
+    Ok(oldest)
+}
+
+pub fn run(root: &Path, older_than: &str, delete: bool, force: bool) -> Result<()> {
+    let requested_ns = format::cutoff_ns(older_than)?;
+
+    let log_dir = paths::log_dir(root);
+    let archive_dir = paths::archive_dir(root);
+    // Expired ephemeral messages are dropped, not archived
+    log::prune_expired(&log_dir)?;
+
+    // Leave anything a registered session hasn't read yet in place
+    let cutoff_ns = match oldest_cursor(root)? {
+        Some(oldest) if !force => requested_ns.min(oldest + 1),
+        _ => requested_ns,
+    };
+    let kept = log::list_messages(&log_dir)?
+        .iter()
+        .filter_map(|(filename, _)| filename.trim_end_matches(".md").parse::<u128>().ok())
+        .filter(|ts| (cutoff_ns..requested_ns).contains(ts))
+        .count();
+
+    if delete {
+        let deleted = log::delete_older_than(&log_dir, cutoff_ns)?;
+        println!("{}", ui::success_line("Compacted:", &format!("{} message(s) deleted", deleted)));
+    } else {
+        let archived = archive::compact(&log_dir, &archive_dir, cutoff_ns)?;
+        println!("{}", ui::success_line("Compacted:", &format!("{} message(s) archived", archived)));
+    }
+    if kept > 0 {
+        println!(
+            "Kept {} message(s) that a session has not read yet (use --force to compact them).",
+            kept
+        );
+    }
+    Ok(())
+}
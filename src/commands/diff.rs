@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::Path;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use crate::error::{AgentChatError, Result};
+use crate::format;
+use crate::storage::{identity, log, paths};
+
+/// Parse a point in time: a nanosecond message id, an RFC 3339 timestamp,
+/// or a local `YYYY-MM-DD HH:MM[:SS]` timestamp. Returns nanoseconds since epoch.
+fn parse_point(s: &str) -> Option<u128> {
+    let s = s.trim();
+    if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        return s.parse().ok();
+    }
+    let dt = DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.timestamp_nanos_opt())
+        .ok()
+        .or_else(|| {
+            ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+                .iter()
+                .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+                .and_then(|naive| Local.from_local_datetime(&naive).single())
+                .map(|dt| dt.timestamp_nanos_opt())
+        })??;
+    u128::try_from(dt).ok()
+}
+
+/// Print everything that happened between two points, inclusive.
+/// Only messages are covered; lock and focus changes are not recorded.
+/// Direct messages between other agents are left out.
+pub fn run(root: &Path, from: &str, to: &str) -> Result<()> {
+    let reader = identity::resolve(root).ok().and_then(|id| id.name);
+    let parse = |s: &str| {
+        parse_point(s).ok_or_else(|| {
+            AgentChatError::Other(format!(
+                "invalid point: {} (use a message id or 'YYYY-MM-DD HH:MM[:SS]')",
+                s
+            ))
+        })
+    };
+    let (mut start, mut end) = (parse(from)?, parse(to)?);
+    if start > end {
+        std::mem::swap(&mut start, &mut end);
+    }
+
+    for (filename, path) in log::list_messages(&paths::log_dir(root))? {
+        let id = filename.trim_end_matches(".md");
+        match id.parse::<u128>() {
+            Ok(ts) if ts >= start && ts <= end => {}
+            _ => continue,
+        }
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Some(msg) = format::parse_message(&content) {
+                if !msg.is_visible_to(reader.as_deref()) {
+                    continue;
+                }
+                let ts = format::parse_timestamp_ns(id);
+                println!("{}", format::format_parsed(&msg, ts));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_point_accepts_ids_and_timestamps() {
+        assert_eq!(parse_point("1736950200000000000"), Some(1736950200000000000));
+        assert_eq!(
+            parse_point("2025-01-15T14:10:00Z"),
+            Some(1736950200000000000)
+        );
+        assert!(parse_point("2025-01-15 14:10").is_some());
+        assert_eq!(parse_point("yesterday"), None);
+    }
+}
@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::commands::br;
+use crate::error::{AgentChatError, Result};
+use crate::hooks::{claude_md, claude_md_br, installer};
+use crate::storage::{config, paths};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Level {
+    Pass,
+    Warn,
+    Fail,
+}
+
+struct Check {
+    level: Level,
+    name: &'static str,
+    detail: String,
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Check { level: Level::Pass, name, detail: detail.into(), hint: None }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check { level: Level::Warn, name, detail: detail.into(), hint: Some(hint.into()) }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Check { level: Level::Fail, name, detail: detail.into(), hint: Some(hint.into()) }
+    }
+}
+
+/// Run every install check from `cwd`, print one line per check, and fail
+/// only when at least one hard failure was found. `explicit_root` (`--root`)
+/// names the chat to check instead of discovering one.
+pub fn run(cwd: &Path, explicit_root: Option<&Path>) -> Result<()> {
+    let root = match explicit_root {
+        Some(path) => paths::explicit_root(path).ok(),
+        None => paths::discover_root(cwd).ok(),
+    };
+    let project_root = root
+        .as_deref()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| cwd.to_path_buf());
+
+    let mut checks = Vec::new();
+    match &root {
+        Some(root) => {
+            checks.push(check_dirs(root));
+            checks.push(check_config(root));
+        }
+        None => checks.push(Check::fail(
+            "data",
+            "no .agent-chat/ directory found",
+            "run `agent-chat init` in the project root",
+        )),
+    }
+    checks.push(check_hooks(&project_root));
+    let claude_md = fs::read_to_string(project_root.join("CLAUDE.md")).unwrap_or_default();
+    let label = root
+        .as_deref()
+        .and_then(|root| config::read_config(&paths::config_path(root)).ok())
+        .and_then(|config| config.label().map(str::to_string));
+    checks.push(check_claude_md(&claude_md, label.as_deref()));
+    if claude_md.contains(claude_md_br::BR_START_SENTINEL) {
+        checks.push(check_br());
+    }
+
+    for check in &checks {
+        let label = match check.level {
+            Level::Pass => "pass",
+            Level::Warn => "warn",
+            Level::Fail => "FAIL",
+        };
+        println!("[{}] {}: {}", label, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("       fix: {}", hint);
+        }
+    }
+
+    let failed = checks.iter().filter(|c| c.level == Level::Fail).count();
+    if failed > 0 {
+        return Err(AgentChatError::DoctorFailed(failed));
+    }
+    Ok(())
+}
+
+fn check_dirs(root: &Path) -> Check {
+    let dirs = [
+        paths::log_dir(root),
+        paths::locks_dir(root),
+        paths::cursors_dir(root),
+        paths::focuses_dir(root),
+        paths::sessions_dir(root),
+    ];
+    let missing: Vec<String> = dirs
+        .iter()
+        .filter(|d| !d.is_dir())
+        .map(|d| d.strip_prefix(root).unwrap_or(d).display().to_string())
+        .collect();
+    if missing.is_empty() {
+        Check::pass("data", format!("{} has all its directories", root.display()))
+    } else {
+        Check::fail(
+            "data",
+            format!("missing {}", missing.join(", ")),
+            "run `agent-chat init` to recreate them",
+        )
+    }
+}
+
+fn check_config(root: &Path) -> Check {
+    let path = paths::config_path(root);
+    if !path.exists() {
+        return Check::warn(
+            "config",
+            "no config.toml, using defaults",
+            "run `agent-chat init` to write a documented default config",
+        );
+    }
+    match config::read_config(&path) {
+        Ok(_) => Check::pass("config", "config.toml parses"),
+        Err(e) => Check::fail(
+            "config",
+            format!("config.toml does not parse: {}", e),
+            format!("fix or delete {}", path.display()),
+        ),
+    }
+}
+
+fn check_hooks(project_root: &Path) -> Check {
+    let path = project_root.join(".claude/settings.local.json");
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Check::warn(
+            "hooks",
+            "no .claude/settings.local.json",
+            "run `agent-chat init --project` (skip if hooks are installed in ~/.claude/settings.json)",
+        );
+    };
+    let settings = match installer::parse_lenient(&content) {
+        Some(v) => v,
+        None => {
+            return Check::fail(
+                "hooks",
+                "settings.local.json is not valid JSON",
+                format!("fix {} and re-run `agent-chat init --project`", path.display()),
+            )
+        }
+    };
+
+    let commands = installer::agent_chat_hook_commands(&settings);
+    if commands.is_empty() {
+        return Check::warn(
+            "hooks",
+            "settings.local.json has no agent-chat hooks",
+            "run `agent-chat init --project`",
+        );
+    }
+    let broken: Vec<&str> = commands
+        .iter()
+        .filter_map(|c| c.split_whitespace().next())
+        .filter(|program| !program_exists(program))
+        .collect();
+    if let Some(program) = broken.first() {
+        return Check::fail(
+            "hooks",
+            format!("hook binary {} does not exist", program),
+            "re-run `agent-chat init --project` from the installed binary",
+        );
+    }
+    let missing: Vec<&str> = installer::HOOK_SUBCOMMANDS
+        .iter()
+        .copied()
+        .filter(|sub| !commands.iter().any(|c| c.split_whitespace().nth(1) == Some(*sub)))
+        .collect();
+    if !missing.is_empty() {
+        return Check::warn(
+            "hooks",
+            format!("missing hook(s): {}", missing.join(", ")),
+            "run `agent-chat init --project`",
+        );
+    }
+    Check::pass("hooks", "all agent-chat hooks installed")
+}
+
+/// True if `program` is a path that exists, or a bare name found on PATH.
+fn program_exists(program: &str) -> bool {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+    std::env::var_os("PATH")
+        .map(|p| std::env::split_paths(&p).any(|dir: PathBuf| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+fn check_claude_md(content: &str, label: Option<&str>) -> Check {
+    let (start_sentinel, end_sentinel) = claude_md::sentinels(label);
+    let start = content.contains(&start_sentinel);
+    let end = content.contains(&end_sentinel);
+    match (start, end) {
+        (true, true) => Check::pass("claude.md", "agent-chat section present"),
+        (false, false) => Check::warn(
+            "claude.md",
+            "no agent-chat section in CLAUDE.md",
+            "run `agent-chat init --project` (skip if it is in ~/.claude/CLAUDE.md)",
+        ),
+        _ => Check::fail(
+            "claude.md",
+            "agent-chat section has mismatched start/end markers",
+            "remove the partial section and re-run `agent-chat init --project`",
+        ),
+    }
+}
+
+fn check_br() -> Check {
+    match br::require_br_in_path() {
+        Ok(()) => Check::pass("br", "br is on PATH"),
+        Err(_) => Check::fail(
+            "br",
+            "br guidance is installed but br is not on PATH",
+            "cargo install beads_rust",
+        ),
+    }
+}
@@ -0,0 +1,271 @@
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde_json::json;
+use crate::error::{AgentChatError, Result};
+use crate::format;
+use crate::storage::{identity, log, paths, session};
+use crate::ui;
+
+/// Replacement for tokens matching a `--redact` pattern.
+const REDACTED: &str = "[redacted]";
+
+/// Bodies `register` posts on its own; left out unless `--include-joins`.
+const JOIN_BODIES: [&str; 3] = ["joined the chat", "rejoined the chat", "resumed the chat"];
+
+/// Options for transforming an export before it is shared.
+#[derive(Debug, Default)]
+pub struct ExportOptions<'a> {
+    /// Replace agent names with stable `agent-N` aliases.
+    pub anonymize: bool,
+    /// Glob patterns; matching path-like tokens in bodies become `[redacted]`.
+    pub redact: &'a [String],
+    /// Emit a JSON array instead of a Markdown transcript.
+    pub json: bool,
+    /// Write to this file instead of stdout.
+    pub out: Option<PathBuf>,
+    /// Keep the join/resume notices `register` posts.
+    pub include_joins: bool,
+}
+
+/// Maps real agent names to `agent-1`, `agent-2`, ... in order of first appearance.
+#[derive(Debug, Default)]
+struct Anonymizer {
+    aliases: Vec<(String, String)>,
+}
+
+impl Anonymizer {
+    fn alias(&mut self, name: &str) -> String {
+        if let Some((_, alias)) = self.aliases.iter().find(|(real, _)| real == name) {
+            return alias.clone();
+        }
+        let alias = format!("agent-{}", self.aliases.len() + 1);
+        self.aliases.push((name.to_string(), alias.clone()));
+        alias
+    }
+
+    /// Replace every known name appearing as a whole word in `text`, in one
+    /// left-to-right scan so an alias is never rewritten again (`agent-2`
+    /// aliased to `agent-1` stays put when `agent-1` is itself aliased).
+    /// The longest name wins at each position, so `reviewer-2` isn't
+    /// rewritten as `agent-1-2`.
+    fn scrub(&self, text: &str) -> String {
+        let mut by_length: Vec<_> = self.aliases.iter().collect();
+        by_length.sort_by_key(|(real, _)| std::cmp::Reverse(real.len()));
+        let mut out = String::with_capacity(text.len());
+        let mut prev: Option<char> = None;
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let hit = if prev.is_some_and(is_word_char) {
+                None
+            } else {
+                by_length.iter().find(|(real, _)| {
+                    rest.starts_with(real.as_str()) && !rest[real.len()..].chars().next().is_some_and(is_word_char)
+                })
+            };
+            let consumed = match hit {
+                Some((real, alias)) => {
+                    out.push_str(alias);
+                    real.len()
+                }
+                None => {
+                    out.push(c);
+                    c.len_utf8()
+                }
+            };
+            prev = rest[..consumed].chars().next_back();
+            rest = &rest[consumed..];
+        }
+        out
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn build_redactor(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern)
+            .map_err(|e| AgentChatError::Other(format!("invalid redact pattern {}: {}", pattern, e)))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| AgentChatError::Other(format!("invalid redact patterns: {}", e)))
+}
+
+/// Replace whitespace-separated tokens that match `redactor`, ignoring
+/// surrounding quotes and punctuation.
+fn redact(text: &str, redactor: &GlobSet) -> String {
+    text.split_inclusive(char::is_whitespace)
+        .map(|chunk| {
+            let token = chunk.trim_end();
+            let core = token.trim_matches(|c: char| "\"'`()[]{}<>,;:".contains(c)).trim_end_matches('.');
+            if !core.is_empty() && redactor.is_match(core) {
+                chunk.replacen(core, REDACTED, 1)
+            } else {
+                chunk.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Write the chat as a chronological Markdown transcript, one
+/// `### author — YYYY-MM-DD HH:MM:SS` section per message, or with `json` as
+/// an array of `{id, name, timestamp, body, ...}` objects. Read-only.
+/// Direct messages between other agents are left out. Messages are read and
+/// written one at a time, so large logs are never held in memory.
+pub fn run(root: &Path, opts: &ExportOptions) -> Result<()> {
+    let reader = identity::resolve(root).ok().and_then(|id| id.name);
+    let redactor = build_redactor(opts.redact)?;
+    let messages = log::list_messages(&paths::log_dir(root))?;
+
+    // Learn every name up front so mentions before an agent's first post are scrubbed too
+    let mut anonymizer = Anonymizer::default();
+    if opts.anonymize {
+        for (_, path) in &messages {
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            if let Some(msg) = format::parse_message(&content) {
+                anonymizer.alias(msg.name);
+                if let Some(to) = msg.to {
+                    anonymizer.alias(to);
+                }
+            }
+        }
+        for (_, name) in session::list_sessions(&paths::sessions_dir(root))? {
+            anonymizer.alias(&name);
+        }
+    }
+    let clean = |text: &str| {
+        let text = match &redactor {
+            Some(redactor) => redact(text, redactor),
+            None => text.to_string(),
+        };
+        if opts.anonymize {
+            anonymizer.scrub(&text)
+        } else {
+            text
+        }
+    };
+
+    let mut out: BufWriter<Box<dyn Write>> = BufWriter::new(match &opts.out {
+        Some(path) => Box::new(fs::File::create(path).map_err(|e| {
+            AgentChatError::Other(format!("Cannot write {}: {}", path.display(), e))
+        })?),
+        None => Box::new(std::io::stdout()),
+    });
+
+    if opts.json {
+        write!(out, "[")?;
+    } else {
+        writeln!(out, "# agent-chat transcript")?;
+    }
+    let mut count = 0;
+    for (filename, path) in messages {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some(msg) = format::parse_message(&content) else {
+            continue;
+        };
+        if !msg.is_visible_to(reader.as_deref()) {
+            continue;
+        }
+        if !opts.include_joins && JOIN_BODIES.contains(&msg.body.trim()) {
+            continue;
+        }
+
+        let id = filename.trim_end_matches(".md");
+        if opts.json {
+            let mut entry = json!({
+                "id": id,
+                "name": clean(msg.name),
+                "timestamp": format::iso8601_ns(id.parse().unwrap_or(0)),
+                "body": clean(msg.body.trim_end()),
+                "priority": msg.priority.as_str(),
+            });
+            if let Some(reply_to) = msg.reply_to {
+                entry["reply_to"] = json!(reply_to);
+            }
+            if let Some(to) = msg.to {
+                entry["to"] = json!(clean(to));
+            }
+            if let Some(status) = msg.status {
+                entry["status"] = json!(status.as_str());
+            }
+            if !msg.refs.is_empty() {
+                entry["refs"] = json!(msg.refs.iter().map(|r| clean(r)).collect::<Vec<_>>());
+            }
+            let separator = if count == 0 { "\n  " } else { ",\n  " };
+            write!(out, "{}{}", separator, serde_json::to_string(&entry)?)?;
+        } else {
+            let ts = format::parse_timestamp_ns(id);
+            let section = format!(
+                "\n### {} — {}\n\n{}",
+                msg.name,
+                ts.format("%Y-%m-%d %H:%M:%S"),
+                format::decorated_body(&msg)
+            );
+            writeln!(out, "{}", clean(&section))?;
+        }
+        count += 1;
+    }
+    if opts.json {
+        writeln!(out, "{}]", if count == 0 { "" } else { "\n" })?;
+    }
+    out.flush()?;
+    drop(out);
+
+    if let Some(path) = &opts.out {
+        println!("{}", ui::success_line("Exported:", &format!("{} message(s) to {}", count, path.display())));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anonymizer_is_stable_and_whole_word() {
+        let mut anon = Anonymizer::default();
+        assert_eq!(anon.alias("swift-fox"), "agent-1");
+        assert_eq!(anon.alias("reviewer"), "agent-2");
+        assert_eq!(anon.alias("swift-fox"), "agent-1");
+        anon.alias("reviewer-2");
+
+        assert_eq!(
+            anon.scrub("@swift-fox ask reviewer-2, not reviewers or reviewer"),
+            "@agent-1 ask agent-3, not reviewers or agent-2"
+        );
+    }
+
+    #[test]
+    fn anonymizer_never_rewrites_an_alias() {
+        // Numbered real names: agent-2 posted first, so the aliases swap
+        let mut anon = Anonymizer::default();
+        assert_eq!(anon.alias("agent-2"), "agent-1");
+        assert_eq!(anon.alias("agent-1"), "agent-2");
+
+        assert_eq!(anon.scrub("agent-2 and agent-1"), "agent-1 and agent-2");
+        assert_eq!(anon.scrub("@agent-1: agent-12 is not agent-1"), "@agent-2: agent-12 is not agent-2");
+    }
+
+    #[test]
+    fn redact_replaces_matching_paths() {
+        let redactor = build_redactor(&["/home/**".to_string()]).unwrap().unwrap();
+        assert_eq!(
+            redact("see /home/me/secret.txt. and src/main.rs", &redactor),
+            "see [redacted]. and src/main.rs"
+        );
+    }
+}
@@ -1,17 +1,40 @@
 use std::path::Path;
+use serde_json::{json, Value};
 use crate::error::Result;
-use crate::storage::{config, focus as focus_store, identity, paths};
+use crate::format;
+use crate::storage::{config, focus as focus_store, identity, log, paths};
 use crate::ui;
 
-pub fn set(root: &Path, text: &str) -> Result<()> {
+/// Set this session's focus for `ttl_secs`, or the configured `focus_ttl_secs` if `None`.
+pub fn set(root: &Path, text: &str, ttl_secs: Option<u64>) -> Result<()> {
     let id = identity::resolve(root)?;
     let name = identity::require_name(&id)?;
 
     let config = config::read_config(&paths::config_path(root))?;
     let focuses_dir = paths::focuses_dir(root);
 
-    focus_store::set(&focuses_dir, text, name, &id.session_id, config.focus_ttl_secs)?;
+    let ttl = ttl_secs.unwrap_or(config.focus_ttl_secs);
+    focus_store::set(&focuses_dir, text, name, &id.session_id, ttl)?;
     println!("{}", ui::success_line("Focus set:", text));
+    if config.announce_focus {
+        log::write_message(&paths::log_dir(root), name, &format!("focusing on: {}", text))?;
+    }
+
+    // Advisory: warn about other agents focused on similar areas, strongest first
+    if let Ok(overlaps) = focus_store::find_overlapping(&focuses_dir, text, &id.session_id, config.focus_overlap_min_tokens) {
+        for (o, score) in &focus_store::rank_overlaps(overlaps, config.focus_overlap_min_score) {
+            eprintln!(
+                "{}",
+                ui::warning(&format!(
+                    "WARNING: {} is focused on '{}' — {}% overlap with '{}'",
+                    o.owner,
+                    o.focus,
+                    focus_store::score_percent(*score),
+                    text
+                ))
+            );
+        }
+    }
     Ok(())
 }
 
@@ -19,15 +42,48 @@ pub fn clear(root: &Path) -> Result<()> {
     let id = identity::resolve(root)?;
 
     let focuses_dir = paths::focuses_dir(root);
-    focus_store::clear(&focuses_dir, &id.session_id)?;
+    let cleared = focus_store::clear(&focuses_dir, &id.session_id)?;
     println!("{}", ui::success_line("Focus cleared.", ""));
+
+    let config = config::read_config(&paths::config_path(root))?;
+    if let (true, Some(focus), Some(name)) = (config.announce_focus, cleared, id.name.as_deref()) {
+        log::write_message(&paths::log_dir(root), name, &format!("cleared focus: {}", focus))?;
+    }
     Ok(())
 }
 
-pub fn list(root: &Path) -> Result<()> {
+/// List active focuses.
+///
+/// Porcelain format (one focus per line, tab-separated, stable across releases):
+/// `<owner>\t<session_id>\t<remaining_secs>\t<focus>`
+pub fn list(root: &Path, porcelain: bool, as_json: bool) -> Result<()> {
     let focuses_dir = paths::focuses_dir(root);
     let focuses = focus_store::list_active(&focuses_dir)?;
 
+    if as_json {
+        let entries: Vec<Value> = focuses
+            .iter()
+            .map(|f| {
+                json!({
+                    "focus": f.focus,
+                    "owner": f.owner,
+                    "session_id": f.session_id,
+                    "set_at": format::iso8601_secs(f.set_at),
+                    "ttl_secs": f.ttl_secs,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if porcelain {
+        for f in &focuses {
+            println!("{}\t{}\t{}\t{}", f.owner, f.session_id, f.remaining_secs(), f.focus);
+        }
+        return Ok(());
+    }
+
     if focuses.is_empty() {
         println!("{}", ui::info_line("Focuses:", "No active focuses."));
         return Ok(());
@@ -39,3 +95,29 @@ pub fn list(root: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+/// Print every agent's focus trail, including clears, oldest first.
+pub fn history(root: &Path) -> Result<()> {
+    let histories = focus_store::list_history(&paths::focuses_dir(root))?;
+    if histories.is_empty() {
+        println!("{}", ui::info_line("Focuses:", "No focus history."));
+        return Ok(());
+    }
+
+    for (i, history) in histories.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let latest = history.last().unwrap();
+        println!("{} ({})", latest.owner, latest.session_id);
+        for h in history {
+            let when = format::parse_timestamp_ns(&(h.at as u128 * 1_000_000_000).to_string());
+            println!(
+                "  {}  {}",
+                when.format("%Y-%m-%d %H:%M:%S"),
+                h.focus.as_deref().unwrap_or("(cleared)")
+            );
+        }
+    }
+    Ok(())
+}
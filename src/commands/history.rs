@@ -0,0 +1,38 @@
+use std::fs;
+use std::path::Path;
+use crate::error::Result;
+use crate::format;
+use crate::storage::{archive, identity, log, paths};
+
+/// Print the full chat history, including own messages. Does not advance the cursor.
+/// Direct messages between other agents are left out. With `author`, only that
+/// agent's messages are shown, with full date and time.
+pub fn run(root: &Path, author: Option<&str>, include_archive: bool) -> Result<()> {
+    let reader = identity::resolve(root).ok().and_then(|id| id.name);
+    let mut entries: Vec<(String, String)> = Vec::new();
+
+    if include_archive {
+        entries.extend(archive::list_archived(&paths::archive_dir(root))?);
+    }
+
+    for (filename, path) in log::list_messages(&paths::log_dir(root))? {
+        if let Ok(content) = fs::read_to_string(&path) {
+            entries.push((filename.trim_end_matches(".md").to_string(), content));
+        }
+    }
+
+    for (id, content) in &entries {
+        if let Some(msg) = format::parse_message(content) {
+            if !msg.is_visible_to(reader.as_deref()) {
+                continue;
+            }
+            let ts = format::parse_timestamp_ns(id);
+            match author {
+                Some(author) if msg.name != author => continue,
+                Some(_) => println!("{}", format::format_parsed_full(&msg, ts)),
+                None => println!("{}", format::format_parsed(&msg, ts)),
+            }
+        }
+    }
+    Ok(())
+}
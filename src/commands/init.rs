@@ -1,8 +1,9 @@
 use std::io::{self, BufRead, Write};
 use std::path::Path;
 use crate::error::{AgentChatError, Result};
+use crate::names;
 use crate::storage::{config, paths};
-use crate::hooks::{agents_md_codex, claude_md, installer};
+use crate::hooks::{agents_md_codex, claude_md, continue_rules, cursor_rules, gemini_md, installer, plan, windsurf_rules};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstallTarget {
@@ -16,6 +17,10 @@ enum ToolTarget {
     Claude,
     Codex,
     Both,
+    Continue,
+    Gemini,
+    Cursor,
+    Windsurf,
 }
 
 fn resolve_target(project: bool, user: bool, both: bool, tool_target: ToolTarget) -> Result<InstallTarget> {
@@ -61,6 +66,42 @@ fn resolve_target(project: bool, user: bool, both: bool, tool_target: ToolTarget
                  Select 1/2/3 (Enter = default) > "
             );
         }
+        ToolTarget::Continue => {
+            eprint!(
+                "\nInstall target for Continue rules:\n\
+                 \x20 [1] Project  -> .continue/rules/agent-chat.md\n\
+                 \x20 [2] User     -> ~/.continue/rules/agent-chat.md (default)\n\
+                 \x20 [3] Both\n\
+                 Select 1/2/3 (Enter = default) > "
+            );
+        }
+        ToolTarget::Gemini => {
+            eprint!(
+                "\nInstall target for Gemini integration:\n\
+                 \x20 [1] Project  -> ./GEMINI.md\n\
+                 \x20 [2] User     -> ~/.gemini/GEMINI.md (default)\n\
+                 \x20 [3] Both\n\
+                 Select 1/2/3 (Enter = default) > "
+            );
+        }
+        ToolTarget::Cursor => {
+            eprint!(
+                "\nInstall target for Cursor rules:\n\
+                 \x20 [1] Project  -> .cursor/rules/agent-chat.mdc\n\
+                 \x20 [2] User     -> ~/.cursor/rules/agent-chat.mdc (default)\n\
+                 \x20 [3] Both\n\
+                 Select 1/2/3 (Enter = default) > "
+            );
+        }
+        ToolTarget::Windsurf => {
+            eprint!(
+                "\nInstall target for Windsurf rules:\n\
+                 \x20 [1] Project  -> ./.windsurfrules\n\
+                 \x20 [2] User     -> ~/.codeium/windsurf/memories/global_rules.md (default)\n\
+                 \x20 [3] Both\n\
+                 Select 1/2/3 (Enter = default) > "
+            );
+        }
     }
     io::stderr().flush()?;
 
@@ -72,7 +113,7 @@ fn resolve_target(project: bool, user: bool, both: bool, tool_target: ToolTarget
         "1" => Ok(InstallTarget::Project),
         "" | "2" => Ok(InstallTarget::User),
         "3" => Ok(InstallTarget::Both),
-        other => Err(AgentChatError::Other(format!("invalid choice: {}", other))),
+        other => Err(AgentChatError::InvalidChoice(other.to_string())),
     }
 }
 
@@ -115,10 +156,169 @@ fn resolve_tools(
         "1" => Ok(ToolTarget::Claude),
         "2" => Ok(ToolTarget::Codex),
         "" | "3" => Ok(ToolTarget::Both),
-        other => Err(AgentChatError::Other(format!("invalid choice: {}", other))),
+        other => Err(AgentChatError::InvalidChoice(other.to_string())),
+    }
+}
+
+/// Create `.agent-chat/` and its default config in the project, unless
+/// they already exist.
+pub(crate) fn create_data_dir(project_root: &Path, dry_run: bool) -> Result<()> {
+    plan::apply(dry_run, || plan::data_dir(project_root), || {
+        paths::create_dirs(project_root)?;
+        let config_path = paths::config_path(&project_root.join(".agent-chat"));
+        if !config_path.exists() {
+            config::write_default_config(&config_path)?;
+        }
+        Ok(())
+    })
+}
+
+/// User-level installs keep the project's `.agent-chat/` out of git.
+pub(crate) fn exclude_data_dir(project_root: &Path, dry_run: bool) -> Result<()> {
+    plan::apply(
+        dry_run,
+        || plan::git_exclude(project_root, ".agent-chat/"),
+        || paths::add_git_exclude(project_root, ".agent-chat/"),
+    )
+}
+
+/// Install Claude hooks into the project's `.claude/settings.local.json` and
+/// the guidance section for `label` into `./CLAUDE.md`.
+fn install_claude_project(project_root: &Path, label: Option<&str>, dry_run: bool) -> Result<()> {
+    plan::apply(
+        dry_run,
+        || installer::describe_install(&project_root.join(".claude"), "settings.local.json"),
+        || installer::install_hooks(project_root),
+    )?;
+    plan::apply(
+        dry_run,
+        || claude_md::describe_install(project_root, label),
+        || claude_md::install_claude_md(project_root, label),
+    )
+}
+
+/// Install Claude hooks into `~/.claude/settings.json` and the guidance
+/// section for `label` into `~/.claude/CLAUDE.md`.
+fn install_claude_user(claude_dir: &Path, label: Option<&str>, dry_run: bool) -> Result<()> {
+    plan::apply(
+        dry_run,
+        || installer::describe_install(claude_dir, "settings.json"),
+        || installer::install_hooks_to(claude_dir, "settings.json"),
+    )?;
+    plan::apply(
+        dry_run,
+        || claude_md::describe_install(claude_dir, label),
+        || claude_md::install_claude_md_to(claude_dir, label),
+    )
+}
+
+pub(crate) fn install_codex(target_dir: &Path, dry_run: bool) -> Result<()> {
+    plan::apply(
+        dry_run,
+        || agents_md_codex::describe_install(target_dir),
+        || agents_md_codex::install_agents_md_to(target_dir),
+    )
+}
+
+/// Install Continue rules for `target`. Continue has no hook model, so the
+/// rules tell the agent to run `register`/`read`/`say` itself.
+fn install_continue(project_root: &Path, target: InstallTarget, dry_run: bool) -> Result<()> {
+    let install = |dir: &Path| {
+        plan::apply(
+            dry_run,
+            || continue_rules::describe_install(dir),
+            || continue_rules::install_continue_rules_to(dir),
+        )
+    };
+    if matches!(target, InstallTarget::Project | InstallTarget::Both) {
+        install(&project_root.join(".continue"))?;
+    }
+    if matches!(target, InstallTarget::User | InstallTarget::Both) {
+        install(&paths::home_dir()?.join(".continue"))?;
+        exclude_data_dir(project_root, dry_run)?;
+    }
+    Ok(())
+}
+
+/// Install the Gemini guidance section into `./GEMINI.md` and/or
+/// `~/.gemini/GEMINI.md` for `target`.
+fn install_gemini(project_root: &Path, target: InstallTarget, dry_run: bool) -> Result<()> {
+    let install = |dir: &Path| {
+        plan::apply(dry_run, || gemini_md::describe_install(dir), || gemini_md::install_gemini_md_to(dir))
+    };
+    if matches!(target, InstallTarget::Project | InstallTarget::Both) {
+        install(project_root)?;
+    }
+    if matches!(target, InstallTarget::User | InstallTarget::Both) {
+        install(&paths::home_dir()?.join(".gemini"))?;
+        exclude_data_dir(project_root, dry_run)?;
+    }
+    Ok(())
+}
+
+/// Install the Cursor rule (`rules/agent-chat.mdc`, always applied) for
+/// `target`. Like Continue, Cursor has no hooks to register the session.
+fn install_cursor(project_root: &Path, target: InstallTarget, dry_run: bool) -> Result<()> {
+    let install = |dir: &Path| {
+        plan::apply(dry_run, || cursor_rules::describe_install(dir), || cursor_rules::install_cursor_rules_to(dir))
+    };
+    if matches!(target, InstallTarget::Project | InstallTarget::Both) {
+        install(&project_root.join(".cursor"))?;
+    }
+    if matches!(target, InstallTarget::User | InstallTarget::Both) {
+        install(&paths::home_dir()?.join(".cursor"))?;
+        exclude_data_dir(project_root, dry_run)?;
     }
+    Ok(())
+}
+
+/// Record `label` as the instance label in `config.toml`, or return the label
+/// already stored there when none is given.
+fn instance_label(project_root: &Path, label: Option<&str>, dry_run: bool) -> Result<Option<String>> {
+    let config_path = paths::config_path(&project_root.join(".agent-chat"));
+    let Some(label) = label else {
+        return Ok(config::read_config(&config_path)?.label().map(str::to_string));
+    };
+    plan::apply(
+        dry_run,
+        || Some(format!("set instance_label = \"{}\" in {}", label, config_path.display())),
+        || config::set_value(&config_path, "instance_label", &format!("\"{}\"", label)),
+    )?;
+    Ok(Some(label.to_string()))
 }
 
+/// Install the Windsurf rules section into `./.windsurfrules` and/or
+/// `~/.codeium/windsurf/memories/global_rules.md` for `target`.
+fn install_windsurf(project_root: &Path, target: InstallTarget, dry_run: bool) -> Result<()> {
+    let install = |dir: &Path, filename: &str| {
+        plan::apply(
+            dry_run,
+            || windsurf_rules::describe_install(dir, filename),
+            || windsurf_rules::install_windsurf_rules_to(dir, filename),
+        )
+    };
+    if matches!(target, InstallTarget::Project | InstallTarget::Both) {
+        install(project_root, windsurf_rules::PROJECT_FILE)?;
+    }
+    if matches!(target, InstallTarget::User | InstallTarget::Both) {
+        install(&windsurf_rules::user_dir(&paths::home_dir()?), windsurf_rules::USER_FILE)?;
+        exclude_data_dir(project_root, dry_run)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn target_label(target: InstallTarget) -> &'static str {
+    match target {
+        InstallTarget::Project => "project",
+        InstallTarget::User => "user",
+        InstallTarget::Both => "project + user",
+    }
+}
+
+/// Set up agent-chat for the chosen tools. With `dry_run`, print each file
+/// that would be created or modified and write nothing. A `label` is stored
+/// in `config.toml` and scopes the CLAUDE.md section to this setup.
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     project_root: &Path,
     project: bool,
@@ -127,92 +327,112 @@ pub fn run(
     claude: bool,
     codex: bool,
     both_tools: bool,
+    continue_: bool,
+    gemini: bool,
+    cursor: bool,
+    windsurf: bool,
+    label: Option<&str>,
+    dry_run: bool,
 ) -> Result<()> {
+    if let Some(label) = label {
+        names::validate_name(label)?;
+    }
     let has_location_flags = project || user || both;
-    let tool_target = resolve_tools(claude, codex, both_tools, has_location_flags)?;
+    // `--continue`, `--gemini`, `--cursor`, or `--windsurf` alone installs only that
+    // integration; combined with other tool flags they are installed alongside.
+    let tool_target = if continue_ && !(claude || codex || both_tools) {
+        ToolTarget::Continue
+    } else if gemini && !(claude || codex || both_tools) {
+        ToolTarget::Gemini
+    } else if cursor && !(claude || codex || both_tools) {
+        ToolTarget::Cursor
+    } else if windsurf && !(claude || codex || both_tools) {
+        ToolTarget::Windsurf
+    } else {
+        resolve_tools(claude, codex, both_tools, has_location_flags)?
+    };
     let target = resolve_target(project, user, both, tool_target)?;
 
     // Always create .agent-chat/ + config in the project
-    paths::create_dirs(project_root)?;
-    let root = project_root.join(".agent-chat");
-    let config_path = paths::config_path(&root);
-    if !config_path.exists() {
-        config::write_default_config(&config_path)?;
-    }
+    create_data_dir(project_root, dry_run)?;
+    let label = instance_label(project_root, label, dry_run)?;
+    let label = label.as_deref();
 
-    match (tool_target, target) {
-        (ToolTarget::Claude, InstallTarget::Project) => {
-            installer::install_hooks(project_root)?;
-            claude_md::install_claude_md(project_root)?;
-            println!("Initialized .agent-chat/ and installed hooks (project).");
+    let with_claude = matches!(tool_target, ToolTarget::Claude | ToolTarget::Both);
+    let with_codex = matches!(tool_target, ToolTarget::Codex | ToolTarget::Both);
+    if with_claude || with_codex {
+        if matches!(target, InstallTarget::Project | InstallTarget::Both) {
+            if with_claude {
+                install_claude_project(project_root, label, dry_run)?;
+            }
+            if with_codex {
+                install_codex(project_root, dry_run)?;
+            }
         }
-        (ToolTarget::Claude, InstallTarget::User) => {
-            let home = paths::home_dir()?;
-            let claude_dir = home.join(".claude");
-            installer::install_hooks_to(&claude_dir, "settings.json")?;
-            claude_md::install_claude_md_to(&claude_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed hooks (user).");
-        }
-        (ToolTarget::Claude, InstallTarget::Both) => {
-            installer::install_hooks(project_root)?;
-            claude_md::install_claude_md(project_root)?;
+        if matches!(target, InstallTarget::User | InstallTarget::Both) {
             let home = paths::home_dir()?;
-            let claude_dir = home.join(".claude");
-            installer::install_hooks_to(&claude_dir, "settings.json")?;
-            claude_md::install_claude_md_to(&claude_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed hooks (project + user).");
+            if with_claude {
+                install_claude_user(&home.join(".claude"), label, dry_run)?;
+            }
+            if with_codex {
+                install_codex(&home.join(".codex"), dry_run)?;
+            }
+            exclude_data_dir(project_root, dry_run)?;
         }
-        (ToolTarget::Codex, InstallTarget::Project) => {
-            agents_md_codex::install_agents_md_to(project_root)?;
-            println!("Initialized .agent-chat/ and installed Codex guidance (project).");
+    }
+
+    let installed = match tool_target {
+        ToolTarget::Claude => "hooks",
+        ToolTarget::Codex => "Codex guidance",
+        ToolTarget::Both => "Claude + Codex integrations",
+        ToolTarget::Continue => {
+            install_continue(project_root, target, dry_run)?;
+            "Continue rules"
         }
-        (ToolTarget::Codex, InstallTarget::User) => {
-            let home = paths::home_dir()?;
-            let codex_dir = home.join(".codex");
-            agents_md_codex::install_agents_md_to(&codex_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed Codex guidance (user).");
+        ToolTarget::Gemini => {
+            install_gemini(project_root, target, dry_run)?;
+            "Gemini guidance"
         }
-        (ToolTarget::Codex, InstallTarget::Both) => {
-            agents_md_codex::install_agents_md_to(project_root)?;
-            let home = paths::home_dir()?;
-            let codex_dir = home.join(".codex");
-            agents_md_codex::install_agents_md_to(&codex_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed Codex guidance (project + user).");
-        }
-        (ToolTarget::Both, InstallTarget::Project) => {
-            installer::install_hooks(project_root)?;
-            claude_md::install_claude_md(project_root)?;
-            agents_md_codex::install_agents_md_to(project_root)?;
-            println!("Initialized .agent-chat/ and installed Claude + Codex integrations (project).");
-        }
-        (ToolTarget::Both, InstallTarget::User) => {
-            let home = paths::home_dir()?;
-            let claude_dir = home.join(".claude");
-            let codex_dir = home.join(".codex");
-            installer::install_hooks_to(&claude_dir, "settings.json")?;
-            claude_md::install_claude_md_to(&claude_dir)?;
-            agents_md_codex::install_agents_md_to(&codex_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed Claude + Codex integrations (user).");
-        }
-        (ToolTarget::Both, InstallTarget::Both) => {
-            installer::install_hooks(project_root)?;
-            claude_md::install_claude_md(project_root)?;
-            agents_md_codex::install_agents_md_to(project_root)?;
-            let home = paths::home_dir()?;
-            let claude_dir = home.join(".claude");
-            let codex_dir = home.join(".codex");
-            installer::install_hooks_to(&claude_dir, "settings.json")?;
-            claude_md::install_claude_md_to(&claude_dir)?;
-            agents_md_codex::install_agents_md_to(&codex_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed Claude + Codex integrations (project + user).");
+        ToolTarget::Cursor => {
+            install_cursor(project_root, target, dry_run)?;
+            "Cursor rules"
         }
+        ToolTarget::Windsurf => {
+            install_windsurf(project_root, target, dry_run)?;
+            "Windsurf rules"
+        }
+    };
+    if !dry_run {
+        println!("Initialized .agent-chat/ and installed {} ({}).", installed, target_label(target));
     }
 
+    if continue_ && tool_target != ToolTarget::Continue {
+        install_continue(project_root, target, dry_run)?;
+        if !dry_run {
+            println!("Installed Continue rules ({}).", target_label(target));
+        }
+    }
+    if gemini && tool_target != ToolTarget::Gemini {
+        install_gemini(project_root, target, dry_run)?;
+        if !dry_run {
+            println!("Installed Gemini guidance ({}).", target_label(target));
+        }
+    }
+    if cursor && tool_target != ToolTarget::Cursor {
+        install_cursor(project_root, target, dry_run)?;
+        if !dry_run {
+            println!("Installed Cursor rules ({}).", target_label(target));
+        }
+    }
+    if windsurf && tool_target != ToolTarget::Windsurf {
+        install_windsurf(project_root, target, dry_run)?;
+        if !dry_run {
+            println!("Installed Windsurf rules ({}).", target_label(target));
+        }
+    }
+
+    if dry_run {
+        println!("Dry run: nothing was written.");
+    }
     Ok(())
 }
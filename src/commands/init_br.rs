@@ -1,7 +1,7 @@
 use std::io::{self, BufRead, Write};
 use std::path::Path;
 use crate::error::{AgentChatError, Result};
-use crate::hooks::claude_md_br;
+use crate::hooks::{claude_md_br, plan};
 use crate::storage::paths;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,31 +39,36 @@ fn resolve_target(project: bool, user: bool) -> Result<BrInstallTarget> {
     match line.trim() {
         "1" => Ok(BrInstallTarget::Project),
         "2" => Ok(BrInstallTarget::User),
-        other => Err(AgentChatError::Other(format!("invalid choice: {}", other))),
+        other => Err(AgentChatError::InvalidChoice(other.to_string())),
     }
 }
 
-pub fn run(project_root: &Path, project: bool, user: bool) -> Result<()> {
+/// Install br guidance at one level and remove it from the other. With
+/// `dry_run`, print what would change and write nothing.
+pub fn run(project_root: &Path, project: bool, user: bool, dry_run: bool) -> Result<()> {
     let target = resolve_target(project, user)?;
+    let claude_dir = paths::home_dir()?.join(".claude");
 
-    match target {
-        BrInstallTarget::Project => {
-            claude_md_br::install_br_claude_md_to(project_root)?;
-            // Auto-cleanup: remove from user level
-            let home = paths::home_dir()?;
-            let claude_dir = home.join(".claude");
-            claude_md_br::remove_br_claude_md_from(&claude_dir)?;
-            println!("Installed br guidance (project).");
-        }
-        BrInstallTarget::User => {
-            let home = paths::home_dir()?;
-            let claude_dir = home.join(".claude");
-            claude_md_br::install_br_claude_md_to(&claude_dir)?;
-            // Auto-cleanup: remove from project level
-            claude_md_br::remove_br_claude_md_from(project_root)?;
-            println!("Installed br guidance (user).");
-        }
-    }
+    // Installing at one level cleans up the other
+    let (install_dir, cleanup_dir, label) = match target {
+        BrInstallTarget::Project => (project_root, claude_dir.as_path(), "project"),
+        BrInstallTarget::User => (claude_dir.as_path(), project_root, "user"),
+    };
+    plan::apply(
+        dry_run,
+        || claude_md_br::describe_install(install_dir),
+        || claude_md_br::install_br_claude_md_to(install_dir),
+    )?;
+    plan::apply(
+        dry_run,
+        || claude_md_br::describe_removal(cleanup_dir),
+        || claude_md_br::remove_br_claude_md_from(cleanup_dir),
+    )?;
 
+    if dry_run {
+        println!("Dry run: nothing was written.");
+    } else {
+        println!("Installed br guidance ({}).", label);
+    }
     Ok(())
 }
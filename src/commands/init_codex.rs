@@ -1,8 +1,8 @@
 use std::io::{self, BufRead, Write};
 use std::path::Path;
 use crate::error::{AgentChatError, Result};
-use crate::hooks::agents_md_codex;
-use crate::storage::{config, paths};
+use crate::commands::init;
+use crate::storage::paths;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CodexInstallTarget {
@@ -43,42 +43,35 @@ fn resolve_target(project: bool, user: bool, both: bool) -> Result<CodexInstallT
         "1" => Ok(CodexInstallTarget::Project),
         "2" => Ok(CodexInstallTarget::User),
         "3" => Ok(CodexInstallTarget::Both),
-        other => Err(AgentChatError::Other(format!("invalid choice: {}", other))),
+        other => Err(AgentChatError::InvalidChoice(other.to_string())),
     }
 }
 
-pub fn run(project_root: &Path, project: bool, user: bool, both: bool) -> Result<()> {
+/// Install Codex guidance. With `dry_run`, print each file that would be
+/// created or modified and write nothing.
+pub fn run(project_root: &Path, project: bool, user: bool, both: bool, dry_run: bool) -> Result<()> {
     let target = resolve_target(project, user, both)?;
 
     // Always create .agent-chat/ + config in the project
-    paths::create_dirs(project_root)?;
-    let root = project_root.join(".agent-chat");
-    let config_path = paths::config_path(&root);
-    if !config_path.exists() {
-        config::write_default_config(&config_path)?;
-    }
+    init::create_data_dir(project_root, dry_run)?;
 
-    match target {
-        CodexInstallTarget::Project => {
-            agents_md_codex::install_agents_md_to(project_root)?;
-            println!("Initialized .agent-chat/ and installed Codex guidance (project).");
-        }
-        CodexInstallTarget::User => {
-            let home = paths::home_dir()?;
-            let codex_dir = home.join(".codex");
-            agents_md_codex::install_agents_md_to(&codex_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed Codex guidance (user).");
-        }
-        CodexInstallTarget::Both => {
-            agents_md_codex::install_agents_md_to(project_root)?;
-            let home = paths::home_dir()?;
-            let codex_dir = home.join(".codex");
-            agents_md_codex::install_agents_md_to(&codex_dir)?;
-            paths::add_git_exclude(project_root, ".agent-chat/")?;
-            println!("Initialized .agent-chat/ and installed Codex guidance (project + user).");
-        }
+    if matches!(target, CodexInstallTarget::Project | CodexInstallTarget::Both) {
+        init::install_codex(project_root, dry_run)?;
+    }
+    if matches!(target, CodexInstallTarget::User | CodexInstallTarget::Both) {
+        init::install_codex(&paths::home_dir()?.join(".codex"), dry_run)?;
+        init::exclude_data_dir(project_root, dry_run)?;
     }
 
+    if dry_run {
+        println!("Dry run: nothing was written.");
+    } else {
+        let label = match target {
+            CodexInstallTarget::Project => "project",
+            CodexInstallTarget::User => "user",
+            CodexInstallTarget::Both => "project + user",
+        };
+        println!("Initialized .agent-chat/ and installed Codex guidance ({}).", label);
+    }
     Ok(())
 }
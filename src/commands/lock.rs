@@ -1,17 +1,104 @@
 use std::path::Path;
-use crate::error::Result;
-use crate::storage::{config, identity, lockfile, paths};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::{json, Value};
+use crate::error::{AgentChatError, Result};
+use crate::format;
+use crate::storage::{config, identity, lockfile, log, paths, session};
 use crate::ui;
 
-pub fn acquire(root: &Path, glob: &str) -> Result<()> {
+/// How often `lock --wait` retries a conflicting lock.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Post a chat note for locks within `lock_expiry_warning_secs` of lapsing
+/// whose owner is still registered, once per lock. Best-effort: called
+/// opportunistically from commands that list or clean locks.
+pub fn warn_expiring(root: &Path) -> Result<()> {
+    let window = config::read_config(&paths::config_path(root))?.lock_expiry_warning_secs;
+    if window == 0 {
+        return Ok(());
+    }
+    let sessions_dir = paths::sessions_dir(root);
+    let log_dir = paths::log_dir(root);
+
+    for lock in lockfile::take_expiring(&paths::locks_dir(root), window)? {
+        if session::read_session(&sessions_dir, &lock.session_id)?.is_none() {
+            continue;
+        }
+        log::write_message(
+            &log_dir,
+            "agent-chat",
+            &format!("{}'s lock on {} expires in {}s", lock.owner, lock.glob, lock.remaining_secs()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Acquire a lock for `ttl_secs`, or the configured `lock_ttl_secs` if `None`.
+/// With `wait_secs`, a conflicting lock is retried until it is released or
+/// expires, or the wait runs out and the conflict is returned as usual.
+pub fn acquire(root: &Path, glob: &str, ttl_secs: Option<u64>, wait_secs: Option<u64>) -> Result<()> {
+    let id = identity::resolve(root)?;
+    let name = identity::require_name(&id)?;
+
+    let config = config::read_config(&paths::config_path(root))?;
+    let locks_dir = paths::locks_dir(root);
+    let ttl = ttl_secs.unwrap_or(config.lock_ttl_secs);
+
+    let deadline = wait_secs.map(|secs| Instant::now() + Duration::from_secs(secs));
+    loop {
+        let result = lockfile::acquire(&locks_dir, glob, name, &id.session_id, ttl, config.max_locks_per_session);
+        let conflict = matches!(result, Err(AgentChatError::LockConflict { .. } | AgentChatError::LockOverlap { .. }));
+        match deadline {
+            Some(deadline) if conflict => {
+                // Never sleep past the deadline
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    return result;
+                }
+                thread::sleep(left.min(WAIT_POLL_INTERVAL));
+            }
+            _ => break result?,
+        }
+    }
+    println!("{}", ui::success_line("Locked:", &format!("{} (ttl {}s)", glob, ttl)));
+    let _ = warn_expiring(root);
+    Ok(())
+}
+
+/// Print the project files `glob` would cover, without locking anything.
+pub fn list_files(root: &Path, glob: &str) -> Result<()> {
+    let project_root = root.parent().unwrap_or(root);
+    let files = lockfile::matching_files(project_root, glob)?;
+    if files.is_empty() {
+        println!("{}", ui::info_line("No files match:", glob));
+        return Ok(());
+    }
+    for file in &files {
+        println!("{}", file);
+    }
+    Ok(())
+}
+
+/// Refresh a lock this session holds, even if it has lapsed, unless another
+/// session has since taken the pattern.
+pub fn renew(root: &Path, glob: &str, ttl_secs: Option<u64>) -> Result<()> {
     let id = identity::resolve(root)?;
     let name = identity::require_name(&id)?;
 
     let config = config::read_config(&paths::config_path(root))?;
     let locks_dir = paths::locks_dir(root);
 
-    lockfile::acquire(&locks_dir, glob, name, &id.session_id, config.lock_ttl_secs)?;
-    println!("{}", ui::success_line("Locked:", glob));
+    let ttl = lockfile::renew(
+        &locks_dir,
+        glob,
+        name,
+        &id.session_id,
+        ttl_secs,
+        config.lock_ttl_secs,
+        config.max_locks_per_session,
+    )?;
+    println!("{}", ui::success_line("Renewed:", &format!("{} (ttl {}s)", glob, ttl)));
     Ok(())
 }
 
@@ -24,10 +111,80 @@ pub fn release(root: &Path, glob: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn list(root: &Path) -> Result<()> {
+/// Break someone else's lock, e.g. one left by a crashed agent. Needs no
+/// registered session; the release is announced in the chat for the record.
+pub fn force_release(root: &Path, glob: &str) -> Result<()> {
+    let lock = lockfile::force_release(&paths::locks_dir(root), glob)?;
+    println!(
+        "{}",
+        ui::success_line(
+            "Force-unlocked:",
+            &format!("{} (held by {}, session {})", glob, lock.owner, lock.session_id)
+        )
+    );
+
+    let by = identity::resolve(root).ok().and_then(|id| id.name);
+    log::write_message(
+        &paths::log_dir(root),
+        by.as_deref().unwrap_or("agent-chat"),
+        &format!("force-released {}'s lock on {}", lock.owner, glob),
+    )?;
+    Ok(())
+}
+
+/// Release every lock held by the current session.
+pub fn release_all(root: &Path) -> Result<()> {
+    let id = identity::resolve(root)?;
+
+    let locks_dir = paths::locks_dir(root);
+    let released = lockfile::release_all_for_session(&locks_dir, &id.session_id)?;
+    if released.is_empty() {
+        println!("{}", ui::info_line("Locks:", "No locks to release."));
+        return Ok(());
+    }
+    for lock in &released {
+        println!("{}", ui::success_line("Unlocked:", &lock.glob));
+    }
+    println!("Released {} lock(s).", released.len());
+    Ok(())
+}
+
+/// List active locks.
+///
+/// Porcelain format (one lock per line, tab-separated, stable across releases):
+/// `<owner>\t<session_id>\t<remaining_secs>\t<glob>`
+pub fn list(root: &Path, porcelain: bool, as_json: bool) -> Result<()> {
+    let _ = warn_expiring(root);
+
     let locks_dir = paths::locks_dir(root);
     let locks = lockfile::list_active(&locks_dir)?;
 
+    if as_json {
+        let entries: Vec<Value> = locks
+            .iter()
+            .map(|lock| {
+                json!({
+                    "glob": lock.glob,
+                    "owner": lock.owner,
+                    "session_id": lock.session_id,
+                    "acquired_at": format::iso8601_secs(lock.acquired_at),
+                    "ttl_secs": lock.ttl_secs,
+                    "expires_at": format::iso8601_secs(lock.expires_at()),
+                    "remaining_secs": lock.remaining_secs(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if porcelain {
+        for lock in &locks {
+            println!("{}\t{}\t{}\t{}", lock.owner, lock.session_id, lock.remaining_secs(), lock.glob);
+        }
+        return Ok(());
+    }
+
     if locks.is_empty() {
         println!("{}", ui::info_line("Locks:", "No active locks."));
         return Ok(());
@@ -35,13 +192,7 @@ pub fn list(root: &Path) -> Result<()> {
 
     println!("{}", ui::table_header("PATTERN", "OWNER", Some("TTL")));
     for lock in &locks {
-        let remaining = (lock.acquired_at + lock.ttl_secs).saturating_sub(
-            std::time::SystemTime::now()
-                .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        );
-        println!("{:<30} {:<15} {}s", lock.glob, lock.owner, remaining);
+        println!("{:<30} {:<15} {}s", lock.glob, lock.owner, lock.remaining_secs());
     }
     Ok(())
 }
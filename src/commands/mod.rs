@@ -1,14 +1,28 @@
+pub mod agents;
+pub mod board;
 pub mod br;
 pub mod br_claim;
 pub mod br_complete;
 pub mod check_lock;
 pub mod check_messages;
+pub mod clear;
+pub mod compact;
+pub mod diff;
+pub mod doctor;
+pub mod export;
 pub mod focus;
+pub mod history;
 pub mod init;
 pub mod init_br;
 pub mod init_codex;
 pub mod lock;
+pub mod mute;
 pub mod read;
+pub mod receipts;
 pub mod register;
+pub mod room;
 pub mod say;
+pub mod search;
+pub mod stats;
 pub mod status;
+pub mod uninstall;
@@ -0,0 +1,42 @@
+use std::path::Path;
+use crate::error::Result;
+use crate::storage::{cursor, identity, paths};
+use crate::ui;
+
+/// Hide messages from `name` in this session's `read`, `status` and
+/// `check-messages`. Other sessions are unaffected.
+pub fn mute(root: &Path, name: &str) -> Result<()> {
+    let cursor_file = session_cursor(root)?;
+    if cursor::set_muted(&cursor_file, name, true)? {
+        println!("{}", ui::success_line("Muted:", name));
+    } else {
+        println!("{}", ui::info_line("Muted:", &format!("{} (already muted)", name)));
+    }
+    Ok(())
+}
+
+pub fn unmute(root: &Path, name: &str) -> Result<()> {
+    let cursor_file = session_cursor(root)?;
+    if cursor::set_muted(&cursor_file, name, false)? {
+        println!("{}", ui::success_line("Unmuted:", name));
+    } else {
+        println!("{}", ui::info_line("Unmuted:", &format!("{} was not muted", name)));
+    }
+    Ok(())
+}
+
+/// Print this session's mute list.
+pub fn list(root: &Path) -> Result<()> {
+    let mutes = cursor::read_mutes(&session_cursor(root)?);
+    if mutes.is_empty() {
+        println!("{}", ui::info_line("Muted:", "nobody"));
+    } else {
+        println!("{}", ui::info_line("Muted:", &mutes.join(", ")));
+    }
+    Ok(())
+}
+
+fn session_cursor(root: &Path) -> Result<std::path::PathBuf> {
+    let id = identity::resolve(root)?;
+    Ok(cursor::cursor_path(&paths::cursors_dir(root), &id.session_id))
+}
@@ -1,59 +1,259 @@
 use std::fs;
-use std::path::Path;
-use crate::error::Result;
-use crate::format;
-use crate::storage::{cursor, identity, log, paths};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use serde_json::json;
+use crate::error::{AgentChatError, Result};
+use crate::format::{self, Priority};
+use crate::storage::{config, cursor, identity, log, paths};
 
-const DEFAULT_FIRST_READ_COUNT: usize = 5;
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
 
-pub fn run(root: &Path, show_all: bool) -> Result<()> {
+/// How `read` selects and renders messages.
+#[derive(Debug, Default)]
+pub struct ReadOptions {
+    /// Show every message instead of just unread ones.
+    pub show_all: bool,
+    /// Hide messages below this priority (the cursor still moves past them).
+    pub min_priority: Option<Priority>,
+    /// Group messages under one header per author instead of interleaving.
+    pub by_author: bool,
+    /// First-read window override; defaults to `first_read_count` from config.
+    pub count: Option<usize>,
+    /// Print a JSON array instead of formatted lines.
+    pub json: bool,
+    /// Only show messages from this author (the cursor still moves past all).
+    pub from: Option<String>,
+    /// Show messages from this far back (e.g. `30m`) regardless of read state,
+    /// leaving the cursor alone.
+    pub since: Option<String>,
+}
+
+/// Print unread (or all) messages and advance the cursor.
+pub fn run(root: &Path, opts: &ReadOptions) -> Result<()> {
     let id = identity::resolve(root)?;
 
     // Filter out own messages to avoid wasting tokens
     let exclude = id.name.as_deref();
+    if exclude.is_none() {
+        eprintln!(
+            "note: no name registered for session {}; your own messages are not filtered",
+            id.session_id
+        );
+    }
 
     let log_dir = paths::log_dir(root);
     let cursors_dir = paths::cursors_dir(root);
     let cursor_file = cursor::cursor_path(&cursors_dir, &id.session_id);
 
-    let message_paths = if show_all {
+    let mutes = cursor::read_mutes(&cursor_file);
+
+    let first_read_count = match opts.count {
+        Some(count) => count,
+        None => config::read_config(&paths::config_path(root))?.first_read_count,
+    };
+
+    let message_paths = if let Some(since) = &opts.since {
+        let cutoff = format::cutoff_ns(since)?;
+        let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_nanos();
+        let index = log::AuthorIndex::load(&log_dir);
+        log::list_messages(&log_dir)?
+            .into_iter()
+            .filter(|(name, _)| {
+                // Scheduled messages stay hidden until their delivery time
+                name.trim_end_matches(".md").parse::<u128>().is_ok_and(|ts| ts >= cutoff && ts <= now)
+            })
+            .filter(|(_, path)| cursor::include_unmuted(&index, path, exclude, &mutes))
+            .map(|(_, p)| p)
+            .collect()
+    } else if opts.show_all {
+        // Full scans are where expired ephemeral messages get cleaned up
+        log::prune_expired(&log_dir)?;
         let msgs = log::list_messages(&log_dir)?;
-        // Filter own messages for --all mode too
+        let index = log::AuthorIndex::load(&log_dir);
+        // Filter own messages and others' direct messages for --all mode too
         msgs.into_iter()
-            .filter(|(_, path)| {
-                match exclude {
-                    Some(name) => {
-                        match fs::read_to_string(path) {
-                            Ok(content) => match format::parse_message_file(&content) {
-                                Some((author, _)) => author != name,
-                                None => true,
-                            },
-                            Err(_) => true,
-                        }
-                    }
-                    None => true,
-                }
-            })
+            .filter(|(_, path)| cursor::include_unmuted(&index, path, exclude, &mutes))
             .map(|(_, p)| p)
             .collect()
     } else {
-        cursor::get_unread_messages(&log_dir, &cursor_file, DEFAULT_FIRST_READ_COUNT, exclude)?
+        cursor::get_unread_messages(&log_dir, &cursor_file, first_read_count, exclude)?
+    };
+
+    let message_paths = match &opts.from {
+        Some(from) => message_paths.into_iter().filter(|path| is_from(path, from)).collect(),
+        None => message_paths,
+    };
+
+    if opts.json {
+        print_json(&message_paths, opts.min_priority)?;
+    } else if opts.by_author {
+        print_grouped_by_author(&message_paths, opts.min_priority);
+    } else {
+        print_messages(&message_paths, opts.min_priority);
+    }
+
+    // Advance cursor after reading (always, even if all were own messages)
+    // We advance based on ALL messages (including own) so the cursor moves past them.
+    // A `--since` window is an ad-hoc view and leaves read state alone.
+    if opts.since.is_none() {
+        cursor::advance(&cursor_file)?;
+    }
+
+    Ok(())
+}
+
+/// Mark messages read without printing them: everything up to now, or only
+/// up to and including message `to`. Never moves the cursor backwards.
+pub fn mark_read(root: &Path, to: Option<&str>) -> Result<()> {
+    let id = identity::resolve(root)?;
+    let cursor_file = cursor::cursor_path(&paths::cursors_dir(root), &id.session_id);
+    let Some(to) = to else {
+        return cursor::advance(&cursor_file);
     };
 
-    for path in &message_paths {
-        if let Ok(content) = fs::read_to_string(path) {
-            if let Some((name, body)) = format::parse_message_file(&content) {
-                // Extract timestamp from filename
-                let filename = path.file_stem().unwrap().to_string_lossy();
-                let ts = format::parse_timestamp_ns(&filename);
-                println!("{}", format::format_message(name, ts, body));
+    let target: u128 = log::find_message(&paths::log_dir(root), to)
+        .and_then(|_| to.parse().ok())
+        .ok_or_else(|| AgentChatError::Other(format!("Message not found: {}", to)))?;
+    if target > cursor::position(&cursor_file) {
+        cursor::advance_to(&cursor_file, target)?;
+    }
+    Ok(())
+}
+
+/// Block until an unread message from another agent appears, print it, and
+/// advance the cursor. Fails with `WatchTimeout` if nothing arrives in time.
+pub fn watch(root: &Path, timeout_secs: u64, min_priority: Option<Priority>) -> Result<()> {
+    let id = identity::resolve(root)?;
+    let exclude = id.name.as_deref();
+    let log_dir = paths::log_dir(root);
+    let cursor_file = cursor::cursor_path(&paths::cursors_dir(root), &id.session_id);
+    let first_read_count = config::read_config(&paths::config_path(root))?.first_read_count;
+
+    let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+    loop {
+        let unread = cursor::get_unread_messages(&log_dir, &cursor_file, first_read_count, exclude)?;
+        if print_messages(&unread, min_priority) > 0 {
+            cursor::advance(&cursor_file)?;
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(AgentChatError::WatchTimeout(timeout_secs));
+        }
+        thread::sleep(WATCH_POLL_INTERVAL);
+    }
+}
+
+/// Whether the message at `path` was written by `author`.
+fn is_from(path: &Path, author: &str) -> bool {
+    fs::read_to_string(path)
+        .ok()
+        .is_some_and(|content| format::parse_message(&content).is_some_and(|m| m.name == author))
+}
+
+/// Render messages at or above `min_priority` as `(author, line)` pairs,
+/// in the order given. Acks whose parent is in the same batch are folded
+/// into the parent's line instead of getting one of their own.
+fn render_messages(message_paths: &[PathBuf], min_priority: Option<Priority>) -> Vec<(String, String)> {
+    let contents: Vec<(String, String)> = message_paths
+        .iter()
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_string_lossy().to_string();
+            Some((id, fs::read_to_string(path).ok()?))
+        })
+        .collect();
+    let parsed: Vec<(&str, format::ParsedMessage)> = contents
+        .iter()
+        .filter_map(|(id, content)| Some((id.as_str(), format::parse_message(content)?)))
+        .collect();
+
+    let mut acks: Vec<(&str, Vec<&str>)> = Vec::new();
+    for (_, msg) in &parsed {
+        if let Some(parent) = msg.ack_of() {
+            if parsed.iter().any(|(id, _)| *id == parent) {
+                match acks.iter_mut().find(|(id, _)| *id == parent) {
+                    Some((_, names)) => names.push(msg.name),
+                    None => acks.push((parent, vec![msg.name])),
+                }
             }
         }
     }
 
-    // Advance cursor after reading (always, even if all were own messages)
-    // We advance based on ALL messages (including own) so the cursor moves past them
-    cursor::advance(&cursor_file)?;
+    let mut rendered = Vec::new();
+    for (id, msg) in &parsed {
+        if msg.ack_of().is_some_and(|parent| acks.iter().any(|(p, _)| *p == parent)) {
+            continue;
+        }
+        // Lower-priority messages are skipped but still marked read by the caller
+        if min_priority.is_some_and(|min| msg.priority < min) {
+            continue;
+        }
+        let ts = format::parse_timestamp_ns(id);
+        let mut line = format::format_parsed(msg, ts);
+        if let Some((_, names)) = acks.iter().find(|(p, _)| p == id) {
+            line.push_str(&format!(" ({}: {})", format::ACK_BODY, names.join(", ")));
+        }
+        rendered.push((msg.name.to_string(), line));
+    }
+    rendered
+}
 
+/// Print messages at or above `min_priority` as a JSON array of
+/// `{id, name, timestamp, body, reply_to?}` objects.
+fn print_json(message_paths: &[PathBuf], min_priority: Option<Priority>) -> Result<()> {
+    let mut messages = Vec::new();
+    for path in message_paths {
+        let Ok(content) = fs::read_to_string(path) else { continue };
+        let Some(msg) = format::parse_message(&content) else { continue };
+        if min_priority.is_some_and(|min| msg.priority < min) {
+            continue;
+        }
+        let id = path.file_stem().unwrap().to_string_lossy();
+        let mut entry = json!({
+            "id": id,
+            "name": msg.name,
+            "timestamp": format::iso8601_ns(id.parse().unwrap_or(0)),
+            "body": msg.body,
+        });
+        if let Some(reply_to) = msg.reply_to {
+            entry["reply_to"] = json!(reply_to);
+        }
+        if !msg.refs.is_empty() {
+            entry["refs"] = json!(msg.refs);
+        }
+        messages.push(entry);
+    }
+    println!("{}", serde_json::to_string_pretty(&messages)?);
     Ok(())
 }
+
+/// Print messages at or above `min_priority`. Returns how many were printed.
+fn print_messages(message_paths: &[PathBuf], min_priority: Option<Priority>) -> usize {
+    let rendered = render_messages(message_paths, min_priority);
+    for (_, line) in &rendered {
+        println!("{}", line);
+    }
+    rendered.len()
+}
+
+/// Print messages in per-author sections, ordered by each author's first
+/// message and chronological within a section.
+fn print_grouped_by_author(message_paths: &[PathBuf], min_priority: Option<Priority>) {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for (author, line) in render_messages(message_paths, min_priority) {
+        match groups.iter_mut().find(|(name, _)| *name == author) {
+            Some((_, lines)) => lines.push(line),
+            None => groups.push((author, vec![line])),
+        }
+    }
+
+    for (i, (author, lines)) in groups.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("=== {} ({}) ===", author, lines.len());
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::fs;
+use std::path::Path;
+use crate::error::{AgentChatError, Result};
+use crate::format;
+use crate::storage::{cursor, log, paths, session};
+
+/// Report which registered agents have read message `id`: those whose cursor
+/// is at or past its timestamp. Sessions that never read count as unread.
+/// The author is left out, and a direct message only lists its recipient.
+pub fn run(root: &Path, id: &str) -> Result<()> {
+    let path = log::find_message(&paths::log_dir(root), id)
+        .ok_or_else(|| AgentChatError::Other(format!("Message not found: {}", id)))?;
+    let position: u128 = id
+        .parse()
+        .map_err(|_| AgentChatError::Other(format!("Message not found: {}", id)))?;
+    let content = fs::read_to_string(&path)?;
+    let message = format::parse_message(&content)
+        .ok_or_else(|| AgentChatError::Other(format!("Message {} is malformed", id)))?;
+
+    let cursors_dir = paths::cursors_dir(root);
+    let mut read = Vec::new();
+    let mut unread = Vec::new();
+    for (session_id, name) in session::list_sessions(&paths::sessions_dir(root))? {
+        if name == message.name || message.to.is_some_and(|to| to != name) {
+            continue;
+        }
+        if cursor::position(&cursor::cursor_path(&cursors_dir, &session_id)) >= position {
+            read.push(name);
+        } else {
+            unread.push(name);
+        }
+    }
+    for names in [&mut read, &mut unread] {
+        names.sort();
+        names.dedup();
+    }
+    // A name with one session caught up and another behind has read it
+    unread.retain(|name| !read.contains(name));
+
+    println!("read by {}; unread by {}", join_or_nobody(&read), join_or_nobody(&unread));
+    Ok(())
+}
+
+fn join_or_nobody(names: &[String]) -> String {
+    if names.is_empty() {
+        "nobody".to_string()
+    } else {
+        names.join(", ")
+    }
+}
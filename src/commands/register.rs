@@ -3,40 +3,70 @@ use serde_json::json;
 use crate::error::{AgentChatError, Result};
 use crate::format;
 use crate::hooks::stdin;
-use crate::names;
-use crate::storage::{cursor, focus, log, paths, session};
+use crate::names::{self, NameStyle};
+use crate::storage::{atomic, config, cursor, focus, identity, log, paths, session};
+use crate::ui;
 
-pub fn run(root: &Path, session_id: Option<&str>) -> Result<()> {
-    let session_id = resolve_session_id(session_id)?;
+/// Register a session. New sessions take `preferred_name` (or
+/// `AGENT_CHAT_PREFERRED_NAME`) when given, suffixed if another session
+/// already uses it, and a generated name otherwise.
+///
+/// A known session keeps its name. With `session_type: "startup"` it is a
+/// fresh process reusing the id, so it announces itself and is treated like a
+/// new session; `"resume"` stays quiet unless `announce_resume` is set.
+///
+/// With `print_env`, stdout carries only `export` lines for `eval`, and
+/// unread messages are left for `read` instead of being injected.
+pub fn run(root: &Path, session_id: Option<&str>, preferred_name: Option<&str>, print_env: bool) -> Result<()> {
+    let (session_id, session_type) = match session_id {
+        Some(_) => (resolve_session_id(session_id)?, None),
+        None => {
+            let input = stdin::read_session_start()?;
+            (input.session_id, input.session_type)
+        }
+    };
+    let env_preferred = std::env::var("AGENT_CHAT_PREFERRED_NAME").ok();
+    let preferred = preferred_name
+        .or(env_preferred.as_deref())
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    if let Some(name) = preferred {
+        names::validate_name(name)?;
+    }
 
     let sessions_dir = paths::sessions_dir(root);
     let log_dir = paths::log_dir(root);
     let cursors_dir = paths::cursors_dir(root);
     let cursor_file = cursor::cursor_path(&cursors_dir, &session_id);
 
-    // Check if already registered (idempotent)
-    let (name, is_new) = if let Some(existing) = session::read_session(&sessions_dir, &session_id)? {
-        (existing, false)
-    } else {
-        let name = names::generate_name();
+    // Check if already registered (idempotent). Choosing and writing a name
+    // happens under a lock so concurrent registrations can't pick the same one.
+    let config = config::read_config(&paths::config_path(root))?;
+    let (name, registered) = atomic::with_lock(&paths::sessions_lock_path(root), || {
+        if let Some(existing) = session::read_session(&sessions_dir, &session_id)? {
+            return Ok((existing, false));
+        }
+        let name = match preferred {
+            Some(name) => session::first_free_name(&sessions_dir, name, &session_id)?,
+            None => generate_unique_name(&sessions_dir, &session_id, config.name_style)?,
+        };
         session::write_session(&sessions_dir, &session_id, &name)?;
-        (name, true)
-    };
+        Ok((name, true))
+    })?;
 
-    // Post join message for new sessions only
-    if is_new {
+    let is_new = registered || session_type.as_deref() == Some("startup");
+    if registered {
         log::write_message(&log_dir, &name, "joined the chat")?;
+    } else if is_new {
+        log::write_message(&log_dir, &name, "rejoined the chat")?;
+    } else if config.announce_resume && session_type.as_deref() == Some("resume") {
+        log::write_message(&log_dir, &name, "resumed the chat")?;
     }
 
-    // Write to CLAUDE_ENV_FILE if set
-    if let Ok(env_file) = std::env::var("CLAUDE_ENV_FILE") {
-        let content = format!(
-            "export AGENT_CHAT_NAME={}\nexport AGENT_CHAT_SESSION_ID={}\n",
-            name, session_id
-        );
-        std::fs::write(&env_file, content).map_err(|e| {
-            AgentChatError::Other(format!("Failed to write CLAUDE_ENV_FILE: {}", e))
-        })?;
+    write_env_file_or_warn(&name, &session_id);
+    if print_env {
+        print!("{}", env_exports(&name, &session_id));
+        return Ok(());
     }
 
     // Build identity string
@@ -57,15 +87,17 @@ pub fn run(root: &Path, session_id: Option<&str>) -> Result<()> {
         }
     }
 
-    // Inject existing unread messages
+    // Inject existing unread messages. Injected messages count as read. With
+    // nothing to inject, the cursor only moves for new sessions (past our own
+    // join message) unless `register_consumes_backlog` is set, in which case
+    // every register leaves the session with zero unread.
     let unread = cursor::get_unread_messages(&log_dir, &cursor_file, 50, Some(&name))?;
     let context = if !unread.is_empty() {
         let formatted = format::format_messages_from_paths(&unread);
         cursor::advance(&cursor_file)?;
         format!("{}\n{}", identity, formatted)
     } else {
-        // Still advance cursor so we don't re-deliver our own join message later
-        if is_new {
+        if is_new || config.register_consumes_backlog {
             cursor::advance(&cursor_file)?;
         }
         identity
@@ -80,13 +112,120 @@ pub fn run(root: &Path, session_id: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Retries for a generated name nobody else holds before falling back to a suffix.
+const GENERATE_ATTEMPTS: usize = 20;
+
+/// Generate a name no other session is using. Must run under the sessions lock.
+fn generate_unique_name(sessions_dir: &Path, session_id: &str, style: NameStyle) -> Result<String> {
+    if style == NameStyle::Numbered {
+        // Start after the existing sessions and skip numbers already taken
+        let mut ordinal = session::list_sessions(sessions_dir)?.len() + 1;
+        while session::name_in_use(sessions_dir, &names::generate_name_for(style, ordinal), session_id)? {
+            ordinal += 1;
+        }
+        return Ok(names::generate_name_for(style, ordinal));
+    }
+
+    let mut name = names::generate_name();
+    for _ in 1..GENERATE_ATTEMPTS {
+        if !session::name_in_use(sessions_dir, &name, session_id)? {
+            return Ok(name);
+        }
+        name = names::generate_name();
+    }
+    session::first_free_name(sessions_dir, &name, session_id)
+}
+
+/// Rename an already-registered session. Deliberately not idempotent: posts a
+/// rename notice so others can follow. Past messages keep their original author.
+pub fn replace_name(root: &Path, session_id: Option<&str>, new_name: &str) -> Result<()> {
+    change_name(root, session_id, new_name, |old| format!("renamed from {} to {}", old, new_name))
+}
+
+/// `rename`: like `replace_name` for the current session, announced as
+/// "<old> is now known as <new>".
+pub fn rename(root: &Path, new_name: &str) -> Result<()> {
+    change_name(root, None, new_name, |old| format!("{} is now known as {}", old, new_name))
+}
+
+fn change_name(
+    root: &Path,
+    session_id: Option<&str>,
+    new_name: &str,
+    notice: impl Fn(&str) -> String,
+) -> Result<()> {
+    let session_id = match session_id {
+        Some(_) => resolve_session_id(session_id)?,
+        None => identity::resolve(root)?.session_id,
+    };
+    names::validate_name(new_name)?;
+
+    let sessions_dir = paths::sessions_dir(root);
+    let old_name = session::read_session(&sessions_dir, &session_id)?.ok_or_else(|| {
+        AgentChatError::Other(format!("session {} is not registered", session_id))
+    })?;
+    if old_name == new_name {
+        return Ok(());
+    }
+    atomic::with_lock(&paths::sessions_lock_path(root), || {
+        if session::name_in_use(&sessions_dir, new_name, &session_id)? {
+            return Err(AgentChatError::Other(format!("name '{}' is already taken", new_name)));
+        }
+        session::write_session(&sessions_dir, &session_id, new_name)
+    })?;
+    log::write_message(&paths::log_dir(root), new_name, &notice(&old_name))?;
+    write_env_file_or_warn(new_name, &session_id);
+
+    println!("{}", ui::success_line("Renamed:", &format!("{} -> {}", old_name, new_name)));
+    println!("export AGENT_CHAT_NAME={}", new_name);
+    Ok(())
+}
+
+/// Shell `export` lines for the session's identity.
+fn env_exports(name: &str, session_id: &str) -> String {
+    format!(
+        "export AGENT_CHAT_NAME={}\nexport AGENT_CHAT_SESSION_ID={}\n",
+        shell_quote(name),
+        shell_quote(session_id)
+    )
+}
+
+/// Single-quote `value` for a POSIX shell unless it is made only of
+/// characters that never need quoting.
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_.:/@".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Write identity exports to CLAUDE_ENV_FILE if set.
+fn write_env_file(name: &str, session_id: &str) -> Result<()> {
+    if let Ok(env_file) = std::env::var("CLAUDE_ENV_FILE") {
+        std::fs::write(&env_file, env_exports(name, session_id)).map_err(|e| {
+            AgentChatError::Other(format!("Failed to write CLAUDE_ENV_FILE: {}", e))
+        })?;
+    }
+    Ok(())
+}
+
+/// `write_env_file`, reporting a failure on stderr instead of returning it:
+/// the session is already registered, so the caller should still print the
+/// identity rather than leave the agent with nothing.
+fn write_env_file_or_warn(name: &str, session_id: &str) {
+    if let Err(e) = write_env_file(name, session_id) {
+        eprintln!("{}", ui::warning(&format!("WARNING: {}", e)));
+    }
+}
+
 fn resolve_session_id(explicit: Option<&str>) -> Result<String> {
     if let Some(id) = explicit {
         let trimmed = id.trim();
         if trimmed.is_empty() {
-            return Err(AgentChatError::Other(
-                "session_id cannot be empty".to_string()
-            ));
+            return Err(AgentChatError::EmptySessionId);
         }
         return Ok(trimmed.to_string());
     }
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::Path;
+use crate::error::{AgentChatError, Result};
+use crate::names;
+use crate::storage::{config, paths};
+use crate::ui;
+
+/// Name shown for the room that lives directly in `.agent-chat/`.
+const DEFAULT_ROOM: &str = "default";
+
+fn active_room(root: &Path) -> Result<String> {
+    let config = config::read_config(&paths::config_path(root))?;
+    Ok(if config.room.is_empty() { DEFAULT_ROOM.to_string() } else { config.room })
+}
+
+fn named_rooms(root: &Path) -> Result<Vec<String>> {
+    let mut rooms = Vec::new();
+    let rooms_dir = paths::rooms_dir(root);
+    if !rooms_dir.is_dir() {
+        return Ok(rooms);
+    }
+    for entry in fs::read_dir(&rooms_dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            rooms.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    rooms.sort();
+    Ok(rooms)
+}
+
+/// Reject reserved and malformed room names, and names with no room behind them
+/// when `must_exist` is set.
+fn check_name(root: &Path, name: &str, must_exist: bool) -> Result<()> {
+    if name == DEFAULT_ROOM {
+        return Err(AgentChatError::Other(format!("'{}' is the built-in room", DEFAULT_ROOM)));
+    }
+    names::validate_name(name)?;
+    if must_exist && !paths::rooms_dir(root).join(name).is_dir() {
+        return Err(AgentChatError::Other(format!("No such room: {}", name)));
+    }
+    Ok(())
+}
+
+/// List rooms, marking the active one with `*`.
+pub fn list(root: &Path) -> Result<()> {
+    let active = active_room(root)?;
+    let mut rooms = vec![DEFAULT_ROOM.to_string()];
+    rooms.extend(named_rooms(root)?);
+    for room in &rooms {
+        let mark = if *room == active { "*" } else { " " };
+        println!("{} {}", mark, room);
+    }
+    Ok(())
+}
+
+/// Create a room with its own log, cursors, locks, and focuses.
+pub fn create(root: &Path, name: &str) -> Result<()> {
+    check_name(root, name, false)?;
+    let dir = paths::rooms_dir(root).join(name);
+    if dir.exists() {
+        return Err(AgentChatError::Other(format!("Room already exists: {}", name)));
+    }
+    paths::create_room_dirs(&dir)?;
+    println!("{}", ui::success_line("Created room:", name));
+    Ok(())
+}
+
+/// Delete a room and everything in it. The active room can't be removed.
+pub fn remove(root: &Path, name: &str) -> Result<()> {
+    check_name(root, name, true)?;
+    if active_room(root)? == name {
+        return Err(AgentChatError::Other(format!(
+            "Room {} is active; switch with `agent-chat room use <name>` first",
+            name
+        )));
+    }
+    fs::remove_dir_all(paths::rooms_dir(root).join(name))?;
+    println!("{}", ui::success_line("Removed room:", name));
+    Ok(())
+}
+
+/// Make `name` the active room for every agent in the project.
+pub fn switch(root: &Path, name: &str) -> Result<()> {
+    let value = if name == DEFAULT_ROOM {
+        String::new()
+    } else {
+        check_name(root, name, true)?;
+        name.to_string()
+    };
+    config::set_value(&paths::config_path(root), "room", &format!("\"{}\"", value))?;
+    println!("{}", ui::success_line("Active room:", name));
+    Ok(())
+}
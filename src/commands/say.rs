@@ -1,12 +1,238 @@
+use std::fs;
+use std::io::Read;
 use std::path::Path;
-use crate::error::Result;
-use crate::storage::{identity, log, paths};
+use std::time::SystemTime;
+use crate::error::{AgentChatError, Result};
+use crate::format::{self, AgentStatus, Priority};
+use crate::names;
+use crate::storage::{config, identity, log, paths, session};
+use crate::ui;
+
+/// Maximum characters of the referenced message kept by `--quote`.
+const QUOTE_MAX_CHARS: usize = 80;
+
+/// Appended to a body cut down by `say --truncate`.
+const TRUNCATED_MARKER: &str = "… [truncated]";
+
+/// Optional extras for a posted message.
+#[derive(Debug, Default)]
+pub struct SayOptions<'a> {
+    /// Id of the message being replied to.
+    pub reply_to: Option<&'a str>,
+    /// Prepend a truncated quote of the replied-to message.
+    pub quote: bool,
+    /// Urgency stored in a `priority:` header; decides whether `status` blocks.
+    pub priority: Option<Priority>,
+    /// Structured work status stored in a `status:` header.
+    pub status: Option<AgentStatus>,
+    /// Recipient of a direct message, stored in a `to:` header.
+    pub to: Option<&'a str>,
+    /// Delay before delivery, e.g. `10m`; `None` delivers immediately.
+    pub delay: Option<&'a str>,
+    /// Seconds after delivery until the message expires and is pruned.
+    pub ttl_secs: Option<u64>,
+    /// Cut an oversized body down to `max_message_bytes` instead of rejecting it.
+    pub truncate: bool,
+    /// Paths the message is about, stored in a `refs:` header.
+    pub refs: &'a [String],
+}
+
+/// Resolve the message text: the positional words, stdin for a lone `-`, or
+/// the contents of `file`. Trailing whitespace from files and pipes is dropped.
+pub fn message_text(words: &[String], file: Option<&Path>) -> Result<String> {
+    let raw = match (file, words) {
+        (Some(path), _) => {
+            let bytes = fs::read(path).map_err(|e| {
+                AgentChatError::Other(format!("Cannot read {}: {}", path.display(), e))
+            })?;
+            String::from_utf8(bytes).map_err(|_| {
+                AgentChatError::Other(format!("{} is not valid UTF-8", path.display()))
+            })?
+        }
+        (None, [dash]) if dash == "-" => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text).map_err(|e| {
+                AgentChatError::Other(format!("Cannot read message from stdin: {}", e))
+            })?;
+            text
+        }
+        (None, _) => return Ok(words.join(" ")),
+    };
+    Ok(raw.trim_end().to_string())
+}
 
 pub fn run(root: &Path, message: &str) -> Result<()> {
+    run_with(root, message, &SayOptions::default())
+}
+
+/// Acknowledge message `id` with a canned reply.
+pub fn ack(root: &Path, id: &str) -> Result<()> {
+    run_with(root, format::ACK_BODY, &SayOptions { reply_to: Some(id), ..Default::default() })
+}
+
+/// Escalate to one agent: a direct message to `recipient` at high priority, so
+/// it blocks their `status`. Warns (but still posts) if no session has that name.
+pub fn notify(root: &Path, recipient: &str, message: &str) -> Result<()> {
+    names::validate_name(recipient)?;
+    let sessions = session::list_sessions(&paths::sessions_dir(root))?;
+    if !sessions.iter().any(|(_, name)| name == recipient) {
+        eprintln!(
+            "{}",
+            ui::warning(&format!("WARNING: no registered session is named '{}'; posting anyway", recipient))
+        );
+    }
+    let opts = SayOptions { to: Some(recipient), priority: Some(Priority::High), ..Default::default() };
+    run_with(root, message, &opts)
+}
+
+pub fn run_with(root: &Path, message: &str, opts: &SayOptions) -> Result<()> {
     let id = identity::resolve(root)?;
     let name = identity::require_name(&id)?;
 
     let log_dir = paths::log_dir(root);
-    log::write_message(&log_dir, name, message)?;
+    let mut headers = Vec::new();
+    let mut body = message.to_string();
+
+    if let Some(priority) = opts.priority {
+        headers.push(("priority", priority.as_str()));
+    }
+
+    if let Some(parent) = opts.reply_to {
+        let parent_path = log::find_message(&log_dir, parent)
+            .ok_or_else(|| AgentChatError::Other(format!("Message not found: {}", parent)))?;
+        if opts.quote {
+            let content = fs::read_to_string(&parent_path)?;
+            if let Some((author, _, parent_body)) = format::parse_message_file(&content) {
+                let quote = format::quote_message(author, parent_body, QUOTE_MAX_CHARS);
+                body = format!("{}\n{}", quote, body);
+            }
+        }
+        headers.push(("reply_to", parent));
+    }
+
+    let config = config::read_config(&paths::config_path(root))?;
+    let max_bytes = config.max_message_bytes;
+    if max_bytes > 0 && body.len() > max_bytes {
+        if !opts.truncate {
+            return Err(AgentChatError::Other(format!(
+                "Message is {} bytes, over the {}-byte limit (max_message_bytes). Shorten it or use --truncate.",
+                body.len(),
+                max_bytes
+            )));
+        }
+        body = truncate_body(&body, max_bytes);
+    }
+
+    if let Some(recipient) = opts.to {
+        names::validate_name(recipient)?;
+        headers.push(("to", recipient));
+    }
+    if let Some(status) = opts.status {
+        headers.push(("status", status.as_str()));
+    }
+    let ref_paths: Vec<&str> = opts.refs.iter().map(|r| r.trim().trim_start_matches("./")).collect();
+    let refs;
+    if !opts.refs.is_empty() {
+        if let Some(bad) = opts.refs.iter().find(|r| r.trim().is_empty() || r.contains([',', '\n'])) {
+            return Err(AgentChatError::Other(format!(
+                "Invalid --ref '{}': paths must be non-empty and contain no commas or newlines",
+                bad
+            )));
+        }
+        refs = ref_paths.join(", ");
+        headers.push(("refs", &refs));
+    }
+
+    let delay_secs = match opts.delay {
+        Some(delay) => Some(format::parse_duration(delay).ok_or_else(|| {
+            AgentChatError::Other(format!(
+                "Invalid delay '{}': use a duration like 30s, 10m, or 2h",
+                delay
+            ))
+        })?),
+        None => None,
+    };
+
+    let expires_at;
+    if let Some(ttl) = opts.ttl_secs {
+        // Count the TTL from delivery, so a scheduled message isn't born expired
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let lifetime = delay_secs.unwrap_or(0).saturating_add(ttl);
+        expires_at = (now + lifetime as u128 * 1_000_000_000).to_string();
+        headers.push(("expires_at", &expires_at));
+    }
+
+    if delay_secs.is_none() && config.dedup_window_secs > 0 && repeats_last(&log_dir, name, opts, &ref_paths, &body, config.dedup_window_secs)? {
+        return Ok(());
+    }
+
+    match delay_secs {
+        Some(secs) => {
+            let deliver_at = log::schedule_message(&log_dir, secs, name, &headers, &body)?;
+            let when = format::parse_timestamp_ns(&deliver_at.to_string()).format("%H:%M");
+            println!("{}", ui::info_line("Scheduled:", &format!("delivery at {}", when)));
+        }
+        None => log::write_message_with_headers(&log_dir, name, &headers, &body)?,
+    }
     Ok(())
 }
+
+/// Cut `body` to at most `max_bytes` (on a char boundary), ending with the
+/// truncation marker. A limit too small for the marker gets a bare `…`, or no
+/// marker at all, so the result never exceeds `max_bytes`.
+fn truncate_body(body: &str, max_bytes: usize) -> String {
+    let marker = [TRUNCATED_MARKER, "…", ""]
+        .into_iter()
+        .find(|m| m.len() <= max_bytes)
+        .unwrap_or_default();
+    let mut end = max_bytes - marker.len();
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", body[..end].trim_end(), marker)
+}
+
+/// True if `name`'s latest message within `window_secs` has the same body,
+/// recipient, parent, status, priority, and refs, so e.g. acks of two
+/// different messages are never collapsed.
+fn repeats_last(
+    log_dir: &Path,
+    name: &str,
+    opts: &SayOptions,
+    refs: &[&str],
+    body: &str,
+    window_secs: u64,
+) -> Result<bool> {
+    let Some(path) = log::latest_from(log_dir, name, window_secs)? else {
+        return Ok(false);
+    };
+    let content = fs::read_to_string(&path)?;
+    Ok(format::parse_message(&content).is_some_and(|m| {
+        m.to == opts.to
+            && m.reply_to == opts.reply_to
+            && m.status == opts.status
+            && m.priority == opts.priority.unwrap_or(Priority::Normal)
+            && m.refs == refs
+            && m.body == body.trim_end()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_body_never_exceeds_limit() {
+        let body = "é".repeat(100);
+        for max in 0..40 {
+            let cut = truncate_body(&body, max);
+            assert!(cut.len() <= max, "{} bytes for max {}", cut.len(), max);
+        }
+        assert_eq!(truncate_body(&body, 5), "é…");
+        assert_eq!(truncate_body(&body, 2), "é");
+        assert!(truncate_body(&body, 40).ends_with(TRUNCATED_MARKER));
+    }
+}
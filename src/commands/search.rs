@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::Path;
+use crate::error::Result;
+use crate::format;
+use crate::storage::{identity, log, paths};
+
+/// Print messages whose body contains `query` (case-insensitive), optionally
+/// limited to one author and to a recent window like `2h` or `1d`.
+/// Read-only: the cursor is not touched. Direct messages between other
+/// agents are left out.
+pub fn run(root: &Path, query: &str, author: Option<&str>, since: Option<&str>) -> Result<()> {
+    let reader = identity::resolve(root).ok().and_then(|id| id.name);
+    let cutoff = since.map(format::cutoff_ns).transpose()?;
+    let needle = query.to_lowercase();
+
+    for (filename, path) in log::list_messages(&paths::log_dir(root))? {
+        let id = filename.trim_end_matches(".md");
+        if let Some(cutoff) = cutoff {
+            if id.parse::<u128>().map_or(true, |ts| ts < cutoff) {
+                continue;
+            }
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some(msg) = format::parse_message(&content) else {
+            continue;
+        };
+        if !msg.is_visible_to(reader.as_deref())
+            || author.is_some_and(|a| a != msg.name)
+            || !msg.body.to_lowercase().contains(&needle)
+        {
+            continue;
+        }
+        let ts = format::parse_timestamp_ns(id);
+        println!("{}", format::format_parsed(&msg, ts));
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use serde_json::json;
+use crate::error::Result;
+use crate::storage::{focus, lockfile, log, paths, session};
+use crate::ui;
+
+/// Author of a message file, read from its first line only so large logs stay fast.
+fn author(path: &Path) -> Option<String> {
+    let mut first = String::new();
+    BufReader::new(File::open(path).ok()?).read_line(&mut first).ok()?;
+    first.trim_end().strip_prefix("name: ").map(str::to_string)
+}
+
+/// Print counts of agents, messages (total and per author), locks, and focuses.
+pub fn run(root: &Path, as_json: bool) -> Result<()> {
+    let agents = session::list_sessions(&paths::sessions_dir(root))?.len();
+    let locks = lockfile::list_active(&paths::locks_dir(root))?.len();
+    let focuses = focus::list_active(&paths::focuses_dir(root))?.len();
+
+    let messages = log::list_messages(&paths::log_dir(root))?;
+    let mut per_author: Vec<(String, usize)> = Vec::new();
+    for (_, path) in &messages {
+        let Some(name) = author(path) else { continue };
+        match per_author.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, count)) => *count += 1,
+            None => per_author.push((name, 1)),
+        }
+    }
+    // Busiest first, then by name for a stable order
+    per_author.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    if as_json {
+        let by_author: serde_json::Map<String, serde_json::Value> =
+            per_author.iter().map(|(name, count)| (name.clone(), json!(count))).collect();
+        let stats = json!({
+            "agents": agents,
+            "messages": messages.len(),
+            "messages_by_author": by_author,
+            "locks": locks,
+            "focuses": focuses,
+        });
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!("{}", ui::info_line("Agents:", &agents.to_string()));
+    println!("{}", ui::info_line("Messages:", &messages.len().to_string()));
+    println!("{}", ui::info_line("Locks:", &locks.to_string()));
+    println!("{}", ui::info_line("Focuses:", &focuses.to_string()));
+    if !per_author.is_empty() {
+        println!();
+        println!("{}", ui::table_header("AGENT", "MESSAGES", None));
+        for (name, count) in &per_author {
+            println!("{:<15} {}", name, count);
+        }
+    }
+    Ok(())
+}
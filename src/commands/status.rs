@@ -1,10 +1,9 @@
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use serde_json::json;
 use crate::error::Result;
 use crate::format;
-use crate::storage::{cursor, identity, paths};
-
-const DEFAULT_FIRST_READ_COUNT: usize = 10;
+use crate::storage::{config, cursor, identity, lockfile, paths};
 
 pub fn run(root: &Path) -> Result<()> {
     let log_dir = paths::log_dir(root);
@@ -16,38 +15,143 @@ pub fn run(root: &Path) -> Result<()> {
     let session_id = id.session_id.as_str();
     let exclude = id.name.as_deref();
 
+    let config = config::read_config(&paths::config_path(root))?;
+    let lock_warnings = expiring_lock_warnings(root, session_id, config.status_lock_expiry_secs)?;
+
     let cursors_dir = paths::cursors_dir(root);
     let cursor_file = cursor::cursor_path(&cursors_dir, session_id);
     let has_unread = cursor::has_unread(&log_dir, &cursor_file)?;
 
     if !has_unread {
-        return Ok(());
+        return report_clear(&config, &lock_warnings);
     }
 
     // Get unread message paths
     let message_paths = cursor::get_unread_messages(
         &log_dir,
         &cursor_file,
-        DEFAULT_FIRST_READ_COUNT,
+        config.first_read_count,
         exclude,
     )?;
 
     if message_paths.is_empty() {
-        return Ok(());
+        return report_clear(&config, &lock_warnings);
     }
 
-    let formatted = format::format_messages_for_status(&message_paths);
+    let mut formatted = format::format_messages_for_status(&message_paths);
     if formatted.is_empty() {
-        return Ok(());
+        return report_clear(&config, &lock_warnings);
+    }
+    for warning in &lock_warnings {
+        formatted.push_str(&format!("\n\n{}", warning));
     }
 
-    // Output decision:block JSON — prevents agent from stopping without reading
     // Do NOT advance cursor — agent should run `agent-chat read` to formally process
+    let blocks = record_block(&cursors_dir, session_id, &message_paths)?;
+    let pinned = has_author(&message_paths, &config.always_block_from);
+    let downgrade = !pinned
+        && (!has_priority(&message_paths, config.status_block_priority)
+            || (config.status_block_limit > 0 && blocks > config.status_block_limit));
+
+    let output = if downgrade {
+        // Only low-priority messages, or blocked enough times on this backlog:
+        // pass it along without stopping the agent
+        json!({
+            "hookSpecificOutput": {
+                "additionalContext": formatted
+            }
+        })
+    } else {
+        // decision:block prevents the agent from stopping without reading
+        json!({
+            "decision": "block",
+            "reason": formatted
+        })
+    };
+    print!("{}", serde_json::to_string(&output)?);
+
+    Ok(())
+}
+
+/// One reminder per lock held by `session_id` that expires within
+/// `window_secs`. These repeat on every Stop until the lock is renewed or
+/// released, and are seen only by the owner.
+fn expiring_lock_warnings(root: &Path, session_id: &str, window_secs: u64) -> Result<Vec<String>> {
+    if window_secs == 0 {
+        return Ok(Vec::new());
+    }
+    let mut locks: Vec<_> = lockfile::list_active(&paths::locks_dir(root))?
+        .into_iter()
+        .filter(|l| l.session_id == session_id && l.remaining_secs() <= window_secs)
+        .collect();
+    locks.sort_by(|a, b| a.glob.cmp(&b.glob));
+    Ok(locks
+        .iter()
+        .map(|l| format!("your lock on {} expires in {}s — renew or unlock", l.glob, l.remaining_secs()))
+        .collect())
+}
+
+/// Count consecutive blocks on the same backlog (identified by its newest
+/// message), stored as `<message_id> <count>` next to the session's cursor.
+/// Returns the count including this block.
+fn record_block(cursors_dir: &Path, session_id: &str, message_paths: &[PathBuf]) -> Result<u32> {
+    let newest = message_paths
+        .last()
+        .and_then(|p| p.file_stem())
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let state_file = cursors_dir.join(format!("{}.blocks", session_id));
+
+    let previous = fs::read_to_string(&state_file).ok().and_then(|content| {
+        let (id, count) = content.trim().split_once(' ')?;
+        (id == newest).then(|| count.parse::<u32>().ok()).flatten()
+    });
+    let count = previous.unwrap_or(0).saturating_add(1);
+    fs::write(&state_file, format!("{} {}\n", newest, count))?;
+    Ok(count)
+}
+
+/// True if any message was written by one of `authors`.
+fn has_author(message_paths: &[PathBuf], authors: &[String]) -> bool {
+    if authors.is_empty() {
+        return false;
+    }
+    message_paths.iter().any(|path| {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| format::parse_message(&content).map(|m| authors.iter().any(|a| a == m.name)))
+            .unwrap_or(false)
+    })
+}
+
+/// True if any message is at or above `min` priority.
+fn has_priority(message_paths: &[PathBuf], min: format::Priority) -> bool {
+    message_paths.iter().any(|path| {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| format::parse_message(&content).map(|m| m.priority >= min))
+            .unwrap_or(false)
+    })
+}
+
+/// Nothing unread. Silent by default; with `status_report_clear` set, emit a
+/// non-blocking context line so users can confirm the Stop hook ran. Lock
+/// expiry warnings are passed along the same way, without blocking.
+fn report_clear(config: &config::Config, lock_warnings: &[String]) -> Result<()> {
+    let mut lines = Vec::new();
+    if config.status_report_clear {
+        lines.push("No unread messages.");
+    }
+    lines.extend(lock_warnings.iter().map(String::as_str));
+    if lines.is_empty() {
+        return Ok(());
+    }
+
     let output = json!({
-        "decision": "block",
-        "reason": formatted
+        "hookSpecificOutput": {
+            "additionalContext": lines.join("\n")
+        }
     });
     print!("{}", serde_json::to_string(&output)?);
-
     Ok(())
 }
@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::Path;
+use crate::error::{AgentChatError, Result};
+use crate::storage::{config, paths};
+use crate::ui;
+use crate::hooks::{agents_md_codex, claude_md, claude_md_br, continue_rules, cursor_rules, gemini_md, installer, windsurf_rules};
+
+/// A tool integration that `init` can install.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Tool {
+    /// Hooks, permissions, and CLAUDE.md sections
+    Claude,
+    /// AGENTS.md section
+    Codex,
+    /// Continue.dev rules file
+    Continue,
+    /// GEMINI.md section
+    Gemini,
+    /// Cursor rule file
+    Cursor,
+    /// Windsurf rules section
+    Windsurf,
+}
+
+const ALL_TOOLS: &[Tool] = &[Tool::Claude, Tool::Codex, Tool::Continue, Tool::Gemini, Tool::Cursor, Tool::Windsurf];
+
+impl Tool {
+    fn label(self) -> &'static str {
+        match self {
+            Tool::Claude => "Claude",
+            Tool::Codex => "Codex",
+            Tool::Continue => "Continue",
+            Tool::Gemini => "Gemini",
+            Tool::Cursor => "Cursor",
+            Tool::Windsurf => "Windsurf",
+        }
+    }
+}
+
+/// Remove a tool's project-level integration: `.claude/settings.local.json` +
+/// `./CLAUDE.md`, `./AGENTS.md`, `.continue/rules/agent-chat.md`, `./GEMINI.md`,
+/// `.cursor/rules/agent-chat.mdc`, or `./.windsurfrules`.
+fn uninstall_project(project_root: &Path, tool: Tool, label: Option<&str>) -> Result<()> {
+    match tool {
+        Tool::Claude => {
+            installer::uninstall_hooks_from(&project_root.join(".claude"), "settings.local.json")?;
+            claude_md::remove_claude_md_from(project_root, label)?;
+            claude_md_br::remove_br_claude_md_from(project_root)?;
+        }
+        Tool::Codex => agents_md_codex::remove_agents_md_from(project_root)?,
+        Tool::Continue => continue_rules::remove_continue_rules_from(&project_root.join(".continue"))?,
+        Tool::Gemini => gemini_md::remove_gemini_md_from(project_root)?,
+        Tool::Cursor => cursor_rules::remove_cursor_rules_from(&project_root.join(".cursor"))?,
+        Tool::Windsurf => windsurf_rules::remove_windsurf_rules_from(project_root, windsurf_rules::PROJECT_FILE)?,
+    }
+    Ok(())
+}
+
+/// Remove a tool's user-level integration: `~/.claude/settings.json` +
+/// `~/.claude/CLAUDE.md`, `~/.codex/AGENTS.md`, `~/.continue/rules/agent-chat.md`,
+/// `~/.gemini/GEMINI.md`, `~/.cursor/rules/agent-chat.mdc`, or
+/// `~/.codeium/windsurf/memories/global_rules.md`.
+fn uninstall_user(tool: Tool, label: Option<&str>) -> Result<()> {
+    let home = paths::home_dir()?;
+    match tool {
+        Tool::Claude => {
+            let claude_dir = home.join(".claude");
+            installer::uninstall_hooks_from(&claude_dir, "settings.json")?;
+            claude_md::remove_claude_md_from(&claude_dir, label)?;
+            claude_md_br::remove_br_claude_md_from(&claude_dir)?;
+        }
+        Tool::Codex => agents_md_codex::remove_agents_md_from(&home.join(".codex"))?,
+        Tool::Continue => continue_rules::remove_continue_rules_from(&home.join(".continue"))?,
+        Tool::Gemini => gemini_md::remove_gemini_md_from(&home.join(".gemini"))?,
+        Tool::Cursor => cursor_rules::remove_cursor_rules_from(&home.join(".cursor"))?,
+        Tool::Windsurf => windsurf_rules::remove_windsurf_rules_from(&windsurf_rules::user_dir(&home), windsurf_rules::USER_FILE)?,
+    }
+    Ok(())
+}
+
+/// Remove `tools` from the locations selected by the flags. Returns a scope
+/// label for the confirmation line.
+fn uninstall(project_root: &Path, tools: &[Tool], project: bool, user: bool, both: bool) -> Result<&'static str> {
+    let (do_project, do_user) = match (project, user, both) {
+        (_, _, true) | (true, true, _) => (true, true),
+        (true, false, false) => (true, false),
+        (false, true, false) => (false, true),
+        (false, false, false) => {
+            return Err(AgentChatError::Other(
+                "Specify --project, --user, or --both.".to_string(),
+            ))
+        }
+    };
+
+    // Only this setup's CLAUDE.md section goes; other labeled sections stay.
+    // A broken config must not keep a user from backing out the install.
+    let label = match config::read_config(&paths::config_path(&project_root.join(".agent-chat"))) {
+        Ok(config) => config.label().map(String::from),
+        Err(e) => {
+            eprintln!("{}", ui::warning(&format!("{}; removing the unlabeled agent-chat sections", e)));
+            None
+        }
+    };
+    for &tool in tools {
+        if do_project {
+            uninstall_project(project_root, tool, label.as_deref())?;
+        }
+        if do_user {
+            uninstall_user(tool, label.as_deref())?;
+        }
+    }
+
+    Ok(match (do_project, do_user) {
+        (true, true) => "project + user",
+        (true, false) => "project",
+        _ => "user",
+    })
+}
+
+pub fn run(project_root: &Path, project: bool, user: bool, both: bool, purge: bool) -> Result<()> {
+    let scope = uninstall(project_root, ALL_TOOLS, project, user, both)?;
+
+    let data_dir = project_root.join(".agent-chat");
+    if purge && (data_dir.exists() || data_dir.is_symlink()) {
+        // A symlinked .agent-chat/ may be shared with other checkouts; only drop the link
+        if data_dir.is_symlink() {
+            fs::remove_file(&data_dir)?;
+        } else {
+            fs::remove_dir_all(&data_dir)?;
+        }
+        println!("Removed agent-chat hooks and guidance ({}) and purged .agent-chat/.", scope);
+    } else {
+        println!("Removed agent-chat hooks and guidance ({}).", scope);
+    }
+    Ok(())
+}
+
+/// Remove a single tool's integration, leaving the others installed.
+pub fn run_tool(project_root: &Path, tool: Tool, project: bool, user: bool, both: bool) -> Result<()> {
+    let scope = uninstall(project_root, &[tool], project, user, both)?;
+    println!("Removed {} integration ({}).", tool.label(), scope);
+    Ok(())
+}
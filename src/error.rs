@@ -20,12 +20,36 @@ pub enum AgentChatError {
     #[error("Lock conflict: {glob} is locked by {owner}")]
     LockConflict { glob: String, owner: String },
 
+    #[error("Lock overlap: {glob} overlaps {other_glob} locked by {owner}")]
+    LockOverlap { glob: String, other_glob: String, owner: String },
+
     #[error("Lock not found: {0}")]
     LockNotFound(String),
 
     #[error("Missing environment variable: {0}")]
     MissingEnv(String),
 
+    #[error("No new messages within {0}s")]
+    WatchTimeout(u64),
+
+    #[error("doctor: {0} check(s) failed")]
+    DoctorFailed(usize),
+
+    #[error("invalid choice: {0}")]
+    InvalidChoice(String),
+
+    #[error("br (beads_rust) not found in PATH. Install it first: cargo install beads_rust")]
+    BrNotFound,
+
+    #[error("br {command} failed: {detail}")]
+    BrCommandFailed { command: String, detail: String },
+
+    #[error("session_id cannot be empty")]
+    EmptySessionId,
+
+    #[error("Could not parse {}; fix it and retry", .0.display())]
+    SettingsParseError(std::path::PathBuf),
+
     #[error("{0}")]
     Other(String),
 }
@@ -1,22 +1,271 @@
 use std::fs;
 use std::path::PathBuf;
-use chrono::{DateTime, Local, NaiveDateTime};
+use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat};
+
+/// Message priority, carried in an optional `priority:` header.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Priority> {
+        match s.trim() {
+            "low" => Some(Priority::Low),
+            "normal" => Some(Priority::Normal),
+            "high" => Some(Priority::High),
+            _ => None,
+        }
+    }
+}
+
+/// Structured work status, carried in an optional `status:` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AgentStatus {
+    Working,
+    Done,
+    Blocked,
+}
+
+impl AgentStatus {
+    pub fn parse(s: &str) -> Option<AgentStatus> {
+        match s.trim() {
+            "working" => Some(AgentStatus::Working),
+            "done" => Some(AgentStatus::Done),
+            "blocked" => Some(AgentStatus::Blocked),
+            _ => None,
+        }
+    }
+
+    /// Header value written to the message file.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AgentStatus::Working => "working",
+            AgentStatus::Done => "done",
+            AgentStatus::Blocked => "blocked",
+        }
+    }
+
+    /// Display prefix: emoji plus label, e.g. `⛔ blocked`.
+    pub fn label(self) -> &'static str {
+        match self {
+            AgentStatus::Working => "🚧 working",
+            AgentStatus::Done => "✅ done",
+            AgentStatus::Blocked => "⛔ blocked",
+        }
+    }
+}
+
+/// Canned body of an acknowledgement posted by `ack`.
+pub const ACK_BODY: &str = "👍 ack";
+
+/// A parsed message file: author, headers, and body.
+#[derive(Debug)]
+pub struct ParsedMessage<'a> {
+    pub name: &'a str,
+    pub priority: Priority,
+    pub reply_to: Option<&'a str>,
+    pub status: Option<AgentStatus>,
+    /// Recipient of a direct message; `None` broadcasts to everyone.
+    pub to: Option<&'a str>,
+    /// Delivery time (ns since the epoch) of a scheduled message.
+    pub deliver_at: Option<u128>,
+    /// Expiry time (ns since the epoch) of an ephemeral message.
+    pub expires_at: Option<u128>,
+    /// Paths the message is about, from a `refs:` header.
+    pub refs: Vec<&'a str>,
+    pub body: &'a str,
+}
+
+impl ParsedMessage<'_> {
+    /// Direct messages are visible only to their recipient and author;
+    /// broadcasts are visible to everyone. Scheduled messages are hidden from
+    /// everyone until their delivery time, ephemeral ones once they expire.
+    pub fn is_visible_to(&self, reader: Option<&str>) -> bool {
+        if !self.is_delivered() || self.is_expired() {
+            return false;
+        }
+        match self.to {
+            Some(to) => reader == Some(to) || reader == Some(self.name),
+            None => true,
+        }
+    }
+
+    /// False while a scheduled message's `deliver_at` is still in the future.
+    pub fn is_delivered(&self) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        self.deliver_at.is_none_or(|at| at <= now)
+    }
+
+    /// True when visibility depends only on the author: no recipient, delivery
+    /// time, or expiry. Only such messages go into the log's author index.
+    pub fn is_plain(&self) -> bool {
+        self.to.is_none() && self.deliver_at.is_none() && self.expires_at.is_none()
+    }
+
+    /// Parent id if this message is an acknowledgement posted by `ack`.
+    pub fn ack_of(&self) -> Option<&str> {
+        self.reply_to.filter(|_| self.body == ACK_BODY)
+    }
+
+    /// True once an ephemeral message's `expires_at` has passed.
+    pub fn is_expired(&self) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
 
 /// Format a message for display: [name HH:MM]: message
 pub fn format_message(name: &str, timestamp: NaiveDateTime, body: &str) -> String {
-    let time = timestamp.format("%H:%M");
-    format!("[{} {}]: {}", name, time, body)
+    render(name, &timestamp.format("%H:%M").to_string(), None, body)
+}
+
+/// Shared layout: `↳ re <id>: [name <time>]: body`, with the reply prefix only for replies.
+fn render(name: &str, time: &str, reply_to: Option<&str>, body: &str) -> String {
+    let line = format!("[{} {}]: {}", name, time, body);
+    match reply_to {
+        Some(parent) => format!("↳ re {}: {}", parent, line),
+        None => line,
+    }
+}
+
+/// Format a message that may be a reply: `↳ re <id>: [name HH:MM]: message`.
+/// Messages without a parent render exactly like `format_message`.
+pub fn format_thread_message(
+    name: &str,
+    timestamp: NaiveDateTime,
+    reply_to: Option<&str>,
+    body: &str,
+) -> String {
+    match reply_to {
+        Some(parent) => format!("↳ re {}: {}", parent, format_message(name, timestamp, body)),
+        None => format_message(name, timestamp, body),
+    }
+}
+
+/// Format a parsed message with its reply, recipient, and status decorations:
+/// `↳ re <id>: [name HH:MM]: (to bold-hawk) ✅ done: body`.
+pub fn format_parsed(msg: &ParsedMessage, timestamp: NaiveDateTime) -> String {
+    format_thread_message(msg.name, timestamp, msg.reply_to, &decorated_body(msg))
+}
+
+/// Like `format_parsed`, but with the full date and time:
+/// `[name 2025-01-15 14:30:05]: body`.
+pub fn format_parsed_full(msg: &ParsedMessage, timestamp: NaiveDateTime) -> String {
+    let time = timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
+    render(msg.name, &time, msg.reply_to, &decorated_body(msg))
+}
+
+/// Message body with its recipient and status prefixes and `(re: <refs>)` suffix.
+pub fn decorated_body(msg: &ParsedMessage) -> String {
+    let mut body = match msg.status {
+        Some(status) => format!("{}: {}", status.label(), msg.body),
+        None => msg.body.to_string(),
+    };
+    if let Some(to) = msg.to {
+        body = format!("(to {}) {}", to, body);
+    }
+    if !msg.refs.is_empty() {
+        body = format!("{} (re: {})", body, msg.refs.join(", "));
+    }
+    body
 }
 
 /// Parse a message file's content. Expected format:
 /// First line: `name: <friendly_name>`
-/// Remaining lines: message body
-pub fn parse_message_file(content: &str) -> Option<(&str, &str)> {
+/// Optional header lines: `priority: <low|normal|high>`, `reply_to: <message_id>`,
+/// `status: <working|done|blocked>`, `to: <recipient>`, `deliver_at: <timestamp_ns>`,
+/// `expires_at: <timestamp_ns>`, `refs: <path>, <path>, ...`
+/// A blank line, then the message body. The blank line ends the headers, so a
+/// body line that looks like a header stays in the body. Files written before
+/// the separator existed have none; their body starts at the first non-header line.
+pub fn parse_message(content: &str) -> Option<ParsedMessage<'_>> {
     let first_newline = content.find('\n')?;
     let header = &content[..first_newline];
     let name = header.strip_prefix("name: ")?;
-    let body = content[first_newline + 1..].trim_end();
-    Some((name, body))
+
+    let mut priority = Priority::Normal;
+    let mut reply_to = None;
+    let mut status = None;
+    let mut to = None;
+    let mut deliver_at = None;
+    let mut expires_at = None;
+    let mut refs = Vec::new();
+    let mut rest = &content[first_newline + 1..];
+    loop {
+        let (line, next) = match rest.find('\n') {
+            Some(i) => (&rest[..i], &rest[i + 1..]),
+            None => (rest, ""),
+        };
+        if line.is_empty() {
+            rest = next;
+            break;
+        }
+        if let Some(p) = line.strip_prefix("priority: ").and_then(Priority::parse) {
+            priority = p;
+        } else if let Some(id) = line.strip_prefix("reply_to: ").filter(|id| is_message_id(id)) {
+            reply_to = Some(id);
+        } else if let Some(s) = line.strip_prefix("status: ").and_then(AgentStatus::parse) {
+            status = Some(s);
+        } else if let Some(recipient) = line.strip_prefix("to: ").filter(|r| !r.is_empty()) {
+            to = Some(recipient);
+        } else if let Some(at) = line.strip_prefix("deliver_at: ").and_then(|at| at.parse().ok()) {
+            deliver_at = Some(at);
+        } else if let Some(at) = line.strip_prefix("expires_at: ").and_then(|at| at.parse().ok()) {
+            expires_at = Some(at);
+        } else if let Some(paths) = line.strip_prefix("refs: ") {
+            refs = paths.split(", ").map(str::trim).filter(|p| !p.is_empty()).collect();
+        } else {
+            break;
+        }
+        rest = next;
+    }
+
+    Some(ParsedMessage { name, priority, reply_to, status, to, deliver_at, expires_at, refs, body: rest.trim_end() })
+}
+
+/// Message ids are the nanosecond timestamp filename stems.
+pub fn is_message_id(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Build a one-line quote of a message, capped at `max_chars` of body text:
+/// `> swift-fox: original text…`
+pub fn quote_message(name: &str, body: &str, max_chars: usize) -> String {
+    let flat = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    let snippet = if flat.chars().count() > max_chars {
+        let cut: String = flat.chars().take(max_chars).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        flat
+    };
+    format!("> {}: {}", name, snippet)
+}
+
+/// Parse a message file's content into `(name, reply_to, body)`.
+/// `reply_to` is the parent message id, or `None` for top-level messages.
+pub fn parse_message_file(content: &str) -> Option<(&str, Option<&str>, &str)> {
+    parse_message(content).map(|m| (m.name, m.reply_to, m.body))
 }
 
 /// Parse nanosecond timestamp from filename to NaiveDateTime (local time).
@@ -32,16 +281,60 @@ pub fn parse_timestamp_ns(filename: &str) -> NaiveDateTime {
     }
 }
 
+/// ISO-8601 (RFC 3339, UTC) form of a nanosecond epoch timestamp, for JSON output.
+pub fn iso8601_ns(ns: u128) -> String {
+    let secs = (ns / 1_000_000_000) as i64;
+    let nsecs = (ns % 1_000_000_000) as u32;
+    DateTime::from_timestamp(secs, nsecs)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// ISO-8601 form of an epoch-seconds timestamp (lock and focus times).
+pub fn iso8601_secs(secs: u64) -> String {
+    iso8601_ns(secs as u128 * 1_000_000_000)
+}
+
+/// Parse a duration like `30s`, `15m`, `2h`, or `7d` into seconds.
+/// A bare number is taken as seconds.
+pub fn parse_duration(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (digits, multiplier) = match s.char_indices().last()? {
+        (i, 's') => (&s[..i], 1),
+        (i, 'm') => (&s[..i], 60),
+        (i, 'h') => (&s[..i], 60 * 60),
+        (i, 'd') => (&s[..i], 24 * 60 * 60),
+        _ => (s, 1),
+    };
+    digits.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+/// Timestamp in nanoseconds `duration` (e.g. `30m`) before now, for the
+/// `--since` / `--older-than` style options.
+pub fn cutoff_ns(duration: &str) -> crate::error::Result<u128> {
+    let secs = parse_duration(duration).ok_or_else(|| {
+        crate::error::AgentChatError::Other(format!(
+            "invalid duration: {} (use e.g. 30s, 15m, 2h, 7d)",
+            duration
+        ))
+    })?;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    Ok(now.saturating_sub(secs as u128 * 1_000_000_000))
+}
+
 /// Read message files from paths and format them as a message list with a header.
 /// Returns empty string if no messages could be parsed.
 pub fn format_messages_from_paths(paths: &[PathBuf]) -> String {
     let mut lines = Vec::new();
     for path in paths {
         if let Ok(content) = fs::read_to_string(path) {
-            if let Some((name, body)) = parse_message_file(&content) {
+            if let Some(msg) = parse_message(&content) {
                 let filename = path.file_stem().unwrap().to_string_lossy();
                 let ts = parse_timestamp_ns(&filename);
-                lines.push(format_message(name, ts, body));
+                lines.push(format_parsed(&msg, ts));
             }
         }
     }
@@ -57,13 +350,34 @@ pub fn format_messages_from_paths(paths: &[PathBuf]) -> String {
     format!("{}\n{}", header, lines.join("\n"))
 }
 
+/// One-line summary of who is waiting, e.g. `2 unread from bold-hawk, quiet-owl`,
+/// with senders in order of their first message.
+fn unread_summary(paths: &[PathBuf]) -> String {
+    let mut count = 0;
+    let mut senders: Vec<String> = Vec::new();
+    for path in paths {
+        let Ok(content) = fs::read_to_string(path) else { continue };
+        let Some(msg) = parse_message(&content) else { continue };
+        count += 1;
+        if !senders.iter().any(|s| s == msg.name) {
+            senders.push(msg.name.to_string());
+        }
+    }
+    format!("{} unread from {}", count, senders.join(", "))
+}
+
 /// Format a path for use in status check — does NOT include cursor-advancing instructions.
+/// Leads with a sender summary so the agent sees at once who is waiting.
 pub fn format_messages_for_status(paths: &[PathBuf]) -> String {
     let formatted = format_messages_from_paths(paths);
     if formatted.is_empty() {
         return String::new();
     }
-    format!("{}\nRun `agent-chat read` to acknowledge, then respond or continue.", formatted)
+    format!(
+        "{}\n{}\nRun `agent-chat read` to acknowledge, then respond or continue.",
+        unread_summary(paths),
+        formatted
+    )
 }
 
 #[cfg(test)]
@@ -77,10 +391,20 @@ mod tests {
         assert_eq!(result, "[swift-fox 14:30]: hello world");
     }
 
+    #[test]
+    fn test_format_parsed_full() {
+        let ts = NaiveDateTime::parse_from_str("2025-01-15 14:30:05", "%Y-%m-%d %H:%M:%S").unwrap();
+        let msg = parse_message("name: swift-fox\nreply_to: 42\nstatus: done\nshipped").unwrap();
+        assert_eq!(
+            format_parsed_full(&msg, ts),
+            "↳ re 42: [swift-fox 2025-01-15 14:30:05]: ✅ done: shipped"
+        );
+    }
+
     #[test]
     fn test_parse_message_file() {
         let content = "name: swift-fox\nhello world";
-        let (name, body) = parse_message_file(content).unwrap();
+        let (name, _, body) = parse_message_file(content).unwrap();
         assert_eq!(name, "swift-fox");
         assert_eq!(body, "hello world");
     }
@@ -88,11 +412,143 @@ mod tests {
     #[test]
     fn test_parse_message_file_multiline_body() {
         let content = "name: bold-hawk\nline one\nline two";
-        let (name, body) = parse_message_file(content).unwrap();
+        let (name, _, body) = parse_message_file(content).unwrap();
         assert_eq!(name, "bold-hawk");
         assert_eq!(body, "line one\nline two");
     }
 
+    #[test]
+    fn test_parse_message_file_reply_to() {
+        let content = "name: bold-hawk\nreply_to: 1736950200000000000\non it";
+        let (name, reply_to, body) = parse_message_file(content).unwrap();
+        assert_eq!(name, "bold-hawk");
+        assert_eq!(reply_to, Some("1736950200000000000"));
+        assert_eq!(body, "on it");
+
+        let (_, reply_to, _) = parse_message_file("name: swift-fox\nhello").unwrap();
+        assert_eq!(reply_to, None);
+    }
+
+    #[test]
+    fn test_format_thread_message() {
+        let ts = NaiveDateTime::parse_from_str("2025-01-15 14:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(
+            format_thread_message("bold-hawk", ts, Some("123"), "on it"),
+            "↳ re 123: [bold-hawk 14:30]: on it"
+        );
+        assert_eq!(format_thread_message("bold-hawk", ts, None, "on it"), "[bold-hawk 14:30]: on it");
+    }
+
+    #[test]
+    fn test_parse_message_status_header() {
+        let msg = parse_message("name: swift-fox\nstatus: blocked\nwaiting on schema").unwrap();
+        assert_eq!(msg.status, Some(AgentStatus::Blocked));
+        assert_eq!(msg.body, "waiting on schema");
+
+        let ts = NaiveDateTime::parse_from_str("2025-01-15 14:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(format_parsed(&msg, ts), "[swift-fox 14:30]: ⛔ blocked: waiting on schema");
+
+        let plain = parse_message("name: swift-fox\nstatus: sleepy\nhi").unwrap();
+        assert_eq!(plain.status, None);
+    }
+
+    #[test]
+    fn test_blank_line_ends_headers() {
+        let msg = parse_message("name: swift-fox\npriority: high\n\nto: whom it may concern\nstatus: done").unwrap();
+        assert_eq!(msg.priority, Priority::High);
+        assert_eq!(msg.to, None);
+        assert_eq!(msg.status, None);
+        assert_eq!(msg.body, "to: whom it may concern\nstatus: done");
+
+        // Files from before the separator still parse
+        let legacy = parse_message("name: swift-fox\nto: bold-hawk\npsst").unwrap();
+        assert_eq!(legacy.to, Some("bold-hawk"));
+        assert_eq!(legacy.body, "psst");
+    }
+
+    #[test]
+    fn test_parse_message_single_ref() {
+        let msg = parse_message("name: swift-fox\nrefs: src/format.rs\nfixed the parser bug").unwrap();
+        assert_eq!(msg.refs, vec!["src/format.rs"]);
+        assert_eq!(msg.body, "fixed the parser bug");
+
+        let ts = NaiveDateTime::parse_from_str("2025-01-15 14:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(format_parsed(&msg, ts), "[swift-fox 14:30]: fixed the parser bug (re: src/format.rs)");
+
+        let plain = parse_message("name: swift-fox\nhi").unwrap();
+        assert!(plain.refs.is_empty());
+    }
+
+    #[test]
+    fn test_parse_message_multiple_refs() {
+        let msg = parse_message("name: swift-fox\nstatus: done\nrefs: src/cli.rs, src/main.rs\nwired up --ref").unwrap();
+        assert_eq!(msg.refs, vec!["src/cli.rs", "src/main.rs"]);
+        assert_eq!(msg.status, Some(AgentStatus::Done));
+
+        let ts = NaiveDateTime::parse_from_str("2025-01-15 14:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(
+            format_parsed(&msg, ts),
+            "[swift-fox 14:30]: ✅ done: wired up --ref (re: src/cli.rs, src/main.rs)"
+        );
+    }
+
+    #[test]
+    fn test_direct_message_visibility() {
+        let msg = parse_message("name: swift-fox\nto: bold-hawk\npsst").unwrap();
+        assert_eq!(msg.to, Some("bold-hawk"));
+        assert!(msg.is_visible_to(Some("bold-hawk")));
+        assert!(msg.is_visible_to(Some("swift-fox")));
+        assert!(!msg.is_visible_to(Some("calm-owl")));
+        assert!(!msg.is_visible_to(None));
+
+        let broadcast = parse_message("name: swift-fox\nhello all").unwrap();
+        assert!(broadcast.is_visible_to(None));
+    }
+
+    #[test]
+    fn test_parse_message_priority_header() {
+        let content = "name: swift-fox\npriority: high\nurgent thing";
+        let msg = parse_message(content).unwrap();
+        assert_eq!(msg.name, "swift-fox");
+        assert_eq!(msg.priority, Priority::High);
+        assert_eq!(msg.body, "urgent thing");
+    }
+
+    #[test]
+    fn test_parse_message_defaults_to_normal() {
+        let msg = parse_message("name: swift-fox\nhello").unwrap();
+        assert_eq!(msg.priority, Priority::Normal);
+        assert_eq!(msg.body, "hello");
+    }
+
+    #[test]
+    fn test_parse_message_reply_to_header() {
+        let msg = parse_message("name: bold-hawk\nreply_to: 1736950200000000000\nsounds good").unwrap();
+        assert_eq!(msg.reply_to, Some("1736950200000000000"));
+        assert_eq!(msg.body, "sounds good");
+
+        let msg = parse_message("name: bold-hawk\nreply_to: nope\nbody").unwrap();
+        assert_eq!(msg.reply_to, None);
+        assert_eq!(msg.body, "reply_to: nope\nbody");
+    }
+
+    #[test]
+    fn test_quote_message_truncates() {
+        assert_eq!(quote_message("swift-fox", "short\ntext", 80), "> swift-fox: short text");
+        assert_eq!(quote_message("swift-fox", "abcdefghij", 4), "> swift-fox: abcd…");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("45"), Some(45));
+        assert_eq!(parse_duration("30s"), Some(30));
+        assert_eq!(parse_duration("15m"), Some(900));
+        assert_eq!(parse_duration("2h"), Some(7200));
+        assert_eq!(parse_duration("7d"), Some(604800));
+        assert_eq!(parse_duration("soon"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
     #[test]
     fn test_format_messages_from_paths_empty() {
         let result = format_messages_from_paths(&[]);
@@ -132,14 +588,39 @@ mod tests {
         std::fs::write(&path, "name: swift-fox\nhello").unwrap();
 
         let result = format_messages_for_status(&[path]);
+        assert!(result.starts_with("1 unread from swift-fox\n"));
         assert!(result.contains("[agent-chat: 1 unread message]"));
         assert!(result.contains("hello"));
         assert!(result.contains("agent-chat read"));
     }
 
+    #[test]
+    fn test_status_summary_lists_each_sender_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut paths = Vec::new();
+        for (i, name) in ["bold-hawk", "quiet-owl", "bold-hawk"].iter().enumerate() {
+            let path = dir.path().join(format!("173695020000000000{}.md", i));
+            std::fs::write(&path, format!("name: {}\nmsg {}", name, i)).unwrap();
+            paths.push(path);
+        }
+
+        let result = format_messages_for_status(&paths);
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(lines[0], "3 unread from bold-hawk, quiet-owl");
+        assert_eq!(lines[1], "[agent-chat: 3 unread messages]");
+        assert!(lines.last().unwrap().starts_with("Run `agent-chat read`"));
+    }
+
     #[test]
     fn test_format_messages_for_status_empty() {
         let result = format_messages_for_status(&[]);
         assert_eq!(result, "");
     }
+
+    #[test]
+    fn test_iso8601() {
+        assert_eq!(iso8601_ns(1736950200000000000), "2025-01-15T14:10:00Z");
+        assert_eq!(iso8601_ns(1736950200500000000), "2025-01-15T14:10:00.500Z");
+        assert_eq!(iso8601_secs(1736950200), "2025-01-15T14:10:00Z");
+    }
 }
@@ -1,6 +1,8 @@
 use std::fs;
 use std::path::Path;
 use crate::error::Result;
+use crate::hooks::plan;
+use crate::storage::atomic;
 
 const START_SENTINEL: &str = "<!-- agent-chat-codex:start -->";
 const END_SENTINEL: &str = "<!-- agent-chat-codex:end -->";
@@ -44,6 +46,11 @@ Use `agent-chat` for inter-agent coordination in this repo.
 4. Run `agent-chat read` once more.
 <!-- agent-chat-codex:end -->"#;
 
+/// What `install_agents_md_to` would do, for `--dry-run`.
+pub fn describe_install(target_dir: &Path) -> Option<String> {
+    plan::section(&target_dir.join("AGENTS.md"), START_SENTINEL)
+}
+
 /// Install or update the agent-chat Codex section in `<target_dir>/AGENTS.md`.
 pub fn install_agents_md_to(target_dir: &Path) -> Result<()> {
     fs::create_dir_all(target_dir)?;
@@ -52,7 +59,7 @@ pub fn install_agents_md_to(target_dir: &Path) -> Result<()> {
     if !path.exists() {
         let tmp = target_dir.join(".tmp.AGENTS.md");
         fs::write(&tmp, GUIDANCE)?;
-        fs::rename(&tmp, &path)?;
+        atomic::rename(&tmp, &path)?;
         return Ok(());
     }
 
@@ -88,7 +95,46 @@ pub fn install_agents_md_to(target_dir: &Path) -> Result<()> {
 
     let tmp = target_dir.join(".tmp.AGENTS.md");
     fs::write(&tmp, &new_content)?;
-    fs::rename(&tmp, &path)?;
+    atomic::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Remove the agent-chat section from `<target_dir>/AGENTS.md`.
+/// No-ops if the file is missing or has no agent-chat section.
+pub fn remove_agents_md_from(target_dir: &Path) -> Result<()> {
+    let path = target_dir.join("AGENTS.md");
+
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(&path)?;
+
+    let Some(start) = existing.find(START_SENTINEL) else {
+        return Ok(());
+    };
+    let Some(end) = existing.find(END_SENTINEL) else {
+        return Ok(());
+    };
+
+    let before = existing[..start].trim_end();
+    let after = existing[end + END_SENTINEL.len()..].trim_start();
+
+    let new_content = match (before.is_empty(), after.is_empty()) {
+        (true, true) => String::new(),
+        (true, false) => after.to_string(),
+        (false, true) => format!("{}\n", before),
+        (false, false) => format!("{}\n\n{}\n", before, after),
+    };
+
+    if new_content.is_empty() {
+        fs::remove_file(&path)?;
+    } else {
+        let tmp = target_dir.join(".tmp.AGENTS.md");
+        fs::write(&tmp, &new_content)?;
+        atomic::rename(&tmp, &path)?;
+    }
+
     Ok(())
 }
 
@@ -132,4 +178,16 @@ mod tests {
         assert_eq!(content.matches(START_SENTINEL).count(), 1);
         assert_eq!(content.matches(END_SENTINEL).count(), 1);
     }
+
+    #[test]
+    fn remove_strips_section_preserves_rest() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("AGENTS.md");
+        fs::write(&path, "# Project Agents\n").unwrap();
+        install_agents_md_to(tmp.path()).unwrap();
+
+        remove_agents_md_from(tmp.path()).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Project Agents\n");
+        remove_agents_md_from(tmp.path()).unwrap();
+    }
 }
@@ -1,9 +1,11 @@
 use std::fs;
 use std::path::Path;
 use crate::error::Result;
+use crate::hooks::plan;
+use crate::storage::atomic;
 
-const START_SENTINEL: &str = "<!-- agent-chat:start -->";
-const END_SENTINEL: &str = "<!-- agent-chat:end -->";
+pub const START_SENTINEL: &str = "<!-- agent-chat:start -->";
+pub const END_SENTINEL: &str = "<!-- agent-chat:end -->";
 
 const GUIDANCE: &str = r#"<!-- agent-chat:start -->
 # Agent Chat
@@ -69,56 +71,125 @@ avoid overlap. Expires after 5 minutes like locks. When claiming a bead that
 overlaps with another agent's focus, you'll see a warning.
 <!-- agent-chat:end -->"#;
 
+/// Start and end markers of the section. A label scopes them to one
+/// agent-chat setup (`<!-- agent-chat:<label>:start -->`), so labeled and
+/// unlabeled sections in the same file never match each other.
+pub fn sentinels(label: Option<&str>) -> (String, String) {
+    match label {
+        Some(label) => (
+            format!("<!-- agent-chat:{}:start -->", label),
+            format!("<!-- agent-chat:{}:end -->", label),
+        ),
+        None => (START_SENTINEL.to_string(), END_SENTINEL.to_string()),
+    }
+}
+
+/// The guidance section wrapped in `label`'s markers.
+fn guidance(label: Option<&str>) -> String {
+    let (start, end) = sentinels(label);
+    let body = &GUIDANCE[START_SENTINEL.len()..GUIDANCE.len() - END_SENTINEL.len()];
+    format!("{}{}{}", start, body, end)
+}
+
 /// Install or update the agent-chat section in `<project_root>/CLAUDE.md`.
-pub fn install_claude_md(project_root: &Path) -> Result<()> {
-    install_claude_md_to(project_root)
+pub fn install_claude_md(project_root: &Path, label: Option<&str>) -> Result<()> {
+    install_claude_md_to(project_root, label)
+}
+
+/// What `install_claude_md_to` would do, for `--dry-run`.
+pub fn describe_install(target_dir: &Path, label: Option<&str>) -> Option<String> {
+    plan::section(&target_dir.join("CLAUDE.md"), &sentinels(label).0)
 }
 
 /// Install or update the agent-chat section in `<target_dir>/CLAUDE.md`.
 /// - No CLAUDE.md: create it with just the agent-chat section
 /// - CLAUDE.md exists with sentinel: replace that section
 /// - CLAUDE.md exists without sentinel: append the section
-pub fn install_claude_md_to(target_dir: &Path) -> Result<()> {
+///
+/// Only the section carrying `label`'s markers is touched.
+pub fn install_claude_md_to(target_dir: &Path, label: Option<&str>) -> Result<()> {
     fs::create_dir_all(target_dir)?;
     let path = target_dir.join("CLAUDE.md");
+    let (start_sentinel, end_sentinel) = sentinels(label);
+    let guidance = guidance(label);
 
     if !path.exists() {
         let tmp = target_dir.join(".tmp.CLAUDE.md");
-        fs::write(&tmp, GUIDANCE)?;
-        fs::rename(&tmp, &path)?;
+        fs::write(&tmp, &guidance)?;
+        atomic::rename(&tmp, &path)?;
         return Ok(());
     }
 
     let existing = fs::read_to_string(&path)?;
 
-    let new_content = if let Some(start) = existing.find(START_SENTINEL) {
-        if let Some(end) = existing.find(END_SENTINEL) {
+    let new_content = if let Some(start) = existing.find(&start_sentinel) {
+        if let Some(end) = existing.find(&end_sentinel) {
             // Replace existing section
             let before = &existing[..start];
-            let after = &existing[end + END_SENTINEL.len()..];
-            format!("{}{}{}", before.trim_end(), if before.is_empty() { "" } else { "\n\n" }, format!("{}{}", GUIDANCE, after))
+            let after = &existing[end + end_sentinel.len()..];
+            format!("{}{}{}{}", before.trim_end(), if before.is_empty() { "" } else { "\n\n" }, guidance, after)
         } else {
             // Malformed: has start but no end. Replace from start to EOF.
             let before = existing[..start].trim_end();
             if before.is_empty() {
-                GUIDANCE.to_string()
+                guidance
             } else {
-                format!("{}\n\n{}", before, GUIDANCE)
+                format!("{}\n\n{}", before, guidance)
             }
         }
     } else {
         // No existing section: append
         let trimmed = existing.trim_end();
         if trimmed.is_empty() {
-            GUIDANCE.to_string()
+            guidance
         } else {
-            format!("{}\n\n{}\n", trimmed, GUIDANCE)
+            format!("{}\n\n{}\n", trimmed, guidance)
         }
     };
 
     let tmp = target_dir.join(".tmp.CLAUDE.md");
     fs::write(&tmp, &new_content)?;
-    fs::rename(&tmp, &path)?;
+    atomic::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Remove `label`'s agent-chat section from `<target_dir>/CLAUDE.md`.
+/// No-ops if the file is missing or has no such section.
+pub fn remove_claude_md_from(target_dir: &Path, label: Option<&str>) -> Result<()> {
+    let path = target_dir.join("CLAUDE.md");
+
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(&path)?;
+    let (start_sentinel, end_sentinel) = sentinels(label);
+
+    let Some(start) = existing.find(&start_sentinel) else {
+        return Ok(());
+    };
+    let Some(end) = existing.find(&end_sentinel) else {
+        return Ok(());
+    };
+
+    let before = existing[..start].trim_end();
+    let after = existing[end + end_sentinel.len()..].trim_start();
+
+    let new_content = match (before.is_empty(), after.is_empty()) {
+        (true, true) => String::new(),
+        (true, false) => after.to_string(),
+        (false, true) => format!("{}\n", before),
+        (false, false) => format!("{}\n\n{}\n", before, after),
+    };
+
+    if new_content.is_empty() {
+        fs::remove_file(&path)?;
+    } else {
+        let tmp = target_dir.join(".tmp.CLAUDE.md");
+        fs::write(&tmp, &new_content)?;
+        atomic::rename(&tmp, &path)?;
+    }
+
     Ok(())
 }
 
@@ -130,7 +201,7 @@ mod tests {
     #[test]
     fn creates_new_claude_md() {
         let tmp = TempDir::new().unwrap();
-        install_claude_md(tmp.path()).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
 
         let content = fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap();
         assert!(content.contains(START_SENTINEL));
@@ -144,7 +215,7 @@ mod tests {
         let path = tmp.path().join("CLAUDE.md");
         fs::write(&path, "# My Project\n\nExisting content here.\n").unwrap();
 
-        install_claude_md(tmp.path()).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
 
         let content = fs::read_to_string(&path).unwrap();
         assert!(content.starts_with("# My Project"));
@@ -163,7 +234,7 @@ mod tests {
         );
         fs::write(&path, &old).unwrap();
 
-        install_claude_md(tmp.path()).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
 
         let content = fs::read_to_string(&path).unwrap();
         assert!(content.contains("Stuff above."));
@@ -178,8 +249,8 @@ mod tests {
     #[test]
     fn idempotent() {
         let tmp = TempDir::new().unwrap();
-        install_claude_md(tmp.path()).unwrap();
-        install_claude_md(tmp.path()).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
 
         let content = fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap();
         assert_eq!(content.matches(START_SENTINEL).count(), 1);
@@ -192,16 +263,70 @@ mod tests {
         let path = tmp.path().join("CLAUDE.md");
         fs::write(&path, "# Header\n\nBefore.\n").unwrap();
 
-        install_claude_md(tmp.path()).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
         let content = fs::read_to_string(&path).unwrap();
         assert!(content.starts_with("# Header"));
         assert!(content.contains("Before."));
 
         // Run again — still preserved
-        install_claude_md(tmp.path()).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
         let content = fs::read_to_string(&path).unwrap();
         assert!(content.starts_with("# Header"));
         assert!(content.contains("Before."));
         assert_eq!(content.matches(START_SENTINEL).count(), 1);
     }
+
+    #[test]
+    fn remove_strips_section_preserves_rest() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("CLAUDE.md");
+        fs::write(&path, "# Header\n\nBefore.\n").unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
+
+        remove_claude_md_from(tmp.path(), None).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "# Header\n\nBefore.\n");
+
+        // Second removal is a no-op
+        remove_claude_md_from(tmp.path(), None).unwrap();
+    }
+
+    #[test]
+    fn remove_deletes_file_when_only_section() {
+        let tmp = TempDir::new().unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
+        remove_claude_md_from(tmp.path(), None).unwrap();
+        assert!(!tmp.path().join("CLAUDE.md").exists());
+    }
+
+    #[test]
+    fn labeled_sections_coexist_and_reinstall_independently() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("CLAUDE.md");
+        install_claude_md(tmp.path(), Some("api")).unwrap();
+        install_claude_md(tmp.path(), Some("web")).unwrap();
+        install_claude_md(tmp.path(), None).unwrap();
+
+        // Simulate a stale web section; re-installing api must leave it alone
+        let stale = fs::read_to_string(&path).unwrap().replacen("## Commands", "## Old commands", 2);
+        fs::write(&path, &stale).unwrap();
+        install_claude_md(tmp.path(), Some("api")).unwrap();
+        install_claude_md(tmp.path(), Some("api")).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        for label in [Some("api"), Some("web"), None] {
+            let (start, end) = sentinels(label);
+            assert_eq!(content.matches(&start).count(), 1, "{}", start);
+            assert_eq!(content.matches(&end).count(), 1, "{}", end);
+        }
+        let web = &content[content.find("agent-chat:web:start").unwrap()..content.find("agent-chat:web:end").unwrap()];
+        assert!(web.contains("## Old commands"));
+        let api = &content[content.find("agent-chat:api:start").unwrap()..content.find("agent-chat:api:end").unwrap()];
+        assert!(!api.contains("## Old commands"));
+
+        remove_claude_md_from(tmp.path(), Some("web")).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("agent-chat:web:"));
+        assert!(content.contains(&sentinels(Some("api")).0));
+        assert!(content.contains(START_SENTINEL));
+    }
 }
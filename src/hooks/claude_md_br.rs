@@ -1,9 +1,11 @@
 use std::fs;
 use std::path::Path;
 use crate::error::Result;
+use crate::hooks::plan;
+use crate::storage::atomic;
 
-const BR_START_SENTINEL: &str = "<!-- agent-chat-br:start -->";
-const BR_END_SENTINEL: &str = "<!-- agent-chat-br:end -->";
+pub const BR_START_SENTINEL: &str = "<!-- agent-chat-br:start -->";
+pub const BR_END_SENTINEL: &str = "<!-- agent-chat-br:end -->";
 
 const BR_GUIDANCE: &str = r#"<!-- agent-chat-br:start -->
 # Beads Issue Tracker (br)
@@ -42,12 +44,11 @@ Set dependencies between beads when order matters:
 
 ## Execution workflow
 
-1. Find ready work: `br ready`
+1. Find ready work: `br ready` (or `agent-chat br-list` to see claims and overlapping focuses)
 2. Claim: `agent-chat br-claim <id>` (sets in_progress + assignee + announces)
 3. Do the work
-4. Complete: `agent-chat br-complete <id> --reason "done, tests passing"`
-5. Sync: `br sync --flush-only`
-6. Commit: `git add .beads/ && git commit -m "beads: update issue state"`
+4. Complete: `agent-chat br-complete <id> --reason "done, tests passing"` (closes, runs `br sync --flush-only`, announces)
+5. Commit: `git add .beads/ && git commit -m "beads: update issue state"`
 
 ## Common commands
 
@@ -65,6 +66,11 @@ Set dependencies between beads when order matters:
 **Note:** Ensure `Bash(br *)` is in your Claude Code permissions to allow direct br commands.
 <!-- agent-chat-br:end -->"#;
 
+/// What `install_br_claude_md_to` would do, for `--dry-run`.
+pub fn describe_install(target_dir: &Path) -> Option<String> {
+    plan::section(&target_dir.join("CLAUDE.md"), BR_START_SENTINEL)
+}
+
 /// Install or update the br section in `<target_dir>/CLAUDE.md`.
 /// - No CLAUDE.md: create it with just the br section
 /// - CLAUDE.md exists with sentinel: replace that section
@@ -76,7 +82,7 @@ pub fn install_br_claude_md_to(target_dir: &Path) -> Result<()> {
     if !path.exists() {
         let tmp = target_dir.join(".tmp.CLAUDE.md");
         fs::write(&tmp, BR_GUIDANCE)?;
-        fs::rename(&tmp, &path)?;
+        atomic::rename(&tmp, &path)?;
         return Ok(());
     }
 
@@ -87,7 +93,7 @@ pub fn install_br_claude_md_to(target_dir: &Path) -> Result<()> {
             // Replace existing section
             let before = &existing[..start];
             let after = &existing[end + BR_END_SENTINEL.len()..];
-            format!("{}{}{}", before.trim_end(), if before.is_empty() { "" } else { "\n\n" }, format!("{}{}", BR_GUIDANCE, after))
+            format!("{}{}{}{}", before.trim_end(), if before.is_empty() { "" } else { "\n\n" }, BR_GUIDANCE, after)
         } else {
             // Malformed: has start but no end. Replace from start to EOF.
             let before = existing[..start].trim_end();
@@ -109,10 +115,15 @@ pub fn install_br_claude_md_to(target_dir: &Path) -> Result<()> {
 
     let tmp = target_dir.join(".tmp.CLAUDE.md");
     fs::write(&tmp, &new_content)?;
-    fs::rename(&tmp, &path)?;
+    atomic::rename(&tmp, &path)?;
     Ok(())
 }
 
+/// What `remove_br_claude_md_from` would do, for `--dry-run`.
+pub fn describe_removal(target_dir: &Path) -> Option<String> {
+    plan::section_removal(&target_dir.join("CLAUDE.md"), BR_START_SENTINEL)
+}
+
 /// Remove the br section from `<target_dir>/CLAUDE.md`.
 /// No-ops if the file is missing or has no br section.
 pub fn remove_br_claude_md_from(target_dir: &Path) -> Result<()> {
@@ -146,7 +157,7 @@ pub fn remove_br_claude_md_from(target_dir: &Path) -> Result<()> {
     } else {
         let tmp = target_dir.join(".tmp.CLAUDE.md");
         fs::write(&tmp, &new_content)?;
-        fs::rename(&tmp, &path)?;
+        atomic::rename(&tmp, &path)?;
     }
 
     Ok(())
@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::Path;
+use crate::error::Result;
+use crate::hooks::plan;
+use crate::storage::atomic;
+
+const START_SENTINEL: &str = "<!-- agent-chat-continue:start -->";
+const END_SENTINEL: &str = "<!-- agent-chat-continue:end -->";
+
+/// Rules file inside a `.continue/` directory, picked up by Continue.dev
+/// as always-on guidance for the agent.
+const RULES_FILE: &str = "agent-chat.md";
+
+const GUIDANCE: &str = r#"<!-- agent-chat-continue:start -->
+## Agent Chat (Continue)
+
+Use `agent-chat` for inter-agent coordination in this repo. Continue has no
+session hooks, so run these commands yourself.
+
+### Commands
+
+- `agent-chat register --session-id <id>` — initialize identity for this session
+- `agent-chat read` — check unread messages from other agents
+- `agent-chat say "<msg>"` — post short status updates
+- `agent-chat lock "<glob>"` — advisory lock before editing shared files
+- `agent-chat unlock "<glob>"` — release lock immediately after edits
+- `agent-chat locks` — inspect active locks
+- `agent-chat focus "<area>"` — declare active focus area
+- `agent-chat focus --clear` — clear focus when done
+
+### Suggested startup
+
+1. Register once per session: `agent-chat register --session-id "$USER-$(date +%s)"`
+2. Run `agent-chat read`
+3. Announce scope: `agent-chat say "starting on <task>"`
+4. Lock planned files: `agent-chat lock "src/<area>/**"`
+
+### While working
+
+- Run `agent-chat read` before each edit and every few tool calls.
+- Run `agent-chat locks` before editing a file you have not locked.
+- Keep messages short and actionable.
+
+### Finishing
+
+1. Unlock files you touched.
+2. Clear focus.
+3. Announce completion.
+4. Run `agent-chat read` once more.
+<!-- agent-chat-continue:end -->"#;
+
+/// What `install_continue_rules_to` would do, for `--dry-run`.
+pub fn describe_install(continue_dir: &Path) -> Option<String> {
+    plan::section(&continue_dir.join("rules").join(RULES_FILE), START_SENTINEL)
+}
+
+/// Install or update the agent-chat section in `<continue_dir>/rules/agent-chat.md`.
+pub fn install_continue_rules_to(continue_dir: &Path) -> Result<()> {
+    let rules_dir = continue_dir.join("rules");
+    fs::create_dir_all(&rules_dir)?;
+    let path = rules_dir.join(RULES_FILE);
+
+    if !path.exists() {
+        let tmp = rules_dir.join(format!(".tmp.{}", RULES_FILE));
+        fs::write(&tmp, GUIDANCE)?;
+        atomic::rename(&tmp, &path)?;
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(&path)?;
+
+    let new_content = if let Some(start) = existing.find(START_SENTINEL) {
+        if let Some(end) = existing.find(END_SENTINEL) {
+            let before = &existing[..start];
+            let after = &existing[end + END_SENTINEL.len()..];
+            format!(
+                "{}{}{}{}",
+                before.trim_end(),
+                if before.is_empty() { "" } else { "\n\n" },
+                GUIDANCE,
+                after
+            )
+        } else {
+            let before = existing[..start].trim_end();
+            if before.is_empty() {
+                GUIDANCE.to_string()
+            } else {
+                format!("{}\n\n{}", before, GUIDANCE)
+            }
+        }
+    } else {
+        let trimmed = existing.trim_end();
+        if trimmed.is_empty() {
+            GUIDANCE.to_string()
+        } else {
+            format!("{}\n\n{}\n", trimmed, GUIDANCE)
+        }
+    };
+
+    let tmp = rules_dir.join(format!(".tmp.{}", RULES_FILE));
+    fs::write(&tmp, &new_content)?;
+    atomic::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Remove the agent-chat section from `<continue_dir>/rules/agent-chat.md`.
+/// No-ops if the file is missing or has no agent-chat section.
+pub fn remove_continue_rules_from(continue_dir: &Path) -> Result<()> {
+    let rules_dir = continue_dir.join("rules");
+    let path = rules_dir.join(RULES_FILE);
+
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(&path)?;
+
+    let Some(start) = existing.find(START_SENTINEL) else {
+        return Ok(());
+    };
+    let Some(end) = existing.find(END_SENTINEL) else {
+        return Ok(());
+    };
+
+    let before = existing[..start].trim_end();
+    let after = existing[end + END_SENTINEL.len()..].trim_start();
+
+    let new_content = match (before.is_empty(), after.is_empty()) {
+        (true, true) => String::new(),
+        (true, false) => after.to_string(),
+        (false, true) => format!("{}\n", before),
+        (false, false) => format!("{}\n\n{}\n", before, after),
+    };
+
+    if new_content.is_empty() {
+        fs::remove_file(&path)?;
+    } else {
+        let tmp = rules_dir.join(format!(".tmp.{}", RULES_FILE));
+        fs::write(&tmp, &new_content)?;
+        atomic::rename(&tmp, &path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn rules_path(tmp: &TempDir) -> std::path::PathBuf {
+        tmp.path().join("rules").join(RULES_FILE)
+    }
+
+    #[test]
+    fn creates_new_rules_file() {
+        let tmp = TempDir::new().unwrap();
+        install_continue_rules_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(rules_path(&tmp)).unwrap();
+        assert!(content.contains(START_SENTINEL));
+        assert!(content.contains(END_SENTINEL));
+        assert!(content.contains("agent-chat register --session-id"));
+    }
+
+    #[test]
+    fn appends_to_existing_rules_file() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("rules")).unwrap();
+        fs::write(rules_path(&tmp), "# Team rules\n\nPrefer small diffs.\n").unwrap();
+
+        install_continue_rules_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(rules_path(&tmp)).unwrap();
+        assert!(content.starts_with("# Team rules"));
+        assert!(content.contains("Prefer small diffs."));
+        assert!(content.contains(START_SENTINEL));
+    }
+
+    #[test]
+    fn idempotent() {
+        let tmp = TempDir::new().unwrap();
+        install_continue_rules_to(tmp.path()).unwrap();
+        install_continue_rules_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(rules_path(&tmp)).unwrap();
+        assert_eq!(content.matches(START_SENTINEL).count(), 1);
+        assert_eq!(content.matches(END_SENTINEL).count(), 1);
+    }
+
+    #[test]
+    fn remove_deletes_file_when_only_section() {
+        let tmp = TempDir::new().unwrap();
+        install_continue_rules_to(tmp.path()).unwrap();
+        remove_continue_rules_from(tmp.path()).unwrap();
+        assert!(!rules_path(&tmp).exists());
+        remove_continue_rules_from(tmp.path()).unwrap();
+    }
+}
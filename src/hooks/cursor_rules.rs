@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::Path;
+use crate::error::Result;
+use crate::hooks::plan;
+use crate::storage::atomic;
+
+const START_SENTINEL: &str = "<!-- agent-chat-cursor:start -->";
+const END_SENTINEL: &str = "<!-- agent-chat-cursor:end -->";
+
+/// Rule file inside a `.cursor/` directory. Cursor loads `rules/*.mdc`.
+const RULES_FILE: &str = "agent-chat.mdc";
+
+/// `.mdc` frontmatter that makes Cursor attach the rule to every request.
+const FRONTMATTER: &str = "---\ndescription: Coordinate with other agents through agent-chat\nalwaysApply: true\n---";
+
+const GUIDANCE: &str = r#"<!-- agent-chat-cursor:start -->
+# Agent Chat (Cursor)
+
+You are collaborating with other agents on this project. Cursor has no
+session hooks, so register yourself and check messages with these commands.
+
+## Commands
+
+- `agent-chat register --session-id <id>` — initialize identity for this session
+- `agent-chat say <msg>` — post to the shared chatroom
+- `agent-chat read` — check for messages from other agents
+- `agent-chat lock <glob>` — claim advisory file lock before editing
+- `agent-chat unlock <glob>` — release when done
+- `agent-chat locks` — see who's locked what
+- `agent-chat focus "<area>"` — declare your focus area
+- `agent-chat focus --clear` — clear your focus
+- `agent-chat focuses` — see who's focused on what
+
+## Workflow
+
+**Starting a task:**
+1. Register once per session: `agent-chat register --session-id "$USER-$(date +%s)"`
+2. Run `agent-chat read` to catch up on any messages
+3. Say what you're about to work on: `agent-chat say "starting on auth middleware"`
+4. Lock files you'll edit: `agent-chat lock "src/auth/**/*.rs"`
+5. Declare your focus: `agent-chat focus "auth middleware"`
+
+**While working:**
+- Run `agent-chat read` every few tool calls. Other agents may be waiting on you.
+- Don't stop to wait for replies; move to your next task.
+
+**Finishing a task:**
+1. Unlock your files: `agent-chat unlock "src/auth/**/*.rs"`
+2. Clear your focus: `agent-chat focus --clear`
+3. Announce completion: `agent-chat say "auth middleware done, tests passing"`
+4. Run `agent-chat read` once more.
+<!-- agent-chat-cursor:end -->"#;
+
+/// What `install_cursor_rules_to` would do, for `--dry-run`.
+pub fn describe_install(cursor_dir: &Path) -> Option<String> {
+    plan::section(&cursor_dir.join("rules").join(RULES_FILE), START_SENTINEL)
+}
+
+/// Install or update the agent-chat rule in `<cursor_dir>/rules/agent-chat.mdc`.
+/// The frontmatter is added when the file has none; the sentinel-bounded body
+/// is replaced in place on re-install.
+pub fn install_cursor_rules_to(cursor_dir: &Path) -> Result<()> {
+    let rules_dir = cursor_dir.join("rules");
+    fs::create_dir_all(&rules_dir)?;
+    let path = rules_dir.join(RULES_FILE);
+
+    let existing = if path.exists() { fs::read_to_string(&path)? } else { String::new() };
+
+    let body = match (existing.find(START_SENTINEL), existing.find(END_SENTINEL)) {
+        (Some(start), Some(end)) => {
+            let before = existing[..start].trim_end();
+            let after = &existing[end + END_SENTINEL.len()..];
+            format!("{}{}{}{}", before, if before.is_empty() { "" } else { "\n\n" }, GUIDANCE, after)
+        }
+        (Some(start), None) => {
+            let before = existing[..start].trim_end();
+            if before.is_empty() {
+                GUIDANCE.to_string()
+            } else {
+                format!("{}\n\n{}", before, GUIDANCE)
+            }
+        }
+        _ => {
+            let trimmed = existing.trim_end();
+            if trimmed.is_empty() {
+                GUIDANCE.to_string()
+            } else {
+                format!("{}\n\n{}\n", trimmed, GUIDANCE)
+            }
+        }
+    };
+
+    let new_content = if body.starts_with("---\n") {
+        body
+    } else {
+        format!("{}\n\n{}", FRONTMATTER, body)
+    };
+
+    let tmp = rules_dir.join(format!(".tmp.{}", RULES_FILE));
+    fs::write(&tmp, &new_content)?;
+    atomic::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Remove the agent-chat section from `<cursor_dir>/rules/agent-chat.mdc`,
+/// deleting the file when only our frontmatter is left.
+/// No-ops if the file is missing or has no agent-chat section.
+pub fn remove_cursor_rules_from(cursor_dir: &Path) -> Result<()> {
+    let rules_dir = cursor_dir.join("rules");
+    let path = rules_dir.join(RULES_FILE);
+
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(&path)?;
+
+    let Some(start) = existing.find(START_SENTINEL) else {
+        return Ok(());
+    };
+    let Some(end) = existing.find(END_SENTINEL) else {
+        return Ok(());
+    };
+
+    let before = existing[..start].trim_end();
+    let after = existing[end + END_SENTINEL.len()..].trim_start();
+
+    let new_content = match (before.is_empty() || before == FRONTMATTER, after.is_empty()) {
+        (true, true) => String::new(),
+        (true, false) => format!("{}\n\n{}", before, after),
+        (false, true) => format!("{}\n", before),
+        (false, false) => format!("{}\n\n{}\n", before, after),
+    };
+
+    if new_content.is_empty() {
+        fs::remove_file(&path)?;
+    } else {
+        let tmp = rules_dir.join(format!(".tmp.{}", RULES_FILE));
+        fs::write(&tmp, new_content.trim_start())?;
+        atomic::rename(&tmp, &path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn rules_path(tmp: &TempDir) -> std::path::PathBuf {
+        tmp.path().join("rules").join(RULES_FILE)
+    }
+
+    #[test]
+    fn creates_rule_with_frontmatter() {
+        let tmp = TempDir::new().unwrap();
+        install_cursor_rules_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(rules_path(&tmp)).unwrap();
+        assert!(content.starts_with("---\ndescription: "));
+        assert!(content.contains("\nalwaysApply: true\n---\n\n"));
+        assert!(content.contains(START_SENTINEL));
+        assert!(content.contains(END_SENTINEL));
+        assert!(content.contains("agent-chat register --session-id"));
+    }
+
+    #[test]
+    fn keeps_user_edits_around_section() {
+        let tmp = TempDir::new().unwrap();
+        install_cursor_rules_to(tmp.path()).unwrap();
+        let path = rules_path(&tmp);
+        let edited = format!("{}\n\nPrefer small diffs.\n", fs::read_to_string(&path).unwrap());
+        fs::write(&path, edited).unwrap();
+
+        install_cursor_rules_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("Prefer small diffs."));
+        assert_eq!(content.matches("alwaysApply").count(), 1);
+    }
+
+    #[test]
+    fn idempotent() {
+        let tmp = TempDir::new().unwrap();
+        install_cursor_rules_to(tmp.path()).unwrap();
+        let first = fs::read_to_string(rules_path(&tmp)).unwrap();
+        install_cursor_rules_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(rules_path(&tmp)).unwrap();
+        assert_eq!(content, first);
+        assert_eq!(content.matches(START_SENTINEL).count(), 1);
+        assert_eq!(content.matches(END_SENTINEL).count(), 1);
+    }
+
+    #[test]
+    fn remove_deletes_file_when_only_section() {
+        let tmp = TempDir::new().unwrap();
+        install_cursor_rules_to(tmp.path()).unwrap();
+        remove_cursor_rules_from(tmp.path()).unwrap();
+        assert!(!rules_path(&tmp).exists());
+        remove_cursor_rules_from(tmp.path()).unwrap();
+    }
+}
@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::Path;
+use crate::error::Result;
+use crate::hooks::plan;
+use crate::storage::atomic;
+
+const START_SENTINEL: &str = "<!-- agent-chat-gemini:start -->";
+const END_SENTINEL: &str = "<!-- agent-chat-gemini:end -->";
+
+const GUIDANCE: &str = r#"<!-- agent-chat-gemini:start -->
+## Agent Chat (Gemini)
+
+Use `agent-chat` for inter-agent coordination in this repo.
+
+### Commands
+
+- `agent-chat register --session-id <id>` — initialize identity for this Gemini session
+- `agent-chat read` — check unread messages from other agents
+- `agent-chat say "<msg>"` — post short status updates
+- `agent-chat lock "<glob>"` — advisory lock before editing shared files
+- `agent-chat unlock "<glob>"` — release lock immediately after edits
+- `agent-chat locks` — inspect active locks
+- `agent-chat focus "<area>"` — declare active focus area
+- `agent-chat focus --clear` — clear focus when done
+- `agent-chat focuses` — inspect active focuses
+
+### Suggested startup
+
+1. Register once per Gemini session: `agent-chat register --session-id "$USER-$(date +%s)"`
+2. Run `agent-chat read`
+3. Announce scope: `agent-chat say "starting on <task>"`
+4. Lock planned files: `agent-chat lock "src/<area>/**"`
+5. Set focus: `agent-chat focus "<area>"`
+
+### While working
+
+- Run `agent-chat read` every few tool calls.
+- Keep messages short and actionable.
+- If you are blocked, say it and move to another task.
+
+### Finishing
+
+1. Unlock files you touched.
+2. Clear focus.
+3. Announce completion.
+4. Run `agent-chat read` once more.
+<!-- agent-chat-gemini:end -->"#;
+
+/// What `install_gemini_md_to` would do, for `--dry-run`.
+pub fn describe_install(target_dir: &Path) -> Option<String> {
+    plan::section(&target_dir.join("GEMINI.md"), START_SENTINEL)
+}
+
+/// Install or update the agent-chat Gemini section in `<target_dir>/GEMINI.md`.
+pub fn install_gemini_md_to(target_dir: &Path) -> Result<()> {
+    fs::create_dir_all(target_dir)?;
+    let path = target_dir.join("GEMINI.md");
+
+    if !path.exists() {
+        let tmp = target_dir.join(".tmp.GEMINI.md");
+        fs::write(&tmp, GUIDANCE)?;
+        atomic::rename(&tmp, &path)?;
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(&path)?;
+
+    let new_content = if let Some(start) = existing.find(START_SENTINEL) {
+        if let Some(end) = existing.find(END_SENTINEL) {
+            let before = &existing[..start];
+            let after = &existing[end + END_SENTINEL.len()..];
+            format!(
+                "{}{}{}{}",
+                before.trim_end(),
+                if before.is_empty() { "" } else { "\n\n" },
+                GUIDANCE,
+                after
+            )
+        } else {
+            let before = existing[..start].trim_end();
+            if before.is_empty() {
+                GUIDANCE.to_string()
+            } else {
+                format!("{}\n\n{}", before, GUIDANCE)
+            }
+        }
+    } else {
+        let trimmed = existing.trim_end();
+        if trimmed.is_empty() {
+            GUIDANCE.to_string()
+        } else {
+            format!("{}\n\n{}\n", trimmed, GUIDANCE)
+        }
+    };
+
+    let tmp = target_dir.join(".tmp.GEMINI.md");
+    fs::write(&tmp, &new_content)?;
+    atomic::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Remove the agent-chat section from `<target_dir>/GEMINI.md`.
+/// No-ops if the file is missing or has no agent-chat section.
+pub fn remove_gemini_md_from(target_dir: &Path) -> Result<()> {
+    let path = target_dir.join("GEMINI.md");
+
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let existing = fs::read_to_string(&path)?;
+
+    let Some(start) = existing.find(START_SENTINEL) else {
+        return Ok(());
+    };
+    let Some(end) = existing.find(END_SENTINEL) else {
+        return Ok(());
+    };
+
+    let before = existing[..start].trim_end();
+    let after = existing[end + END_SENTINEL.len()..].trim_start();
+
+    let new_content = match (before.is_empty(), after.is_empty()) {
+        (true, true) => String::new(),
+        (true, false) => after.to_string(),
+        (false, true) => format!("{}\n", before),
+        (false, false) => format!("{}\n\n{}\n", before, after),
+    };
+
+    if new_content.is_empty() {
+        fs::remove_file(&path)?;
+    } else {
+        let tmp = target_dir.join(".tmp.GEMINI.md");
+        fs::write(&tmp, &new_content)?;
+        atomic::rename(&tmp, &path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn creates_new_gemini_md() {
+        let tmp = TempDir::new().unwrap();
+        install_gemini_md_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(tmp.path().join("GEMINI.md")).unwrap();
+        assert!(content.contains(START_SENTINEL));
+        assert!(content.contains(END_SENTINEL));
+        assert!(content.contains("agent-chat register --session-id"));
+    }
+
+    #[test]
+    fn appends_to_existing_gemini_md() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("GEMINI.md");
+        fs::write(&path, "# Project Gemini\n\nExisting guidance.\n").unwrap();
+
+        install_gemini_md_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# Project Gemini"));
+        assert!(content.contains("Existing guidance."));
+        assert!(content.contains(START_SENTINEL));
+    }
+
+    #[test]
+    fn idempotent() {
+        let tmp = TempDir::new().unwrap();
+        install_gemini_md_to(tmp.path()).unwrap();
+        install_gemini_md_to(tmp.path()).unwrap();
+
+        let content = fs::read_to_string(tmp.path().join("GEMINI.md")).unwrap();
+        assert_eq!(content.matches(START_SENTINEL).count(), 1);
+        assert_eq!(content.matches(END_SENTINEL).count(), 1);
+    }
+
+    #[test]
+    fn remove_strips_section_preserves_rest() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("GEMINI.md");
+        fs::write(&path, "# Project Gemini\n").unwrap();
+        install_gemini_md_to(tmp.path()).unwrap();
+
+        remove_gemini_md_from(tmp.path()).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "# Project Gemini\n");
+        remove_gemini_md_from(tmp.path()).unwrap();
+    }
+}
@@ -2,6 +2,7 @@ use serde_json::{json, Value};
 use std::fs;
 use std::path::Path;
 use crate::error::Result;
+use crate::storage::atomic;
 
 /// Resolve the absolute path to the current binary.
 /// Falls back to "agent-chat" if resolution fails (e.g. in tests).
@@ -37,7 +38,7 @@ fn hooks_config() -> Value {
                 }]
             }],
             "PreToolUse": [{
-                "matcher": "Edit|Write",
+                "matcher": "Edit|Write|MultiEdit|NotebookEdit",
                 "hooks": [{
                     "type": "command",
                     "command": format!("{} check-lock", bin),
@@ -63,6 +64,16 @@ pub fn install_hooks(project_root: &Path) -> Result<()> {
     install_hooks_to(&project_root.join(".claude"), "settings.local.json")
 }
 
+/// What `install_hooks_to` would do, for `--dry-run`.
+pub fn describe_install(claude_dir: &Path, filename: &str) -> Option<String> {
+    let path = claude_dir.join(filename);
+    Some(if path.exists() {
+        format!("merge agent-chat hooks into {}", path.display())
+    } else {
+        format!("create {} with agent-chat hooks", path.display())
+    })
+}
+
 /// Install hooks by merging into `<claude_dir>/<filename>`.
 /// Creates the directory and file if they don't exist.
 pub fn install_hooks_to(claude_dir: &Path, filename: &str) -> Result<()> {
@@ -71,7 +82,20 @@ pub fn install_hooks_to(claude_dir: &Path, filename: &str) -> Result<()> {
     let settings_path = claude_dir.join(filename);
     let mut existing: Value = if settings_path.exists() {
         let content = fs::read_to_string(&settings_path)?;
-        serde_json::from_str(&content).unwrap_or_else(|_| json!({}))
+        match parse_lenient(&content) {
+            Some(value) => value,
+            None => {
+                // Never drop a settings file we can't read: keep a copy first
+                let backup = claude_dir.join(format!("{}.bak", filename));
+                fs::copy(&settings_path, &backup)?;
+                eprintln!(
+                    "Warning: could not parse {}; saved a copy to {} and started from empty settings.",
+                    settings_path.display(),
+                    backup.display()
+                );
+                json!({})
+            }
+        }
     } else {
         json!({})
     };
@@ -147,7 +171,165 @@ pub fn install_hooks_to(claude_dir: &Path, filename: &str) -> Result<()> {
     let tmp_name = format!(".tmp.{}", filename);
     let tmp = claude_dir.join(tmp_name);
     fs::write(&tmp, &content)?;
-    fs::rename(&tmp, &settings_path)?;
+    atomic::rename(&tmp, &settings_path)?;
+    Ok(())
+}
+
+/// Parse settings as JSON, falling back to JSONC (`//` and `/* */` comments,
+/// trailing commas), which editors and Claude sometimes write.
+pub fn parse_lenient(content: &str) -> Option<Value> {
+    serde_json::from_str(content)
+        .or_else(|_| serde_json::from_str(&strip_jsonc(content)))
+        .ok()
+}
+
+/// Remove comments and trailing commas outside of string literals.
+fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.next_if(|&n| n != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for n in chars.by_ref() {
+                    if prev == '*' && n == '/' {
+                        break;
+                    }
+                    prev = n;
+                }
+            }
+            (']' | '}', _) => {
+                // Drop a trailing comma before the closing bracket
+                let kept = out.trim_end().len();
+                if out[..kept].ends_with(',') {
+                    out.truncate(kept - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Subcommands agent-chat installs as hooks.
+pub const HOOK_SUBCOMMANDS: &[&str] = &["register", "status", "check-lock", "check-messages"];
+
+/// True if `program` is an agent-chat binary: the running executable, or any
+/// path whose file name is `agent-chat` (e.g. installed from another location).
+fn is_agent_chat_program(program: &str) -> bool {
+    program == binary_path()
+        || Path::new(program)
+            .file_name()
+            .map(|n| n == "agent-chat")
+            .unwrap_or(false)
+}
+
+/// True if a hook command was installed by agent-chat, regardless of binary location.
+fn is_agent_chat_hook(command: &str) -> bool {
+    let mut parts = command.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(program), Some(sub), None) => {
+            is_agent_chat_program(program) && HOOK_SUBCOMMANDS.contains(&sub)
+        }
+        _ => false,
+    }
+}
+
+/// Every agent-chat hook command found in a parsed settings file, in file order.
+pub fn agent_chat_hook_commands(settings: &Value) -> Vec<String> {
+    let Some(hooks) = settings.get("hooks").and_then(|h| h.as_object()) else {
+        return Vec::new();
+    };
+    hooks
+        .values()
+        .filter_map(|entries| entries.as_array())
+        .flatten()
+        .filter_map(|entry| entry["hooks"].as_array())
+        .flatten()
+        .filter_map(|h| h["command"].as_str())
+        .filter(|c| is_agent_chat_hook(c))
+        .map(str::to_string)
+        .collect()
+}
+
+/// True if a permission entry is agent-chat's `Bash(<bin> *)` allowance.
+fn is_agent_chat_permission(entry: &str) -> bool {
+    entry
+        .strip_prefix("Bash(")
+        .and_then(|rest| rest.strip_suffix(" *)"))
+        .map(is_agent_chat_program)
+        .unwrap_or(false)
+}
+
+/// Remove agent-chat hooks and permissions from `<claude_dir>/<filename>`,
+/// preserving everything else. No-ops if the file is missing.
+/// Deletes the file if nothing but an empty object would remain.
+pub fn uninstall_hooks_from(claude_dir: &Path, filename: &str) -> Result<()> {
+    let settings_path = claude_dir.join(filename);
+    if !settings_path.exists() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(&settings_path)?;
+    let Some(mut settings) = parse_lenient(&content) else {
+        return Err(crate::error::AgentChatError::SettingsParseError(settings_path));
+    };
+
+    if let Some(perms) = settings.get_mut("permissions").and_then(|p| p.as_object_mut()) {
+        if let Some(allow) = perms.get_mut("allow").and_then(|a| a.as_array_mut()) {
+            allow.retain(|v| !v.as_str().map(is_agent_chat_permission).unwrap_or(false));
+            if allow.is_empty() {
+                perms.remove("allow");
+            }
+        }
+        if perms.is_empty() {
+            settings.as_object_mut().map(|o| o.remove("permissions"));
+        }
+    }
+
+    if let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) {
+        for entries in hooks.values_mut() {
+            let Some(arr) = entries.as_array_mut() else { continue };
+            for entry in arr.iter_mut() {
+                if let Some(inner) = entry.get_mut("hooks").and_then(|h| h.as_array_mut()) {
+                    inner.retain(|h| !h["command"].as_str().map(is_agent_chat_hook).unwrap_or(false));
+                }
+            }
+            arr.retain(|e| e["hooks"].as_array().map(|h| !h.is_empty()).unwrap_or(true));
+        }
+        hooks.retain(|_, entries| entries.as_array().map(|a| !a.is_empty()).unwrap_or(true));
+        if hooks.is_empty() {
+            settings.as_object_mut().map(|o| o.remove("hooks"));
+        }
+    }
+
+    if settings.as_object().map(|o| o.is_empty()).unwrap_or(false) {
+        fs::remove_file(&settings_path)?;
+        return Ok(());
+    }
+
+    let content = serde_json::to_string_pretty(&settings)?;
+    let tmp = claude_dir.join(format!(".tmp.{}", filename));
+    fs::write(&tmp, &content)?;
+    atomic::rename(&tmp, &settings_path)?;
     Ok(())
 }
 
@@ -172,10 +354,49 @@ mod tests {
         // Permission uses absolute binary path
         let allow = val["permissions"]["allow"].as_array().unwrap();
         assert!(allow.iter().any(|v| {
-            v.as_str().map(|s| s.starts_with("Bash(") && s.contains("agent-chat") && s.ends_with("*)")).unwrap_or(false)
+            v.as_str().map(|s| s.starts_with("Bash(") && s.contains(&binary_path()) && s.ends_with("*)")).unwrap_or(false)
         }));
     }
 
+    #[test]
+    fn install_preserves_commented_settings() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        let settings_path = claude_dir.join("settings.local.json");
+        fs::write(
+            &settings_path,
+            "{\n  // my model\n  \"model\": \"opus\", /* keep */\n  \"url\": \"http://x//y\",\n  \"permissions\": {\"allow\": [\"Bash(git *)\",]},\n}\n",
+        )
+        .unwrap();
+
+        install_hooks(tmp.path()).unwrap();
+
+        let val: Value = serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+        assert_eq!(val["model"], "opus");
+        assert_eq!(val["url"], "http://x//y");
+        let allow = val["permissions"]["allow"].as_array().unwrap();
+        assert!(allow.contains(&json!("Bash(git *)")));
+        assert!(val["hooks"]["SessionStart"].is_array());
+        assert!(!claude_dir.join("settings.local.json.bak").exists());
+    }
+
+    #[test]
+    fn install_backs_up_unparseable_settings() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        fs::write(claude_dir.join("settings.local.json"), "{ not json").unwrap();
+
+        install_hooks(tmp.path()).unwrap();
+
+        let backup = fs::read_to_string(claude_dir.join("settings.local.json.bak")).unwrap();
+        assert_eq!(backup, "{ not json");
+        let val: Value =
+            serde_json::from_str(&fs::read_to_string(claude_dir.join("settings.local.json")).unwrap()).unwrap();
+        assert!(val["hooks"]["Stop"].is_array());
+    }
+
     #[test]
     fn install_preserves_existing_settings() {
         let tmp = TempDir::new().unwrap();
@@ -191,7 +412,7 @@ mod tests {
         let allow = val["permissions"]["allow"].as_array().unwrap();
         assert!(allow.contains(&json!("Bash(git *)")));
         assert!(allow.iter().any(|v| {
-            v.as_str().map(|s| s.contains("agent-chat")).unwrap_or(false)
+            v.as_str().map(|s| s.contains(&binary_path())).unwrap_or(false)
         }));
     }
 
@@ -207,4 +428,65 @@ mod tests {
         let session_start = val["hooks"]["SessionStart"].as_array().unwrap();
         assert_eq!(session_start.len(), 1);
     }
+
+    #[test]
+    fn uninstall_removes_only_agent_chat_entries() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        let settings_path = claude_dir.join("settings.local.json");
+        fs::write(
+            &settings_path,
+            r#"{"permissions":{"allow":["Bash(git *)"]},"hooks":{"Stop":[{"hooks":[{"type":"command","command":"my-linter"}]}]},"custom":"value"}"#,
+        )
+        .unwrap();
+
+        install_hooks(tmp.path()).unwrap();
+        uninstall_hooks_from(&claude_dir, "settings.local.json").unwrap();
+
+        let val: Value = serde_json::from_str(&fs::read_to_string(&settings_path).unwrap()).unwrap();
+        assert_eq!(val["permissions"]["allow"], json!(["Bash(git *)"]));
+        assert_eq!(val["custom"], "value");
+        assert_eq!(val["hooks"]["Stop"].as_array().unwrap().len(), 1);
+        assert_eq!(val["hooks"]["Stop"][0]["hooks"][0]["command"], "my-linter");
+        assert!(val["hooks"].get("SessionStart").is_none());
+        assert!(val["hooks"].get("PreToolUse").is_none());
+    }
+
+    #[test]
+    fn uninstall_deletes_file_left_empty_and_is_idempotent() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        install_hooks(tmp.path()).unwrap();
+
+        uninstall_hooks_from(&claude_dir, "settings.local.json").unwrap();
+        assert!(!claude_dir.join("settings.local.json").exists());
+        uninstall_hooks_from(&claude_dir, "settings.local.json").unwrap();
+    }
+
+    #[test]
+    fn uninstall_refuses_unparseable_settings() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        fs::write(claude_dir.join("settings.local.json"), "{ not json").unwrap();
+
+        let err = uninstall_hooks_from(&claude_dir, "settings.local.json").unwrap_err();
+        assert!(
+            matches!(&err, crate::error::AgentChatError::SettingsParseError(path) if path.ends_with("settings.local.json")),
+            "got {:?}",
+            err
+        );
+        assert_eq!(fs::read_to_string(claude_dir.join("settings.local.json")).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn agent_chat_hook_matching() {
+        assert!(is_agent_chat_hook("agent-chat status"));
+        assert!(is_agent_chat_hook("/usr/local/bin/agent-chat check-lock"));
+        assert!(!is_agent_chat_hook("agent-chat-fork status"));
+        assert!(!is_agent_chat_hook("my-linter"));
+        assert!(is_agent_chat_permission("Bash(/opt/bin/agent-chat *)"));
+        assert!(!is_agent_chat_permission("Bash(git *)"));
+    }
 }
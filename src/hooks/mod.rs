@@ -1,5 +1,10 @@
 pub mod agents_md_codex;
 pub mod claude_md;
 pub mod claude_md_br;
+pub mod continue_rules;
+pub mod cursor_rules;
+pub mod gemini_md;
 pub mod installer;
+pub mod plan;
 pub mod stdin;
+pub mod windsurf_rules;
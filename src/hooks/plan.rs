@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::Path;
+use crate::error::Result;
+
+/// Run `install`, or with `dry_run` print what it would do (`describe`
+/// returns `None` when it would change nothing) and write nothing.
+pub fn apply(
+    dry_run: bool,
+    describe: impl FnOnce() -> Option<String>,
+    install: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    if !dry_run {
+        return install();
+    }
+    if let Some(action) = describe() {
+        println!("would {}", action);
+    }
+    Ok(())
+}
+
+/// Describe installing a sentinel-delimited guidance section into `path`.
+pub fn section(path: &Path, start_sentinel: &str) -> Option<String> {
+    Some(match fs::read_to_string(path) {
+        Err(_) => format!("create {}", path.display()),
+        Ok(content) if content.contains(start_sentinel) => {
+            format!("update the agent-chat section in {}", path.display())
+        }
+        Ok(_) => format!("append the agent-chat section to {}", path.display()),
+    })
+}
+
+/// Describe removing a guidance section from `path`, if it has one.
+pub fn section_removal(path: &Path, start_sentinel: &str) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .filter(|content| content.contains(start_sentinel))
+        .map(|_| format!("remove the agent-chat section from {}", path.display()))
+}
+
+/// Describe creating `.agent-chat/` and its config under `project_root`.
+pub fn data_dir(project_root: &Path) -> Option<String> {
+    let base = project_root.join(".agent-chat");
+    (!base.join("config.toml").exists()).then(|| format!("create {} with config.toml", base.display()))
+}
+
+/// Describe adding `pattern` to the project's `.git/info/exclude`.
+pub fn git_exclude(project_root: &Path, pattern: &str) -> Option<String> {
+    let git_dir = project_root.join(".git");
+    if !git_dir.is_dir() {
+        return None;
+    }
+    let exclude_path = git_dir.join("info").join("exclude");
+    let listed = fs::read_to_string(&exclude_path).is_ok_and(|c| c.lines().any(|l| l.trim() == pattern));
+    (!listed).then(|| format!("add {} to {}", pattern, exclude_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn section_describes_create_append_and_update() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("CLAUDE.md");
+        assert!(section(&path, "<!-- s -->").unwrap().starts_with("create "));
+        fs::write(&path, "# Notes\n").unwrap();
+        assert!(section(&path, "<!-- s -->").unwrap().starts_with("append the agent-chat section to "));
+        assert_eq!(section_removal(&path, "<!-- s -->"), None);
+        fs::write(&path, "# Notes\n<!-- s -->\n").unwrap();
+        assert!(section(&path, "<!-- s -->").unwrap().starts_with("update the agent-chat section in "));
+        assert!(section_removal(&path, "<!-- s -->").is_some());
+    }
+
+    #[test]
+    fn dry_run_skips_install() {
+        let mut ran = false;
+        apply(true, || None, || {
+            ran = true;
+            Ok(())
+        })
+        .unwrap();
+        assert!(!ran);
+    }
+}
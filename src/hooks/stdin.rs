@@ -5,7 +5,9 @@ use crate::error::Result;
 #[derive(Debug, Deserialize)]
 pub struct SessionStartInput {
     pub session_id: String,
-    #[allow(dead_code)]
+    /// "startup" for a new process, "resume" when an earlier session is
+    /// continued. Claude Code sends this as `source`.
+    #[serde(alias = "source")]
     pub session_type: Option<String>,
 }
 
@@ -17,6 +19,23 @@ pub struct PreToolUseInput {
     pub tool_input: serde_json::Value,
 }
 
+impl PreToolUseInput {
+    /// Every file the tool call would modify, without duplicates:
+    /// `file_path` (Edit, Write, MultiEdit), `notebook_path` (NotebookEdit),
+    /// and any `file_path` inside a MultiEdit `edits` array.
+    pub fn file_paths(&self) -> Vec<&str> {
+        let input = &self.tool_input;
+        let nested = input["edits"].as_array().into_iter().flatten().map(|e| &e["file_path"]);
+        let mut paths: Vec<&str> = Vec::new();
+        for value in [&input["file_path"], &input["notebook_path"]].into_iter().chain(nested) {
+            if let Some(path) = value.as_str().filter(|p| !paths.contains(p)) {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+}
+
 /// Read and parse hook JSON from stdin.
 pub fn read_session_start() -> Result<SessionStartInput> {
     let mut input = String::new();
@@ -42,6 +61,11 @@ mod tests {
         let json = r#"{"session_id": "abc123", "session_type": "startup"}"#;
         let input: SessionStartInput = serde_json::from_str(json).unwrap();
         assert_eq!(input.session_id, "abc123");
+        assert_eq!(input.session_type.as_deref(), Some("startup"));
+
+        let json = r#"{"session_id": "abc123", "source": "resume"}"#;
+        let input: SessionStartInput = serde_json::from_str(json).unwrap();
+        assert_eq!(input.session_type.as_deref(), Some("resume"));
     }
 
     #[test]
@@ -62,6 +86,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn file_paths_cover_multiedit_and_notebookedit() {
+        let multi: PreToolUseInput = serde_json::from_str(
+            r#"{"tool_name": "MultiEdit", "tool_input": {"file_path": "src/a.rs", "edits": [
+                {"old_string": "x", "new_string": "y"},
+                {"file_path": "src/b.rs", "old_string": "x", "new_string": "y"},
+                {"file_path": "src/a.rs", "old_string": "z", "new_string": "w"}
+            ]}}"#,
+        )
+        .unwrap();
+        assert_eq!(multi.file_paths(), vec!["src/a.rs", "src/b.rs"]);
+
+        let notebook: PreToolUseInput = serde_json::from_str(
+            r#"{"tool_name": "NotebookEdit", "tool_input": {"notebook_path": "nb/analysis.ipynb", "new_source": ""}}"#,
+        )
+        .unwrap();
+        assert_eq!(notebook.file_paths(), vec!["nb/analysis.ipynb"]);
+    }
+
     #[test]
     fn parse_pre_tool_use_write() {
         let json = r#"{
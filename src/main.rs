@@ -8,117 +8,309 @@ mod storage;
 mod ui;
 
 use clap::Parser;
-use cli::{Cli, Command};
+use cli::{Cli, Command, RoomAction};
 use std::process;
 
 fn main() {
     let cli = Cli::parse();
+    let root_flag = cli.root;
 
     let result = match cli.command {
-        Command::Init { project, user, both, claude, codex, both_tools } => {
-            let cwd = std::env::current_dir().unwrap_or_else(|e| {
-                eprintln!("Cannot determine current directory: {}", e);
+        Command::Init { project, user, both, claude, codex, both_tools, continue_, gemini, cursor, windsurf, label, dry_run } => {
+            let cwd = project_dir_or_exit(root_flag.as_deref());
+            commands::init::run(&cwd, project, user, both, claude, codex, both_tools, continue_, gemini, cursor, windsurf, label.as_deref(), dry_run)
+        }
+        Command::Register { session_id, replace_name, name, print_env } => {
+            let root = hook_root_or_exit(root_flag.as_deref());
+            match replace_name {
+                Some(new_name) => commands::register::replace_name(&root, session_id.as_deref(), &new_name),
+                None => commands::register::run(&root, session_id.as_deref(), name.as_deref(), print_env),
+            }
+        }
+        Command::Rename { new_name } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::register::rename(&root, &new_name)
+        }
+        Command::Say { message, file, reply_to, quote, priority, status, to, delay, ttl, truncate, refs } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            let msg = commands::say::message_text(&message, file.as_deref()).unwrap_or_else(|e| {
+                eprintln!("{}", e);
                 process::exit(1);
             });
-            commands::init::run(&cwd, project, user, both, claude, codex, both_tools)
+            if msg.is_empty() {
+                eprintln!("Message cannot be empty.");
+                process::exit(1);
+            }
+            let opts = commands::say::SayOptions {
+                reply_to: reply_to.as_deref(),
+                quote,
+                priority,
+                status,
+                to: to.as_deref(),
+                delay: delay.as_deref(),
+                ttl_secs: ttl,
+                truncate,
+                refs: &refs,
+            };
+            commands::say::run_with(&root, &msg, &opts)
         }
-        Command::Register { session_id } => {
-            let root = find_root_or_exit();
-            commands::register::run(&root, session_id.as_deref())
+        Command::Reply { id, message, quote } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            let msg = message.join(" ");
+            if msg.is_empty() {
+                eprintln!("Message cannot be empty.");
+                process::exit(1);
+            }
+            let opts = commands::say::SayOptions { reply_to: Some(&id), quote, ..Default::default() };
+            commands::say::run_with(&root, &msg, &opts)
         }
-        Command::Say { message } => {
-            let root = find_root_or_exit();
+        Command::Notify { name, message } => {
+            let root = find_root_or_exit(root_flag.as_deref());
             let msg = message.join(" ");
             if msg.is_empty() {
                 eprintln!("Message cannot be empty.");
                 process::exit(1);
             }
-            commands::say::run(&root, &msg)
+            commands::say::notify(&root, &name, &msg)
+        }
+        Command::Ack { id } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::say::ack(&root, &id)
+        }
+        Command::Receipts { id } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::receipts::run(&root, &id)
+        }
+        Command::Read { all, watch, timeout, by_author, min_priority, count, json, from, since } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            if watch {
+                commands::read::watch(&root, timeout, min_priority)
+            } else {
+                commands::read::run(
+                    &root,
+                    &commands::read::ReadOptions { show_all: all, min_priority, by_author, count, json, from, since },
+                )
+            }
         }
-        Command::Read { all } => {
-            let root = find_root_or_exit();
-            commands::read::run(&root, all)
+        Command::History { name, include_archive } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::history::run(&root, name.as_deref(), include_archive)
+        }
+        Command::Search { query, author, since } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::search::run(&root, &query, author.as_deref(), since.as_deref())
+        }
+        Command::Export { anonymize, redact, json, out, include_joins } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            let opts = commands::export::ExportOptions { anonymize, redact: &redact, json, out, include_joins };
+            commands::export::run(&root, &opts)
+        }
+        Command::Diff { from, to } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::diff::run(&root, &from, &to)
+        }
+        Command::Compact { older_than, delete, force } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::compact::run(&root, &older_than, delete, force)
+        }
+        Command::Clear { messages, locks, focuses, cursors, yes } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            let targets = commands::clear::ClearTargets { messages, locks, focuses, cursors };
+            commands::clear::run(&root, targets, yes)
         }
         Command::Status => {
-            let root = find_root_or_exit();
+            let root = hook_root_or_exit(root_flag.as_deref());
             commands::status::run(&root)
         }
-        Command::Lock { glob } => {
-            let root = find_root_or_exit();
-            commands::lock::acquire(&root, &glob)
+        Command::MarkRead { to } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::read::mark_read(&root, to.as_deref())
+        }
+        Command::Mute { name } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            match name {
+                Some(name) => commands::mute::mute(&root, &name),
+                None => commands::mute::list(&root),
+            }
+        }
+        Command::Unmute { name } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::mute::unmute(&root, &name)
+        }
+        Command::Lock { glob, ttl, renew, wait, list_files } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            if list_files {
+                commands::lock::list_files(&root, &glob)
+            } else if renew {
+                commands::lock::renew(&root, &glob, ttl)
+            } else {
+                commands::lock::acquire(&root, &glob, ttl, wait)
+            }
         }
-        Command::Unlock { glob } => {
-            let root = find_root_or_exit();
-            commands::lock::release(&root, &glob)
+        Command::Unlock { glob, all, force } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            match glob {
+                Some(glob) if force => commands::lock::force_release(&root, &glob),
+                Some(glob) if !all => commands::lock::release(&root, &glob),
+                _ => commands::lock::release_all(&root),
+            }
         }
-        Command::Locks => {
-            let root = find_root_or_exit();
-            commands::lock::list(&root)
+        Command::Locks { porcelain, json } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::lock::list(&root, porcelain, json)
         }
         Command::CheckLock => {
-            let root = find_root_or_exit();
+            let root = hook_root_or_exit(root_flag.as_deref());
             commands::check_lock::run(&root)
         }
         Command::CheckMessages => {
-            let root = find_root_or_exit();
+            let root = hook_root_or_exit(root_flag.as_deref());
             commands::check_messages::run(&root)
         }
-        Command::Focus { text, clear } => {
-            let root = find_root_or_exit();
+        Command::Focus { text, clear, ttl } => {
+            let root = find_root_or_exit(root_flag.as_deref());
             if clear {
                 commands::focus::clear(&root)
             } else if let Some(text) = text {
-                commands::focus::set(&root, &text)
+                commands::focus::set(&root, &text, ttl)
             } else {
                 eprintln!("Usage: agent-chat focus \"<area>\" or agent-chat focus --clear");
                 process::exit(1);
             }
         }
-        Command::Focuses => {
-            let root = find_root_or_exit();
-            commands::focus::list(&root)
+        Command::Focuses { porcelain, json, history } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            if history {
+                commands::focus::history(&root)
+            } else {
+                commands::focus::list(&root, porcelain, json)
+            }
         }
-        Command::InitBr { project, user } => {
-            let cwd = std::env::current_dir().unwrap_or_else(|e| {
-                eprintln!("Cannot determine current directory: {}", e);
-                process::exit(1);
-            });
-            commands::init_br::run(&cwd, project, user)
+        Command::Stats { json } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::stats::run(&root, json)
         }
-        Command::InitCodex { project, user, both } => {
-            let cwd = std::env::current_dir().unwrap_or_else(|e| {
-                eprintln!("Cannot determine current directory: {}", e);
-                process::exit(1);
-            });
-            commands::init_codex::run(&cwd, project, user, both)
+        Command::Board { json } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::board::show(&root, json)
+        }
+        Command::Agents { porcelain, sort } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::agents::list(&root, porcelain, sort)
+        }
+        Command::InitBr { project, user, dry_run } => {
+            let cwd = project_dir_or_exit(root_flag.as_deref());
+            commands::init_br::run(&cwd, project, user, dry_run)
+        }
+        Command::Doctor => {
+            let cwd = project_dir_or_exit(root_flag.as_deref());
+            commands::doctor::run(&cwd, root_flag.as_deref())
+        }
+        Command::Uninstall { project, user, both, purge } => {
+            let cwd = project_dir_or_exit(root_flag.as_deref());
+            commands::uninstall::run(&cwd, project, user, both, purge)
+        }
+        Command::UninstallTool { tool, project, user, both } => {
+            let cwd = project_dir_or_exit(root_flag.as_deref());
+            commands::uninstall::run_tool(&cwd, tool, project, user, both)
+        }
+        Command::InitCodex { project, user, both, dry_run } => {
+            let cwd = project_dir_or_exit(root_flag.as_deref());
+            commands::init_codex::run(&cwd, project, user, both, dry_run)
+        }
+        Command::Rooms => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::room::list(&root)
+        }
+        Command::Room { action } => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            match action {
+                RoomAction::New { name } => commands::room::create(&root, &name),
+                RoomAction::Rm { name } => commands::room::remove(&root, &name),
+                RoomAction::Use { name } => commands::room::switch(&root, &name),
+            }
         }
         Command::BrClaim { id } => {
-            let root = find_root_or_exit();
+            let root = find_root_or_exit(root_flag.as_deref());
             commands::br_claim::run(&root, &id)
         }
+        Command::BrList => {
+            let root = find_root_or_exit(root_flag.as_deref());
+            commands::br::list(&root)
+        }
         Command::BrComplete { id, reason } => {
-            let root = find_root_or_exit();
+            let root = find_root_or_exit(root_flag.as_deref());
             commands::br_complete::run(&root, &id, reason.as_deref())
         }
     };
 
     if let Err(e) = result {
+        match e {
+            error::AgentChatError::LockConflict { .. } | error::AgentChatError::LockOverlap { .. } => {
+                eprintln!("{}", ui::warning(&e.to_string()))
+            }
+            _ => eprintln!("{}", e),
+        }
+        // `read --watch` reports a timeout through its exit status
+        // as does `doctor` when a check fails
+        if matches!(e, error::AgentChatError::WatchTimeout(_) | error::AgentChatError::DoctorFailed(_)) {
+            process::exit(1);
+        }
         // Hook commands exit 0 even on error (advisory, never block)
-        eprintln!("{}", e);
         process::exit(0);
     }
 }
 
-fn find_root_or_exit() -> std::path::PathBuf {
-    let cwd = std::env::current_dir().unwrap_or_else(|e| {
-        eprintln!("Cannot determine current directory: {}", e);
+/// Project directory for commands that set up or inspect an install: the
+/// `--root` project if given, else the current directory.
+fn project_dir_or_exit(explicit: Option<&std::path::Path>) -> std::path::PathBuf {
+    let result = match explicit {
+        Some(path) => storage::paths::project_dir(path),
+        None => std::env::current_dir().map_err(|e| {
+            error::AgentChatError::Other(format!("Cannot determine current directory: {}", e))
+        }),
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    })
+}
+
+fn find_root_or_exit(explicit: Option<&std::path::Path>) -> std::path::PathBuf {
+    let root = locate_root_or_exit(explicit);
+    // Refuse to guess the room when the config can't be read
+    if let Err(e) = storage::paths::resolve_room(&root) {
+        eprintln!("{}", e);
         process::exit(1);
-    });
-    match storage::paths::find_root(&cwd) {
-        Ok(root) => root,
-        Err(e) => {
-            eprintln!("{}", e);
-            process::exit(1);
-        }
     }
+    root
+}
+
+/// `find_root_or_exit` for hook commands, which are advisory: a room that
+/// can't be resolved is reported on stderr and the hook does nothing.
+fn hook_root_or_exit(explicit: Option<&std::path::Path>) -> std::path::PathBuf {
+    let root = locate_root_or_exit(explicit);
+    if let Err(e) = storage::paths::resolve_room(&root) {
+        eprintln!("{}", ui::warning(&e.to_string()));
+        process::exit(0);
+    }
+    root
+}
+
+fn locate_root_or_exit(explicit: Option<&std::path::Path>) -> std::path::PathBuf {
+    // `--root` wins over `AGENT_CHAT_ROOT` and the upward walk
+    let root = match explicit {
+        Some(path) => storage::paths::explicit_root(path),
+        None => {
+            let cwd = std::env::current_dir().unwrap_or_else(|e| {
+                eprintln!("Cannot determine current directory: {}", e);
+                process::exit(1);
+            });
+            storage::paths::discover_root(&cwd)
+        }
+    };
+    root.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    })
 }
@@ -1,4 +1,26 @@
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use crate::error::{AgentChatError, Result};
+
+/// How `register` generates names for new sessions (`name_style` in config).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NameStyle {
+    /// Random adjective-animal pairs like `swift-fox`.
+    #[default]
+    Animal,
+    /// Sequential `agent-1`, `agent-2`, ...
+    Numbered,
+}
+
+impl NameStyle {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NameStyle::Animal => "animal",
+            NameStyle::Numbered => "numbered",
+        }
+    }
+}
 
 const ADJECTIVES: &[&str] = &[
     "amber", "bold", "bright", "calm", "clever",
@@ -34,6 +56,31 @@ pub fn generate_name() -> String {
     format!("{}-{}", adj, animal)
 }
 
+/// Generate a name in `style`. `ordinal` numbers `Numbered` names and is
+/// ignored for `Animal`.
+pub fn generate_name_for(style: NameStyle, ordinal: usize) -> String {
+    match style {
+        NameStyle::Animal => generate_name(),
+        NameStyle::Numbered => format!("agent-{}", ordinal),
+    }
+}
+
+/// Validate a user-chosen name: 1-32 chars of lowercase letters, digits, and dashes.
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 32
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        return Err(AgentChatError::Other(format!(
+            "invalid name '{}': use 1-32 lowercase letters, digits, and dashes",
+            name
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,6 +103,22 @@ mod tests {
         assert!(names.len() > 10, "Expected variety, got {} unique names", names.len());
     }
 
+    #[test]
+    fn validate_name_accepts_and_rejects() {
+        assert!(validate_name("swift-fox").is_ok());
+        assert!(validate_name("reviewer2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("Swift Fox").is_err());
+        assert!(validate_name("-fox").is_err());
+        assert!(validate_name(&"a".repeat(33)).is_err());
+    }
+
+    #[test]
+    fn numbered_style_uses_ordinal() {
+        assert_eq!(generate_name_for(NameStyle::Numbered, 3), "agent-3");
+        assert!(validate_name(&generate_name_for(NameStyle::Animal, 3)).is_ok());
+    }
+
     #[test]
     fn word_list_sizes() {
         assert_eq!(ADJECTIVES.len(), 50);
@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use crate::error::Result;
+use crate::storage::{atomic, log};
+
+/// Marker line that starts each archived message: `--- <timestamp_ns> <bytes>`,
+/// followed by exactly `<bytes>` of message content, so a body line that looks
+/// like a marker can't split the entry.
+const ENTRY_PREFIX: &str = "--- ";
+
+/// Parse a marker line into `(timestamp_ns, byte_count)`.
+fn parse_marker(line: &str) -> Option<(&str, usize)> {
+    let (id, len) = line.strip_prefix(ENTRY_PREFIX)?.split_once(' ')?;
+    let len = len.parse().ok()?;
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_digit())).then_some((id, len))
+}
+
+/// Archive file for the UTC day containing `timestamp_ns`: `<dir>/<YYYY-MM-DD>.md`
+fn archive_file(archive_dir: &Path, timestamp_ns: u128) -> PathBuf {
+    let secs = (timestamp_ns / 1_000_000_000) as i64;
+    let date = DateTime::<Utc>::from_timestamp(secs, 0)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    archive_dir.join(format!("{}.md", date))
+}
+
+/// Move messages with a filename timestamp older than `cutoff_ns` from the log
+/// into per-day archive files. Originals are removed only after the archive
+/// write succeeds. Returns the number of messages archived.
+pub fn compact(log_dir: &Path, archive_dir: &Path, cutoff_ns: u128) -> Result<usize> {
+    let mut batches: Vec<(PathBuf, Vec<(String, PathBuf)>)> = Vec::new();
+    for (filename, path) in log::list_messages(log_dir)? {
+        let stem = filename.trim_end_matches(".md");
+        let ts = match stem.parse::<u128>() {
+            Ok(ts) if ts < cutoff_ns => ts,
+            _ => continue,
+        };
+        let target = archive_file(archive_dir, ts);
+        match batches.iter_mut().find(|(t, _)| *t == target) {
+            Some((_, entries)) => entries.push((stem.to_string(), path)),
+            None => batches.push((target, vec![(stem.to_string(), path)])),
+        }
+    }
+
+    if batches.is_empty() {
+        return Ok(0);
+    }
+
+    fs::create_dir_all(archive_dir)?;
+    let mut archived = 0;
+    for (target, entries) in &batches {
+        let mut content = if target.exists() {
+            fs::read_to_string(target)?
+        } else {
+            String::new()
+        };
+        for (id, path) in entries {
+            let body = format!("{}\n", fs::read_to_string(path)?.trim_end());
+            content.push_str(&format!("{}{} {}\n", ENTRY_PREFIX, id, body.len()));
+            content.push_str(&body);
+        }
+
+        let file_name = target.file_name().unwrap().to_string_lossy();
+        let tmp = archive_dir.join(format!(".tmp.{}", file_name));
+        fs::write(&tmp, &content)?;
+        atomic::rename(&tmp, target)?;
+
+        for (_, path) in entries {
+            // Ignore ENOENT race with a concurrent compaction
+            let _ = fs::remove_file(path);
+            archived += 1;
+        }
+    }
+    let _ = log::prune_index(log_dir);
+    Ok(archived)
+}
+
+/// List archived messages as `(timestamp_ns, raw_content)` pairs in chronological order.
+pub fn list_archived(archive_dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut entries = Vec::new();
+    if !archive_dir.exists() {
+        return Ok(entries);
+    }
+
+    for entry in fs::read_dir(archive_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.ends_with(".md") || name.starts_with(".tmp.") {
+            continue;
+        }
+        let content = fs::read_to_string(entry.path())?;
+        let mut rest = content.as_str();
+        while !rest.is_empty() {
+            let (line, after) = match rest.find('\n') {
+                Some(i) => (&rest[..i], &rest[i + 1..]),
+                None => (rest, ""),
+            };
+            rest = after;
+            let Some((id, len)) = parse_marker(line) else {
+                continue;
+            };
+            // A truncated file ends with a partial entry; leave it out
+            if after.len() < len || !after.is_char_boundary(len) {
+                break;
+            }
+            entries.push((id.to_string(), after[..len].to_string()));
+            rest = &after[len..];
+        }
+    }
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format;
+    use tempfile::TempDir;
+
+    #[test]
+    fn compact_moves_old_messages() {
+        let tmp = TempDir::new().unwrap();
+        let log_dir = tmp.path().join("log");
+        let archive_dir = tmp.path().join("archive");
+        fs::create_dir(&log_dir).unwrap();
+        fs::write(log_dir.join("1736950200000000000.md"), "name: swift-fox\nold one\n").unwrap();
+        fs::write(log_dir.join("1736950260000000000.md"), "name: bold-hawk\nold two\n").unwrap();
+        fs::write(log_dir.join("9736950200000000000.md"), "name: swift-fox\nnew\n").unwrap();
+
+        let archived = compact(&log_dir, &archive_dir, 2_000_000_000_000_000_000).unwrap();
+        assert_eq!(archived, 2);
+        assert_eq!(log::list_messages(&log_dir).unwrap().len(), 1);
+
+        let entries = list_archived(&archive_dir).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "1736950200000000000");
+        let (name, _, body) = format::parse_message_file(&entries[1].1).unwrap();
+        assert_eq!(name, "bold-hawk");
+        assert_eq!(body, "old two");
+    }
+
+    #[test]
+    fn compact_prunes_author_index() {
+        let tmp = TempDir::new().unwrap();
+        let log_dir = tmp.path().join("log");
+        let archive_dir = tmp.path().join("archive");
+        fs::create_dir(&log_dir).unwrap();
+        log::write_message(&log_dir, "swift-fox", "old").unwrap();
+        log::write_message(&log_dir, "bold-hawk", "also old").unwrap();
+        let index_path = tmp.path().join("log.index");
+        assert_eq!(fs::read_to_string(&index_path).unwrap().lines().count(), 2);
+
+        assert_eq!(compact(&log_dir, &archive_dir, u128::MAX).unwrap(), 2);
+        assert_eq!(fs::read_to_string(&index_path).unwrap(), "");
+    }
+
+    #[test]
+    fn compact_appends_to_existing_archive() {
+        let tmp = TempDir::new().unwrap();
+        let log_dir = tmp.path().join("log");
+        let archive_dir = tmp.path().join("archive");
+        fs::create_dir(&log_dir).unwrap();
+
+        fs::write(log_dir.join("1736950200000000000.md"), "name: swift-fox\nfirst\n").unwrap();
+        compact(&log_dir, &archive_dir, u128::MAX).unwrap();
+        fs::write(log_dir.join("1736950260000000000.md"), "name: swift-fox\nsecond\n").unwrap();
+        compact(&log_dir, &archive_dir, u128::MAX).unwrap();
+
+        let entries = list_archived(&archive_dir).unwrap();
+        assert_eq!(entries.len(), 2);
+    }
+
+    #[test]
+    fn body_with_marker_line_round_trips() {
+        let tmp = TempDir::new().unwrap();
+        let log_dir = tmp.path().join("log");
+        let archive_dir = tmp.path().join("archive");
+        fs::create_dir(&log_dir).unwrap();
+
+        let tricky = "name: swift-fox\n\nsee below\n--- 1736950200000000001\nstill mine";
+        fs::write(log_dir.join("1736950200000000000.md"), tricky).unwrap();
+        fs::write(log_dir.join("1736950260000000000.md"), "name: bold-hawk\n\nnext\n").unwrap();
+        compact(&log_dir, &archive_dir, u128::MAX).unwrap();
+
+        let entries = list_archived(&archive_dir).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].1, format!("{}\n", tricky));
+        let (name, _, body) = format::parse_message_file(&entries[1].1).unwrap();
+        assert_eq!((name, body), ("bold-hawk", "next"));
+    }
+
+    #[test]
+    fn list_archived_missing_dir() {
+        let tmp = TempDir::new().unwrap();
+        assert!(list_archived(&tmp.path().join("archive")).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use crate::error::{AgentChatError, Result};
+
+/// How long to wait for a contended `with_lock` before giving up.
+const LOCK_WAIT: Duration = Duration::from_secs(5);
+/// A lock file older than this is assumed left behind by a crashed process.
+const LOCK_STALE: Duration = Duration::from_secs(10);
+const LOCK_POLL: Duration = Duration::from_millis(5);
+
+/// Replace `to` with `from` atomically. Callers write `from` as a `.tmp.`
+/// file in the same directory as `to`, so both are always on one filesystem
+/// (a symlinked `.agent-chat/` on another mount included, since the tmp file
+/// is reached through the same link) and readers never see a torn file.
+pub fn rename(from: &Path, to: &Path) -> Result<()> {
+    fs::rename(from, to)?;
+    Ok(())
+}
+
+/// Run `f` while holding an exclusive lock file at `lock_path`, created with
+/// `create_new` so concurrent processes serialize their check-and-write steps.
+/// Stale locks from crashed processes are broken after `LOCK_STALE`.
+pub fn with_lock<T>(lock_path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let deadline = SystemTime::now() + LOCK_WAIT;
+    loop {
+        match fs::OpenOptions::new().write(true).create_new(true).open(lock_path) {
+            Ok(_) => break,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                let stale = fs::metadata(lock_path)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .is_some_and(|age| age > LOCK_STALE);
+                if stale {
+                    let _ = fs::remove_file(lock_path);
+                    continue;
+                }
+                if SystemTime::now() > deadline {
+                    return Err(AgentChatError::Other(format!(
+                        "Timed out waiting for {}",
+                        lock_path.display()
+                    )));
+                }
+                thread::sleep(LOCK_POLL);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let result = f();
+    let _ = fs::remove_file(lock_path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rename_moves_file() {
+        let tmp = TempDir::new().unwrap();
+        let from = tmp.path().join(".tmp.a");
+        let to = tmp.path().join("a");
+        fs::write(&from, "hello").unwrap();
+        rename(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert_eq!(fs::read_to_string(&to).unwrap(), "hello");
+    }
+
+    #[test]
+    fn with_lock_releases_and_breaks_stale_locks() {
+        let tmp = TempDir::new().unwrap();
+        let lock = tmp.path().join("test.lock");
+
+        assert_eq!(with_lock(&lock, || Ok(1)).unwrap(), 1);
+        assert!(!lock.exists());
+
+        fs::write(&lock, "").unwrap();
+        let old = filetime::FileTime::from_unix_time(0, 0);
+        filetime::set_file_mtime(&lock, old).unwrap();
+        assert_eq!(with_lock(&lock, || Ok(2)).unwrap(), 2);
+        assert!(!lock.exists());
+    }
+
+    #[test]
+    fn errors_propagate() {
+        let tmp = TempDir::new().unwrap();
+        let from = tmp.path().join(".tmp.a");
+        let to = tmp.path().join("a");
+
+        assert!(rename(&from, &to).is_err());
+        assert!(!to.exists());
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use crate::error::Result;
+use crate::error::{AgentChatError, Result};
+use crate::format::Priority;
+use crate::names::{self, NameStyle};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
@@ -8,6 +10,73 @@ pub struct Config {
     pub lock_ttl_secs: u64,
     #[serde(default = "default_focus_ttl")]
     pub focus_ttl_secs: u64,
+    /// Shared significant words needed before two focuses count as overlapping.
+    #[serde(default = "default_focus_overlap_min_tokens")]
+    pub focus_overlap_min_tokens: usize,
+    /// Lowest overlap score (shared fraction of significant words, 0.0-1.0) that gets reported.
+    #[serde(default)]
+    pub focus_overlap_min_score: f32,
+    /// Post a chat message whenever a session sets or clears its focus.
+    #[serde(default)]
+    pub announce_focus: bool,
+    /// Emit a benign "No unread messages." context from `status` when clear.
+    #[serde(default)]
+    pub status_report_clear: bool,
+    /// Block the Stop hook at most this many times on the same unread backlog,
+    /// then pass it as non-blocking context (0 = always block).
+    #[serde(default)]
+    pub status_block_limit: u32,
+    /// Lowest message priority that makes `status` block; lower ones only add context.
+    #[serde(default)]
+    pub status_block_priority: Priority,
+    /// Authors whose unread messages always make `status` block, even past the limit.
+    #[serde(default)]
+    pub always_block_from: Vec<String>,
+    /// Post a one-time chat note when a registered session's lock expires within this many seconds (0 = off).
+    #[serde(default = "default_lock_expiry_warning")]
+    pub lock_expiry_warning_secs: u64,
+    /// Remind a session from `status` when one of its locks expires within this many seconds (0 = off).
+    #[serde(default)]
+    pub status_lock_expiry_secs: u64,
+    /// Always advance the cursor on `register`, even on resume with nothing to inject.
+    #[serde(default)]
+    pub register_consumes_backlog: bool,
+    /// Post "resumed the chat" when a known session registers with `session_type: "resume"`.
+    #[serde(default)]
+    pub announce_resume: bool,
+    /// Have `check-lock` post a chat message to the lock owner on conflict.
+    #[serde(default)]
+    pub checklock_notify_owner: bool,
+    /// Have `check-lock` emit `decision: block` on conflict instead of an advisory message.
+    #[serde(default)]
+    pub check_lock_block: bool,
+    /// Most live locks one session may hold at once (0 = unlimited).
+    #[serde(default)]
+    pub max_locks_per_session: usize,
+    /// Largest message body `say` accepts, in bytes (0 = unlimited).
+    #[serde(default = "default_max_message_bytes")]
+    pub max_message_bytes: usize,
+    /// Skip a `say` repeating the author's last message within this many seconds (0 = off).
+    #[serde(default)]
+    pub dedup_window_secs: u64,
+    /// Messages shown by `read`, `status`, and `check-messages` for a session with no cursor yet.
+    #[serde(default = "default_first_read_count")]
+    pub first_read_count: usize,
+    /// Unread backlogs larger than this get a one-line digest from `check-messages` (0 = never).
+    #[serde(default = "default_digest_threshold")]
+    pub check_messages_digest_threshold: usize,
+    /// Run `br sync --flush-only` after `br-complete` closes an issue.
+    #[serde(default = "default_true")]
+    pub br_auto_sync: bool,
+    /// Scheme for generated session names.
+    #[serde(default)]
+    pub name_style: NameStyle,
+    /// Active room under `rooms/`; empty means the default room.
+    #[serde(default)]
+    pub room: String,
+    /// Label scoping this setup's CLAUDE.md section; empty means unlabeled.
+    #[serde(default)]
+    pub instance_label: String,
 }
 
 fn default_lock_ttl() -> u64 {
@@ -18,19 +87,222 @@ fn default_focus_ttl() -> u64 {
     300
 }
 
+fn default_focus_overlap_min_tokens() -> usize {
+    1
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_message_bytes() -> usize {
+    4096
+}
+
+fn default_first_read_count() -> usize {
+    5
+}
+
+fn default_lock_expiry_warning() -> u64 {
+    30
+}
+
+fn default_digest_threshold() -> usize {
+    10
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             lock_ttl_secs: default_lock_ttl(),
             focus_ttl_secs: default_focus_ttl(),
+            focus_overlap_min_tokens: default_focus_overlap_min_tokens(),
+            focus_overlap_min_score: 0.0,
+            announce_focus: false,
+            status_report_clear: false,
+            status_block_limit: 0,
+            status_block_priority: Priority::default(),
+            always_block_from: Vec::new(),
+            lock_expiry_warning_secs: default_lock_expiry_warning(),
+            status_lock_expiry_secs: 0,
+            register_consumes_backlog: false,
+            announce_resume: false,
+            checklock_notify_owner: false,
+            check_lock_block: false,
+            max_locks_per_session: 0,
+            max_message_bytes: default_max_message_bytes(),
+            dedup_window_secs: 0,
+            first_read_count: default_first_read_count(),
+            check_messages_digest_threshold: default_digest_threshold(),
+            br_auto_sync: true,
+            name_style: NameStyle::default(),
+            room: String::new(),
+            instance_label: String::new(),
         }
     }
 }
 
+impl Config {
+    /// The instance label, or `None` when unlabeled.
+    pub fn label(&self) -> Option<&str> {
+        Some(self.instance_label.as_str()).filter(|l| !l.is_empty())
+    }
+}
+
+/// Render the default config with a comment above every key.
+fn documented_default_config() -> String {
+    let c = Config::default();
+    format!(
+        r#"# agent-chat configuration. Every key is optional; shown values are the defaults.
+
+# Seconds a `lock` lasts unless renewed or overridden with `lock --ttl`.
+lock_ttl_secs = {lock_ttl_secs}
+
+# Seconds a `focus` stays active before it expires.
+focus_ttl_secs = {focus_ttl_secs}
+
+# Significant words two focuses must share before `focus` warns about overlap.
+focus_overlap_min_tokens = {focus_overlap_min_tokens}
+
+# Lowest overlap score that gets reported: the fraction of the smaller focus's
+# significant words the two share, from 0.0 (any shared word) to 1.0 (one
+# contains the other), e.g. 0.5 to skip overlaps on a minor word.
+focus_overlap_min_score = {focus_overlap_min_score:?}
+
+# Post "focusing on: <area>" / "cleared focus: <area>" to the chat on `focus`
+# and `focus --clear`, so other agents' hooks pick up the change.
+announce_focus = {announce_focus}
+
+# Emit a "No unread messages." context from the Stop hook when there is nothing to read.
+status_report_clear = {status_report_clear}
+
+# Block the Stop hook at most this many times on the same unread backlog, then
+# pass it along as non-blocking context instead (0 = always block).
+status_block_limit = {status_block_limit}
+
+# Lowest message priority (low, normal, high) that blocks the Stop hook; unread
+# messages below it are passed along as non-blocking context instead.
+status_block_priority = "{status_block_priority}"
+
+# Authors whose unread messages always block the Stop hook, e.g. ["lead", "ci-bot"].
+always_block_from = {always_block_from:?}
+
+# Post a one-time chat note ("swift-fox's lock on src/*.rs expires in 25s") when a
+# registered session's lock expires within this many seconds. Checked whenever
+# `lock`, `locks` or `check-lock` runs (0 = off).
+lock_expiry_warning_secs = {lock_expiry_warning_secs}
+
+# Have the Stop hook remind a session when one of its own locks expires within
+# this many seconds ("your lock on src/*.rs expires in 45s"), e.g. 60 (0 = off).
+status_lock_expiry_secs = {status_lock_expiry_secs}
+
+# Always advance the read cursor on `register`, even on resume with nothing to inject.
+register_consumes_backlog = {register_consumes_backlog}
+
+# Post "resumed the chat" when a known session resumes (SessionStart with
+# session_type "resume"). A fresh "startup" always announces itself.
+announce_resume = {announce_resume}
+
+# Post a chat message to the lock owner when `check-lock` finds a conflict
+# (once per session per lock, not on every retried edit).
+checklock_notify_owner = {checklock_notify_owner}
+
+# Make `check-lock` block the edit (decision: block) instead of only warning.
+check_lock_block = {check_lock_block}
+
+# Most live locks one session may hold at once (0 = unlimited).
+max_locks_per_session = {max_locks_per_session}
+
+# Largest message body `say` accepts, in bytes; longer messages are rejected
+# unless sent with `say --truncate` (0 = unlimited).
+max_message_bytes = {max_message_bytes}
+
+# Drop a `say` whose body repeats the author's latest message from the last
+# this-many seconds, e.g. from an agent stuck in a retry loop (0 = off).
+dedup_window_secs = {dedup_window_secs}
+
+# Recent messages shown on a session's first read, before it has a cursor.
+first_read_count = {first_read_count}
+
+# Unread backlogs larger than this reach the PreToolUse hook (`check-messages`)
+# as a one-line digest instead of in full, once per backlog (0 = never digest).
+check_messages_digest_threshold = {check_messages_digest_threshold}
+
+# Export the beads JSONL (`br sync --flush-only`) after `br-complete` closes an issue.
+br_auto_sync = {br_auto_sync}
+
+# Generated session names: "animal" (swift-fox) or "numbered" (agent-1, agent-2, ...).
+name_style = "{name_style}"
+
+# Active chat room (see `agent-chat rooms`); empty means the default room.
+room = "{room}"
+
+# Label for this agent-chat setup, set by `init --label`. It scopes the CLAUDE.md
+# section markers (`<!-- agent-chat:<label>:start -->`) so several setups, e.g.
+# one per monorepo subproject, can share a CLAUDE.md. Empty means unlabeled.
+instance_label = "{instance_label}"
+"#,
+        lock_ttl_secs = c.lock_ttl_secs,
+        focus_ttl_secs = c.focus_ttl_secs,
+        focus_overlap_min_tokens = c.focus_overlap_min_tokens,
+        focus_overlap_min_score = c.focus_overlap_min_score,
+        announce_focus = c.announce_focus,
+        status_report_clear = c.status_report_clear,
+        status_block_limit = c.status_block_limit,
+        status_block_priority = c.status_block_priority.as_str(),
+        always_block_from = c.always_block_from,
+        lock_expiry_warning_secs = c.lock_expiry_warning_secs,
+        status_lock_expiry_secs = c.status_lock_expiry_secs,
+        register_consumes_backlog = c.register_consumes_backlog,
+        announce_resume = c.announce_resume,
+        checklock_notify_owner = c.checklock_notify_owner,
+        check_lock_block = c.check_lock_block,
+        max_locks_per_session = c.max_locks_per_session,
+        max_message_bytes = c.max_message_bytes,
+        dedup_window_secs = c.dedup_window_secs,
+        first_read_count = c.first_read_count,
+        check_messages_digest_threshold = c.check_messages_digest_threshold,
+        br_auto_sync = c.br_auto_sync,
+        name_style = c.name_style.as_str(),
+        room = c.room,
+        instance_label = c.instance_label,
+    )
+}
+
 pub fn write_default_config(path: &Path) -> Result<()> {
-    let config = Config::default();
-    let content = toml::to_string_pretty(&config)?;
-    std::fs::write(path, content)?;
+    std::fs::write(path, documented_default_config())?;
+    Ok(())
+}
+
+/// Set one top-level key in an existing config file, keeping comments and
+/// other lines intact. `value` must already be a TOML literal (e.g. `"auth"`).
+pub fn set_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    let content = if path.exists() {
+        std::fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+    let line = format!("{} = {}", key, value);
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|l| {
+            let is_key = l.split('=').next().is_some_and(|k| k.trim() == key);
+            if is_key && !replaced {
+                replaced = true;
+                line.clone()
+            } else {
+                l.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        lines.push(line);
+    }
+    let updated = format!("{}\n", lines.join("\n"));
+    // Refuse to write something read_config would reject
+    validate(&toml::from_str::<Config>(&updated)?)?;
+    std::fs::write(path, updated)?;
     Ok(())
 }
 
@@ -40,5 +312,89 @@ pub fn read_config(path: &Path) -> Result<Config> {
     }
     let content = std::fs::read_to_string(path)?;
     let config: Config = toml::from_str(&content)?;
+    validate(&config)?;
     Ok(config)
 }
+
+/// Reject values that parse but can't work, such as a zero TTL that would
+/// make every lock or focus expire the moment it is set.
+fn validate(config: &Config) -> Result<()> {
+    for (key, value) in [("lock_ttl_secs", config.lock_ttl_secs), ("focus_ttl_secs", config.focus_ttl_secs)] {
+        if value == 0 {
+            return Err(AgentChatError::Other(format!(
+                "Invalid config: {} must be a positive number of seconds, got 0",
+                key
+            )));
+        }
+    }
+    if !(0.0..=1.0).contains(&config.focus_overlap_min_score) {
+        return Err(AgentChatError::Other(format!(
+            "Invalid config: focus_overlap_min_score must be between 0.0 and 1.0, got {}",
+            config.focus_overlap_min_score
+        )));
+    }
+    if let Some(label) = config.label() {
+        names::validate_name(label).map_err(|_| {
+            AgentChatError::Other(format!(
+                "Invalid config: instance_label '{}' must be 1-32 lowercase letters, digits, and dashes",
+                label
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documented_config_covers_every_key_and_parses() {
+        let content = documented_default_config();
+
+        let defaults = toml::Value::try_from(Config::default()).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        for key in defaults.as_table().unwrap().keys() {
+            let at = lines
+                .iter()
+                .position(|l| l.starts_with(&format!("{} = ", key)))
+                .unwrap_or_else(|| panic!("missing key {}", key));
+            assert!(lines[at - 1].starts_with("# "), "{} is undocumented", key);
+        }
+
+        let parsed: Config = toml::from_str(&content).unwrap();
+        assert_eq!(toml::Value::try_from(parsed).unwrap(), defaults);
+    }
+
+    #[test]
+    fn zero_ttl_is_rejected() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, "focus_ttl_secs = 0\n").unwrap();
+
+        let err = read_config(&path).unwrap_err();
+        assert!(err.to_string().contains("focus_ttl_secs must be a positive number"), "{}", err);
+        assert!(set_value(&path, "lock_ttl_secs", "0").is_err());
+    }
+
+    #[test]
+    fn set_value_replaces_in_place() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        write_default_config(&path).unwrap();
+
+        set_value(&path, "room", "\"auth\"").unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("room = \"auth\""));
+        assert!(content.contains("# Active chat room"));
+        assert_eq!(read_config(&path).unwrap().room, "auth");
+    }
+
+    #[test]
+    fn read_config_accepts_written_default() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.toml");
+        write_default_config(&path).unwrap();
+        assert_eq!(read_config(&path).unwrap().lock_ttl_secs, 300);
+    }
+}
@@ -1,91 +1,177 @@
 use std::fs;
 use std::path::Path;
-use filetime::{self, FileTime};
+use std::time::SystemTime;
+use filetime::FileTime;
 use crate::error::Result;
 use crate::format;
+use crate::storage::atomic;
+use crate::storage::log::AuthorIndex;
 
 /// Get the cursor file path for a given session.
 pub fn cursor_path(cursors_dir: &Path, session_id: &str) -> std::path::PathBuf {
     cursors_dir.join(session_id)
 }
 
-/// Check if there are unread messages by comparing mtimes.
-/// Returns true if log_dir mtime > cursor mtime, or if cursor doesn't exist and log has entries.
+/// Check if there are unread messages: any message filename timestamp newer
+/// than the stored cursor position and not in the future. Only lists the log
+/// directory; no message is read. Without a cursor, any message counts.
 pub fn has_unread(log_dir: &Path, cursor_file: &Path) -> Result<bool> {
     if !cursor_file.exists() {
         // No cursor: check if log dir has any entries
         return crate::storage::log::has_any_messages(log_dir);
     }
 
-    let log_meta = fs::metadata(log_dir)?;
-    let cursor_meta = fs::metadata(cursor_file)?;
-
-    let log_mtime = FileTime::from_last_modification_time(&log_meta);
-    let cursor_mtime = FileTime::from_last_modification_time(&cursor_meta);
+    let messages = crate::storage::log::list_messages(log_dir)?;
+    Ok(!unread_window(&messages, position(cursor_file), now_ns()).is_empty())
+}
 
-    Ok(log_mtime > cursor_mtime)
+/// The slice of `messages` (sorted by filename) with timestamps in
+/// `(cursor_pos, now]`, found by binary search so only the unread tail is
+/// ever opened. Later scheduled messages wait for a future read.
+fn unread_window(
+    messages: &[(String, std::path::PathBuf)],
+    cursor_pos: u128,
+    now: u128,
+) -> &[(String, std::path::PathBuf)] {
+    // Non-numeric names sort after the digits; mapping them past `now` keeps
+    // the key monotonic and leaves them out of the window
+    let ns = |name: &str| message_ns(name).unwrap_or(u128::MAX);
+    let start = messages.partition_point(|(name, _)| ns(name) <= cursor_pos);
+    let end = messages.partition_point(|(name, _)| ns(name) <= now).max(start);
+    &messages[start..end]
 }
 
-/// Count unread messages (messages newer than cursor mtime).
-/// If `exclude_name` is Some, skip messages authored by that name.
-pub fn count_unread(log_dir: &Path, cursor_file: &Path, exclude_name: Option<&str>) -> Result<usize> {
-    let messages = crate::storage::log::list_messages(log_dir)?;
+/// Check if a message file should be shown to `reader`: not authored by the
+/// reader, and either a broadcast or a direct message addressed to the reader.
+/// Direct messages are hidden when the reader's name is unknown.
+pub fn should_include(path: &Path, reader: Option<&str>) -> bool {
+    match fs::read_to_string(path) {
+        Ok(content) => match format::parse_message(&content) {
+            Some(msg) => Some(msg.name) != reader && msg.is_visible_to(reader),
+            None => true,
+        },
+        Err(_) => true,
+    }
+}
 
-    if !cursor_file.exists() {
-        return Ok(count_excluding(&messages, exclude_name));
+/// Like `should_include`, but answers from the author index when the message
+/// is indexed, opening the file only for unindexed or stale entries.
+pub fn include_indexed(index: &AuthorIndex, path: &Path, reader: Option<&str>) -> bool {
+    match index.author_of(path) {
+        Some(author) => Some(author) != reader,
+        None => should_include(path, reader),
     }
+}
 
-    let cursor_meta = fs::metadata(cursor_file)?;
-    let cursor_mtime = FileTime::from_last_modification_time(&cursor_meta);
-
-    let mut count = 0;
-    for (_name, path) in &messages {
-        if let Ok(meta) = fs::metadata(path) {
-            let msg_mtime = FileTime::from_last_modification_time(&meta);
-            if msg_mtime > cursor_mtime {
-                if should_include(path, exclude_name) {
-                    count += 1;
-                }
-            }
-        }
+/// The session's mute list, stored next to its cursor as `<cursor>.mutes`.
+fn mutes_path(cursor_file: &Path) -> std::path::PathBuf {
+    let file_name = cursor_file.file_name().unwrap_or_default().to_string_lossy();
+    cursor_file.with_file_name(format!("{}.mutes", file_name))
+}
+
+/// Senders muted by the session owning `cursor_file`, one name per line.
+pub fn read_mutes(cursor_file: &Path) -> Vec<String> {
+    fs::read_to_string(mutes_path(cursor_file))
+        .map(|content| content.lines().map(str::trim).filter(|l| !l.is_empty()).map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Add `name` to (or with `muted == false` remove it from) the session's mute
+/// list. Returns false when the list already said so.
+pub fn set_muted(cursor_file: &Path, name: &str, muted: bool) -> Result<bool> {
+    let mut mutes = read_mutes(cursor_file);
+    if mutes.iter().any(|m| m == name) == muted {
+        return Ok(false);
     }
-    Ok(count)
+    if muted {
+        mutes.push(name.to_string());
+    } else {
+        mutes.retain(|m| m != name);
+    }
+    let path = mutes_path(cursor_file);
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = path.with_file_name(format!(".tmp.{}", file_name));
+    fs::write(&tmp, mutes.iter().map(|m| format!("{}\n", m)).collect::<String>())?;
+    atomic::rename(&tmp, &path)?;
+    Ok(true)
 }
 
-/// Check if a message file should be included (not authored by exclude_name).
-fn should_include(path: &Path, exclude_name: Option<&str>) -> bool {
-    let exclude = match exclude_name {
-        Some(name) => name,
-        None => return true,
-    };
-    match fs::read_to_string(path) {
-        Ok(content) => match format::parse_message_file(&content) {
-            Some((name, _)) => name != exclude,
-            None => true,
-        },
-        Err(_) => true,
+/// `include_indexed`, additionally dropping messages from `mutes`.
+pub fn include_unmuted(index: &AuthorIndex, path: &Path, reader: Option<&str>, mutes: &[String]) -> bool {
+    if !include_indexed(index, path, reader) {
+        return false;
+    }
+    if mutes.is_empty() {
+        return true;
     }
+    let author = match index.author_of(path) {
+        Some(author) => Some(author.to_string()),
+        None => fs::read_to_string(path)
+            .ok()
+            .and_then(|content| format::parse_message(&content).map(|m| m.name.to_string())),
+    };
+    author.is_none_or(|a| !mutes.contains(&a))
 }
 
-/// Count messages in a list, excluding those authored by exclude_name.
-fn count_excluding(messages: &[(String, std::path::PathBuf)], exclude_name: Option<&str>) -> usize {
-    messages.iter().filter(|(_, path)| should_include(path, exclude_name)).count()
+fn now_ns() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
 }
 
-/// Advance the cursor to "now" by touching the cursor file.
+/// Advance the cursor to "now": store the position as `<timestamp_ns>\n`.
+/// Written via tmp+rename, which also repairs a corrupt cursor file.
 pub fn advance(cursor_file: &Path) -> Result<()> {
-    // Create or update the cursor file
-    if !cursor_file.exists() {
-        fs::write(cursor_file, "")?;
-    }
-    let now = FileTime::now();
-    filetime::set_file_mtime(cursor_file, now)?;
+    advance_to(cursor_file, now_ns())
+}
+
+/// Move the cursor to `position_ns`, marking every message up to and
+/// including that timestamp as read.
+pub fn advance_to(cursor_file: &Path, position_ns: u128) -> Result<()> {
+    let file_name = cursor_file.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = cursor_file.with_file_name(format!(".tmp.{}", file_name));
+    fs::write(&tmp, format!("{}\n", position_ns))?;
+    atomic::rename(&tmp, cursor_file)?;
     Ok(())
 }
 
-/// Get messages that are unread (newer than cursor mtime).
+/// Parse stored cursor content. Requires the trailing newline so a
+/// truncated write is rejected rather than read as an earlier position.
+fn parse_position(content: &str) -> Option<u128> {
+    content.strip_suffix('\n')?.trim().parse().ok()
+}
+
+fn mtime_ns(time: FileTime) -> u128 {
+    time.unix_seconds().max(0) as u128 * 1_000_000_000 + time.nanoseconds() as u128
+}
+
+/// Cursor position in nanoseconds since the epoch. Cursors without a valid
+/// stored position (legacy empty files, partial writes, garbage) fall back to
+/// the file mtime, and are rewritten on the next advance.
+pub fn position(cursor_file: &Path) -> u128 {
+    let stored = fs::read(cursor_file)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|content| parse_position(&content));
+    stored.unwrap_or_else(|| {
+        fs::metadata(cursor_file)
+            .map(|meta| mtime_ns(FileTime::from_last_modification_time(&meta)))
+            .unwrap_or(0)
+    })
+}
+
+/// A message's timestamp, from its `{timestamp_ns}.md` filename. Files that
+/// don't follow the naming scheme never count as unread.
+fn message_ns(filename: &str) -> Option<u128> {
+    filename.trim_end_matches(".md").parse().ok()
+}
+
+/// Get messages that are unread (newer than the cursor position).
 /// If no cursor exists, returns the last `default_count` messages.
 /// If `exclude_name` is Some, skip messages authored by that name.
+/// Direct messages addressed to someone else and messages from senders on
+/// the session's mute list are always skipped.
 pub fn get_unread_messages(
     log_dir: &Path,
     cursor_file: &Path,
@@ -93,31 +179,30 @@ pub fn get_unread_messages(
     exclude_name: Option<&str>,
 ) -> Result<Vec<std::path::PathBuf>> {
     let messages = crate::storage::log::list_messages(log_dir)?;
+    let mutes = read_mutes(cursor_file);
 
     if !cursor_file.exists() {
         // First session: show last N messages, filtered
+        let index = AuthorIndex::load(log_dir);
         let filtered: Vec<_> = messages
             .iter()
-            .filter(|(_, path)| should_include(path, exclude_name))
+            .filter(|(_, path)| include_unmuted(&index, path, exclude_name, &mutes))
             .map(|(_, p)| p.clone())
             .collect();
         let start = filtered.len().saturating_sub(default_count);
         return Ok(filtered[start..].to_vec());
     }
 
-    let cursor_meta = fs::metadata(cursor_file)?;
-    let cursor_mtime = FileTime::from_last_modification_time(&cursor_meta);
-
-    let mut unread = Vec::new();
-    for (_name, path) in &messages {
-        if let Ok(meta) = fs::metadata(path) {
-            let msg_mtime = FileTime::from_last_modification_time(&meta);
-            if msg_mtime > cursor_mtime && should_include(path, exclude_name) {
-                unread.push(path.clone());
-            }
-        }
+    let unread = unread_window(&messages, position(cursor_file), now_ns());
+    if unread.is_empty() {
+        return Ok(Vec::new());
     }
-    Ok(unread)
+    let index = AuthorIndex::load(log_dir);
+    Ok(unread
+        .iter()
+        .filter(|(_, path)| include_unmuted(&index, path, exclude_name, &mutes))
+        .map(|(_, path)| path.clone())
+        .collect())
 }
 
 #[cfg(test)]
@@ -157,13 +242,11 @@ mod tests {
         write_message(&log, "test", "hello").unwrap();
         advance(&cursor).unwrap();
 
-        // After advancing, should not have unread
-        // (unless a new message was written in the same instant)
-        std::thread::sleep(std::time::Duration::from_millis(50));
-        // No new messages, so should be false
-        // Note: on some filesystems mtime granularity may cause this to be flaky
-        // but with the sleep it should be reliable
+        // Positions come from the cursor content, so no sleep is needed
         assert!(!has_unread(&log, &cursor).unwrap());
+
+        write_message(&log, "test", "again").unwrap();
+        assert!(has_unread(&log, &cursor).unwrap());
     }
 
     #[test]
@@ -183,27 +266,7 @@ mod tests {
     }
 
     #[test]
-    fn count_unread_excludes_own_messages() {
-        let tmp = TempDir::new().unwrap();
-        let log = tmp.path().join("log");
-        fs::create_dir(&log).unwrap();
-        let cursor = tmp.path().join("cursor");
-
-        // Advance cursor first so all messages are "new"
-        advance(&cursor).unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(10));
-
-        write_message(&log, "other-agent", "msg 1").unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(5));
-        write_message(&log, "me", "msg 2").unwrap();
-        std::thread::sleep(std::time::Duration::from_millis(5));
-        write_message(&log, "other-agent", "msg 3").unwrap();
-
-        assert_eq!(count_unread(&log, &cursor, Some("me")).unwrap(), 2);
-    }
-
-    #[test]
-    fn count_unread_no_filter_counts_all() {
+    fn get_unread_no_filter_includes_all() {
         let tmp = TempDir::new().unwrap();
         let log = tmp.path().join("log");
         fs::create_dir(&log).unwrap();
@@ -218,7 +281,7 @@ mod tests {
         std::thread::sleep(std::time::Duration::from_millis(5));
         write_message(&log, "other-agent", "msg 3").unwrap();
 
-        assert_eq!(count_unread(&log, &cursor, None).unwrap(), 3);
+        assert_eq!(get_unread_messages(&log, &cursor, 5, None).unwrap().len(), 3);
     }
 
     #[test]
@@ -242,7 +305,7 @@ mod tests {
         // Verify none of the returned paths contain "me" as author
         for path in &unread {
             let content = fs::read_to_string(path).unwrap();
-            let (name, _) = format::parse_message_file(&content).unwrap();
+            let (name, _, _) = format::parse_message_file(&content).unwrap();
             assert_ne!(name, "me");
         }
     }
@@ -265,8 +328,90 @@ mod tests {
         assert_eq!(unread.len(), 5);
         for path in &unread {
             let content = fs::read_to_string(path).unwrap();
-            let (name, _) = format::parse_message_file(&content).unwrap();
+            let (name, _, _) = format::parse_message_file(&content).unwrap();
             assert_ne!(name, "me");
         }
     }
+
+    #[test]
+    fn advance_stores_timestamp_content() {
+        let tmp = TempDir::new().unwrap();
+        let cursor = tmp.path().join("cursor");
+        advance(&cursor).unwrap();
+
+        let content = fs::read_to_string(&cursor).unwrap();
+        assert!(parse_position(&content).is_some(), "got: {:?}", content);
+    }
+
+    #[test]
+    fn unread_window_bounds_by_cursor_and_now() {
+        let messages: Vec<(String, std::path::PathBuf)> = [100, 200, 300, 400]
+            .iter()
+            .map(|ns| (format!("{}.md", ns), std::path::PathBuf::from(format!("{}.md", ns))))
+            .collect();
+
+        let names = |w: &[(String, std::path::PathBuf)]| w.iter().map(|(n, _)| n.clone()).collect::<Vec<_>>();
+        assert_eq!(names(unread_window(&messages, 200, 350)), vec!["300.md"]);
+        assert_eq!(names(unread_window(&messages, 0, 1000)).len(), 4);
+        assert!(unread_window(&messages, 400, 1000).is_empty());
+        // A cursor ahead of `now` yields nothing rather than a reversed range
+        assert!(unread_window(&messages, 350, 250).is_empty());
+    }
+
+    #[test]
+    fn has_unread_ignores_future_scheduled_messages() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+        let cursor = tmp.path().join("cursor");
+
+        advance(&cursor).unwrap();
+        crate::storage::log::schedule_message(&log, 600, "other-agent", &[], "later").unwrap();
+        assert!(!has_unread(&log, &cursor).unwrap());
+    }
+
+    #[test]
+    fn unread_counts_survive_missing_index() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+        let cursor = tmp.path().join("cursor");
+
+        advance(&cursor).unwrap();
+        write_message(&log, "other-agent", "msg 1").unwrap();
+        write_message(&log, "me", "msg 2").unwrap();
+        write_message(&log, "other-agent", "msg 3").unwrap();
+        assert!(tmp.path().join("log.index").exists());
+        assert_eq!(get_unread_messages(&log, &cursor, 5, Some("me")).unwrap().len(), 2);
+
+        fs::remove_file(tmp.path().join("log.index")).unwrap();
+        assert_eq!(get_unread_messages(&log, &cursor, 5, Some("me")).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn corrupt_cursor_falls_back_to_mtime_and_is_repaired() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+        let cursor = tmp.path().join("cursor");
+
+        write_message(&log, "other-agent", "old").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        // A legacy empty cursor, garbage and a truncated position (no trailing
+        // newline) all fall back to the mtime
+        for corrupt in [&b""[..], &b"\xff\x00garbage"[..], &b"17"[..]] {
+            fs::write(&cursor, corrupt).unwrap();
+            assert!(!has_unread(&log, &cursor).unwrap());
+            assert!(get_unread_messages(&log, &cursor, 5, None).unwrap().is_empty());
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        write_message(&log, "other-agent", "new").unwrap();
+        assert_eq!(get_unread_messages(&log, &cursor, 5, None).unwrap().len(), 1);
+
+        advance(&cursor).unwrap();
+        assert!(parse_position(&fs::read_to_string(&cursor).unwrap()).is_some());
+        assert!(get_unread_messages(&log, &cursor, 5, None).unwrap().is_empty());
+    }
 }
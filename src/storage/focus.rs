@@ -1,11 +1,13 @@
 use std::collections::HashSet;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::Result;
+use crate::storage::atomic;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FocusEntry {
@@ -24,12 +26,44 @@ impl FocusEntry {
             .as_secs();
         now > self.set_at + self.ttl_secs
     }
+
+    /// Seconds until this focus expires (0 if already expired).
+    pub fn remaining_secs(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        (self.set_at + self.ttl_secs).saturating_sub(now)
+    }
+}
+
+/// One line of a session's focus history; `focus` is `None` for a clear.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FocusHistoryEntry {
+    pub at: u64, // unix epoch seconds
+    pub owner: String,
+    pub session_id: String,
+    pub focus: Option<String>,
 }
 
 fn focus_path(focuses_dir: &Path, session_id: &str) -> PathBuf {
     focuses_dir.join(format!("{}.focus", session_id))
 }
 
+fn history_path(focuses_dir: &Path, session_id: &str) -> PathBuf {
+    focuses_dir.join(format!("{}.history", session_id))
+}
+
+/// Append one JSON line to the session's focus history.
+fn append_history(focuses_dir: &Path, entry: &FocusHistoryEntry) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_path(focuses_dir, &entry.session_id))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
 /// Set a focus for the given session. Replaces any previous focus.
 pub fn set(
     focuses_dir: &Path,
@@ -55,15 +89,72 @@ pub fn set(
     let path = focus_path(focuses_dir, session_id);
     let tmp = focuses_dir.join(format!(".tmp.{}.focus", session_id));
     fs::write(&tmp, &content)?;
-    fs::rename(&tmp, &path)?;
-    Ok(())
+    atomic::rename(&tmp, &path)?;
+
+    append_history(
+        focuses_dir,
+        &FocusHistoryEntry {
+            at: entry.set_at,
+            owner: entry.owner,
+            session_id: entry.session_id,
+            focus: Some(entry.focus),
+        },
+    )
 }
 
-/// Clear the focus for the given session.
-pub fn clear(focuses_dir: &Path, session_id: &str) -> Result<()> {
+/// Clear the focus for the given session. Clearing an active focus is
+/// recorded in the session's history. Returns the cleared focus text, if any.
+pub fn clear(focuses_dir: &Path, session_id: &str) -> Result<Option<String>> {
     let path = focus_path(focuses_dir, session_id);
+    let previous = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<FocusEntry>(&content).ok());
     let _ = fs::remove_file(&path); // ignore ENOENT
-    Ok(())
+
+    let Some(previous) = previous else {
+        return Ok(None);
+    };
+    append_history(
+        focuses_dir,
+        &FocusHistoryEntry {
+            at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            owner: previous.owner,
+            session_id: previous.session_id,
+            focus: None,
+        },
+    )?;
+    Ok(Some(previous.focus))
+}
+
+/// Every session's focus history, oldest first within each session.
+/// Sessions are ordered by their first entry.
+pub fn list_history(focuses_dir: &Path) -> Result<Vec<Vec<FocusHistoryEntry>>> {
+    let mut histories = Vec::new();
+    if !focuses_dir.exists() {
+        return Ok(histories);
+    }
+
+    for entry in fs::read_dir(focuses_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.ends_with(".history") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path()) else { continue };
+        // Skip a torn final line rather than losing the whole history
+        let history: Vec<FocusHistoryEntry> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        if !history.is_empty() {
+            histories.push(history);
+        }
+    }
+    histories.sort_by_key(|h: &Vec<FocusHistoryEntry>| h[0].at);
+    Ok(histories)
 }
 
 /// List all active (non-expired) focuses.
@@ -111,12 +202,24 @@ fn tokenize(text: &str) -> HashSet<String> {
         .collect()
 }
 
-/// Find focuses from other sessions that overlap with the given text.
+/// Fraction of significant tokens shared between two texts, relative to the
+/// smaller token set: 1.0 when one is fully contained in the other.
+fn overlap_score(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let smaller = a.len().min(b.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / smaller as f32
+}
+
+/// Find focuses from other sessions sharing at least `min_tokens` significant
+/// tokens with the given text, each paired with its overlap score in `(0.0, 1.0]`.
 pub fn find_overlapping(
     focuses_dir: &Path,
     text: &str,
     session_id: &str,
-) -> Result<Vec<FocusEntry>> {
+    min_tokens: usize,
+) -> Result<Vec<(FocusEntry, f32)>> {
     let text_tokens = tokenize(text);
     if text_tokens.is_empty() {
         return Ok(Vec::new());
@@ -130,14 +233,28 @@ pub fn find_overlapping(
             continue;
         }
         let focus_tokens = tokenize(&focus.focus);
-        if !text_tokens.is_disjoint(&focus_tokens) {
-            overlapping.push(focus);
+        let shared = text_tokens.intersection(&focus_tokens).count();
+        if shared > 0 && shared >= min_tokens {
+            let score = overlap_score(&text_tokens, &focus_tokens);
+            overlapping.push((focus, score));
         }
     }
 
     Ok(overlapping)
 }
 
+/// An overlap score as a whole percentage, for warnings.
+pub fn score_percent(score: f32) -> u32 {
+    (score * 100.0).round() as u32
+}
+
+/// Keep overlaps scoring at least `min_score`, strongest first.
+pub fn rank_overlaps(mut overlaps: Vec<(FocusEntry, f32)>, min_score: f32) -> Vec<(FocusEntry, f32)> {
+    overlaps.retain(|(_, score)| *score >= min_score);
+    overlaps.sort_by(|a, b| b.1.total_cmp(&a.1));
+    overlaps
+}
+
 /// Clean up expired focus files.
 fn cleanup_expired(focuses_dir: &Path) -> Result<()> {
     if !focuses_dir.exists() {
@@ -194,6 +311,25 @@ mod tests {
         assert_eq!(focuses.len(), 0);
     }
 
+    #[test]
+    fn history_records_sets_and_clears() {
+        let tmp = TempDir::new().unwrap();
+        set(tmp.path(), "CI pipeline", "swift-fox", "sess1", 1800).unwrap();
+        set(tmp.path(), "API work", "swift-fox", "sess1", 1800).unwrap();
+        clear(tmp.path(), "sess1").unwrap();
+        // Clearing with no active focus adds nothing
+        clear(tmp.path(), "sess1").unwrap();
+
+        let histories = list_history(tmp.path()).unwrap();
+        assert_eq!(histories.len(), 1);
+        let focuses: Vec<Option<&str>> = histories[0].iter().map(|h| h.focus.as_deref()).collect();
+        assert_eq!(focuses, vec![Some("CI pipeline"), Some("API work"), None]);
+        assert!(histories[0].iter().all(|h| h.owner == "swift-fox"));
+
+        // The active focus file is unaffected by the history
+        assert!(list_active(tmp.path()).unwrap().is_empty());
+    }
+
     #[test]
     fn clear_nonexistent_ok() {
         let tmp = TempDir::new().unwrap();
@@ -222,24 +358,39 @@ mod tests {
     fn find_overlapping_matches() {
         let tmp = TempDir::new().unwrap();
         set(tmp.path(), "CI pipeline", "swift-fox", "sess1", 300).unwrap();
-        let overlaps = find_overlapping(tmp.path(), "CI configuration", "sess2").unwrap();
+        let overlaps = find_overlapping(tmp.path(), "CI configuration", "sess2", 1).unwrap();
         assert_eq!(overlaps.len(), 1);
-        assert_eq!(overlaps[0].owner, "swift-fox");
+        assert_eq!(overlaps[0].0.owner, "swift-fox");
     }
 
     #[test]
     fn find_overlapping_skips_own_session() {
         let tmp = TempDir::new().unwrap();
         set(tmp.path(), "CI pipeline", "swift-fox", "sess1", 300).unwrap();
-        let overlaps = find_overlapping(tmp.path(), "CI configuration", "sess1").unwrap();
+        let overlaps = find_overlapping(tmp.path(), "CI configuration", "sess1", 1).unwrap();
         assert_eq!(overlaps.len(), 0);
     }
 
+    #[test]
+    fn find_overlapping_respects_min_tokens() {
+        let tmp = TempDir::new().unwrap();
+        set(tmp.path(), "CI pipeline", "swift-fox", "sess1", 1800).unwrap();
+        set(tmp.path(), "pipeline caching metrics", "calm-owl", "sess2", 1800).unwrap();
+
+        // One incidental shared word is enough at the default threshold
+        assert_eq!(find_overlapping(tmp.path(), "pipeline refactor", "sess3", 1).unwrap().len(), 2);
+        assert!(find_overlapping(tmp.path(), "pipeline refactor", "sess3", 2).unwrap().is_empty());
+
+        let overlaps = find_overlapping(tmp.path(), "pipeline caching refactor", "sess3", 2).unwrap();
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].0.owner, "calm-owl");
+    }
+
     #[test]
     fn find_overlapping_no_match() {
         let tmp = TempDir::new().unwrap();
         set(tmp.path(), "CI pipeline", "swift-fox", "sess1", 300).unwrap();
-        let overlaps = find_overlapping(tmp.path(), "database migration", "sess2").unwrap();
+        let overlaps = find_overlapping(tmp.path(), "database migration", "sess2", 1).unwrap();
         assert_eq!(overlaps.len(), 0);
     }
 
@@ -254,4 +405,32 @@ mod tests {
         assert!(!tokens.contains("on"));
         assert!(!tokens.contains("for"));
     }
+
+    #[test]
+    fn overlap_score_higher_for_more_shared_tokens() {
+        let tmp = TempDir::new().unwrap();
+        set(tmp.path(), "CI pipeline caching", "swift-fox", "sess1", 300).unwrap();
+        set(tmp.path(), "pipeline docs", "bold-hawk", "sess2", 300).unwrap();
+
+        let overlaps = rank_overlaps(
+            find_overlapping(tmp.path(), "CI pipeline caching fixes", "sess3", 1).unwrap(),
+            0.0,
+        );
+        assert_eq!(overlaps.len(), 2);
+        assert_eq!(overlaps[0].0.owner, "swift-fox");
+        assert!(overlaps[0].1 > overlaps[1].1);
+        assert_eq!(overlaps[0].1, 1.0);
+    }
+
+    #[test]
+    fn rank_overlaps_filters_below_threshold() {
+        let tmp = TempDir::new().unwrap();
+        set(tmp.path(), "CI pipeline caching", "swift-fox", "sess1", 300).unwrap();
+        set(tmp.path(), "pipeline refactor docs", "bold-hawk", "sess2", 300).unwrap();
+
+        let overlaps = find_overlapping(tmp.path(), "CI pipeline caching", "sess3", 1).unwrap();
+        let ranked = rank_overlaps(overlaps, 0.5);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].0.owner, "swift-fox");
+    }
 }
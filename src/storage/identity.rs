@@ -1,5 +1,6 @@
 use crate::error::{AgentChatError, Result};
 use crate::storage::{paths, session};
+use serde::Deserialize;
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -8,28 +9,67 @@ pub struct Identity {
     pub name: Option<String>,
 }
 
+/// Contents of `.agent-chat/identity`.
+#[derive(Debug, Deserialize)]
+struct IdentityFile {
+    session_id: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+fn read_identity_file(root: &Path) -> Result<Option<IdentityFile>> {
+    let path = paths::identity_path(root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)?;
+    let file: IdentityFile = serde_json::from_str(&content).map_err(|e| {
+        AgentChatError::Other(format!("Invalid identity file {}: {}", path.display(), e))
+    })?;
+    Ok(Some(file).filter(|f| !f.session_id.trim().is_empty()))
+}
+
 /// Resolve identity with env-first semantics and Codex-friendly fallbacks.
 /// 1) Use AGENT_CHAT_SESSION_ID / AGENT_CHAT_NAME when present.
-/// 2) If session_id exists but name is missing, read name from sessions/<session_id>.
-/// 3) If session_id is missing and exactly one session file exists, use that session id.
+/// 2) Otherwise use `.agent-chat/identity`; its name only applies to its own session.
+/// 3) If session_id exists but name is missing, read name from sessions/<session_id>.
+/// 4) If session_id is still missing and exactly one session file exists, use that session id.
+///
+/// With both env vars set nothing under `.agent-chat/` is read, and the
+/// sessions directory is only scanned when no session id is known at all.
 pub fn resolve(root: &Path) -> Result<Identity> {
-    let env_session = std::env::var("AGENT_CHAT_SESSION_ID")
-        .ok()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty());
-    let env_name = std::env::var("AGENT_CHAT_NAME")
+    resolve_from(root, env_value("AGENT_CHAT_SESSION_ID"), env_value("AGENT_CHAT_NAME"))
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
         .ok()
         .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty());
+        .filter(|s| !s.is_empty())
+}
+
+fn resolve_from(root: &Path, env_session: Option<String>, env_name: Option<String>) -> Result<Identity> {
+    if let (Some(session_id), Some(name)) = (&env_session, &env_name) {
+        return Ok(Identity { session_id: session_id.clone(), name: Some(name.clone()) });
+    }
 
-    let session_id = match env_session {
-        Some(sid) => sid,
-        None => infer_single_session_id(root)?.ok_or_else(|| {
+    let file = read_identity_file(root)?;
+
+    let session_id = match (env_session, &file) {
+        (Some(sid), _) => sid,
+        (None, Some(file)) => file.session_id.trim().to_string(),
+        (None, None) => infer_single_session_id(root)?.ok_or_else(|| {
             AgentChatError::MissingEnv("AGENT_CHAT_SESSION_ID".to_string())
         })?,
     };
 
-    let name = match env_name {
+    let file_name = file
+        .filter(|f| f.session_id.trim() == session_id)
+        .and_then(|f| f.name)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    let name = match env_name.or(file_name) {
         Some(name) => Some(name),
         None => {
             let sessions_dir = paths::sessions_dir(root);
@@ -99,6 +139,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn identity_file_parses_with_optional_name() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        assert!(read_identity_file(root).unwrap().is_none());
+
+        std::fs::write(paths::identity_path(root), r#"{"session_id": "sid-1"}"#).unwrap();
+        let file = read_identity_file(root).unwrap().unwrap();
+        assert_eq!(file.session_id, "sid-1");
+        assert_eq!(file.name, None);
+
+        std::fs::write(paths::identity_path(root), "not json").unwrap();
+        assert!(read_identity_file(root).unwrap_err().to_string().contains("Invalid identity file"));
+    }
+
+    /// A root where reading the identity file or listing sessions fails.
+    fn unreadable_root() -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(paths::identity_path(tmp.path())).unwrap();
+        std::fs::write(paths::sessions_dir(tmp.path()), "not a directory").unwrap();
+        tmp
+    }
+
+    #[test]
+    fn env_identity_reads_nothing_from_disk() {
+        let tmp = unreadable_root();
+        let root = tmp.path();
+        assert!(read_identity_file(root).is_err());
+        assert!(infer_single_session_id(root).is_err());
+
+        let id = resolve_from(root, Some("sid-1".into()), Some("swift-fox".into())).unwrap();
+        assert_eq!(id.session_id, "sid-1");
+        assert_eq!(id.name.as_deref(), Some("swift-fox"));
+    }
+
+    #[test]
+    fn env_session_id_skips_session_inference() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::write(paths::sessions_dir(root), "not a directory").unwrap();
+        assert!(infer_single_session_id(root).is_err());
+
+        let id = resolve_from(root, Some("sid-1".into()), None).unwrap();
+        assert_eq!(id.session_id, "sid-1");
+        assert_eq!(id.name, None);
+    }
+
     #[test]
     fn infer_single_session_returns_none_when_multiple() {
         let tmp = TempDir::new().unwrap();
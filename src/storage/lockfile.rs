@@ -4,10 +4,12 @@ use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
-use globset::{Glob, GlobMatcher};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{AgentChatError, Result};
+use crate::storage::atomic;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LockEntry {
@@ -16,15 +18,62 @@ pub struct LockEntry {
     pub session_id: String,
     pub acquired_at: u64, // unix epoch seconds
     pub ttl_secs: u64,
+    #[serde(default)]
+    pub expiry_warned: bool,
+    /// Owner of the expired lock this one replaced, if it belonged to another session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_session_id: Option<String>,
+    /// Whether the new owner has been told about the takeover.
+    #[serde(default)]
+    pub takeover_warned: bool,
+    /// Sessions that have already messaged the owner about this lock.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notified_by: Vec<String>,
 }
 
 impl LockEntry {
+    /// Unix epoch second at which this lock lapses unless renewed.
+    pub fn expires_at(&self) -> u64 {
+        self.acquired_at + self.ttl_secs
+    }
+
+    /// Seconds until this lock expires (0 if already expired).
+    pub fn remaining_secs(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        self.expires_at().saturating_sub(now)
+    }
+
     pub fn is_expired(&self) -> bool {
         let now = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        now > self.acquired_at + self.ttl_secs
+        now > self.expires_at()
+    }
+
+    /// Carry takeover details over from the entry this one replaces: a lapsed
+    /// lock of another session becomes the previous owner, while refreshing
+    /// one's own lock keeps any earlier takeover record.
+    fn after(mut self, replaced: Option<&LockEntry>) -> Self {
+        match replaced {
+            Some(old) if old.session_id != self.session_id => {
+                self.previous_owner = Some(old.owner.clone());
+                self.previous_session_id = Some(old.session_id.clone());
+            }
+            Some(old) => {
+                self.previous_owner = old.previous_owner.clone();
+                self.previous_session_id = old.previous_session_id.clone();
+                self.takeover_warned = old.takeover_warned;
+                self.notified_by = old.notified_by.clone();
+            }
+            None => {}
+        }
+        self
     }
 }
 
@@ -39,32 +88,58 @@ fn lock_path(locks_dir: &Path, glob: &str) -> PathBuf {
     locks_dir.join(format!("{}.lock", hash_glob(glob)))
 }
 
-/// Acquire a lock on a glob pattern.
+/// Acquire a lock on a glob pattern. A non-zero `max_locks` caps how many
+/// live locks one session may hold; re-acquiring a held pattern doesn't count.
 pub fn acquire(
     locks_dir: &Path,
     glob: &str,
     owner: &str,
     session_id: &str,
     ttl_secs: u64,
+    max_locks: usize,
 ) -> Result<()> {
+    validate_pattern(glob)?;
+
+    // Read the current holder before cleanup can delete an expired entry
+    let path = lock_path(locks_dir, glob);
+    let existing = read_entry(&path);
+
     // Clean expired locks first
     cleanup_expired(locks_dir)?;
 
     // Check for existing lock
-    let path = lock_path(locks_dir, glob);
-    if path.exists() {
-        let content = fs::read_to_string(&path)?;
-        if let Ok(existing) = serde_json::from_str::<LockEntry>(&content) {
-            if !existing.is_expired() {
-                if existing.session_id == session_id {
-                    // Re-acquiring own lock is OK, refresh it
-                } else {
-                    return Err(AgentChatError::LockConflict {
-                        glob: glob.to_string(),
-                        owner: existing.owner.clone(),
-                    });
-                }
-            }
+    // Re-acquiring own lock is OK, refresh it
+    if let Some(existing) = &existing {
+        if !existing.is_expired() && existing.session_id != session_id {
+            return Err(AgentChatError::LockConflict {
+                glob: glob.to_string(),
+                owner: existing.owner.clone(),
+            });
+        }
+    }
+
+    // Another session's pattern that can match some of the same paths
+    if let Some(other) = list_active(locks_dir)?
+        .into_iter()
+        .find(|l| l.session_id != session_id && l.glob != glob && globs_overlap(&l.glob, glob))
+    {
+        return Err(AgentChatError::LockOverlap {
+            glob: glob.to_string(),
+            other_glob: other.glob,
+            owner: other.owner,
+        });
+    }
+
+    if max_locks > 0 {
+        let held = list_active(locks_dir)?
+            .into_iter()
+            .filter(|l| l.session_id == session_id && l.glob != glob)
+            .count();
+        if held >= max_locks {
+            return Err(AgentChatError::Other(format!(
+                "Lock limit reached: this session already holds {} lock(s) (max_locks_per_session = {}). Release one before locking {}",
+                held, max_locks, glob
+            )));
         }
     }
 
@@ -77,12 +152,82 @@ pub fn acquire(
             .unwrap()
             .as_secs(),
         ttl_secs,
+        expiry_warned: false,
+        previous_owner: None,
+        previous_session_id: None,
+        takeover_warned: false,
+        notified_by: Vec::new(),
+    }
+    .after(existing.as_ref());
+
+    write_entry(locks_dir, &entry)
+}
+
+fn read_entry(path: &Path) -> Option<LockEntry> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Renew a lock held by `session_id`, resetting `acquired_at` to now. Works
+/// after the lock has expired, as long as no other session has taken it;
+/// a lapsed or missing lock is re-created through `acquire`, so the overlap
+/// check and `max_locks` apply as for a new lock.
+/// Keeps the lock's previous TTL unless `ttl_secs` overrides it
+/// (`default_ttl_secs` applies when there is no previous lock).
+/// Returns the effective TTL.
+pub fn renew(
+    locks_dir: &Path,
+    glob: &str,
+    owner: &str,
+    session_id: &str,
+    ttl_secs: Option<u64>,
+    default_ttl_secs: u64,
+    max_locks: usize,
+) -> Result<u64> {
+    validate_pattern(glob)?;
+    let existing = read_entry(&lock_path(locks_dir, glob));
+
+    let previous_ttl = match &existing {
+        Some(e) if e.session_id == session_id => Some(e.ttl_secs),
+        Some(e) if !e.is_expired() => {
+            return Err(AgentChatError::Other(format!(
+                "Cannot renew {}: it is now locked by {}",
+                glob, e.owner
+            )));
+        }
+        _ => None,
     };
 
-    let content = serde_json::to_string_pretty(&entry)?;
-    let tmp = locks_dir.join(format!(".tmp.{}", hash_glob(glob)));
+    let ttl = ttl_secs.or(previous_ttl).unwrap_or(default_ttl_secs);
+    if !existing.as_ref().is_some_and(|e| e.session_id == session_id && !e.is_expired()) {
+        // Overlapping locks may have been taken while ours was gone
+        acquire(locks_dir, glob, owner, session_id, ttl, max_locks)?;
+        return Ok(ttl);
+    }
+    let entry = LockEntry {
+        glob: glob.to_string(),
+        owner: owner.to_string(),
+        session_id: session_id.to_string(),
+        acquired_at: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+        ttl_secs: ttl,
+        expiry_warned: false,
+        previous_owner: None,
+        previous_session_id: None,
+        takeover_warned: false,
+        notified_by: Vec::new(),
+    }
+    .after(existing.as_ref());
+    write_entry(locks_dir, &entry)?;
+    Ok(ttl)
+}
+
+fn write_entry(locks_dir: &Path, entry: &LockEntry) -> Result<()> {
+    let content = serde_json::to_string_pretty(entry)?;
+    let tmp = locks_dir.join(format!(".tmp.{}", hash_glob(&entry.glob)));
     fs::write(&tmp, &content)?;
-    fs::rename(&tmp, &path)?;
+    atomic::rename(&tmp, &lock_path(locks_dir, &entry.glob))?;
     Ok(())
 }
 
@@ -108,6 +253,31 @@ pub fn release(locks_dir: &Path, glob: &str, session_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Remove the lock on `glob` whoever holds it, expired or not. Returns the
+/// removed entry so the caller can report whose lock it was.
+pub fn force_release(locks_dir: &Path, glob: &str) -> Result<LockEntry> {
+    let path = lock_path(locks_dir, glob);
+    let entry = read_entry(&path).ok_or_else(|| AgentChatError::LockNotFound(glob.to_string()))?;
+    // Ignore ENOENT race
+    let _ = fs::remove_file(&path);
+    Ok(entry)
+}
+
+/// Release every active lock held by `session_id`. Returns the released
+/// entries; locks owned by other sessions are left alone.
+pub fn release_all_for_session(locks_dir: &Path, session_id: &str) -> Result<Vec<LockEntry>> {
+    let mut released = Vec::new();
+    for lock in list_active(locks_dir)? {
+        if lock.session_id != session_id {
+            continue;
+        }
+        // Ignore ENOENT race
+        let _ = fs::remove_file(lock_path(locks_dir, &lock.glob));
+        released.push(lock);
+    }
+    Ok(released)
+}
+
 /// List all active (non-expired) locks.
 pub fn list_active(locks_dir: &Path) -> Result<Vec<LockEntry>> {
     let mut locks = Vec::new();
@@ -138,6 +308,53 @@ pub fn list_active(locks_dir: &Path) -> Result<Vec<LockEntry>> {
     Ok(locks)
 }
 
+/// Find active locks expiring within `window_secs` that haven't been warned
+/// about yet, and mark them warned so each lock is reported only once.
+pub fn take_expiring(locks_dir: &Path, window_secs: u64) -> Result<Vec<LockEntry>> {
+    let mut expiring = Vec::new();
+    for mut lock in list_active(locks_dir)? {
+        if lock.expiry_warned || lock.remaining_secs() > window_secs {
+            continue;
+        }
+        lock.expiry_warned = true;
+        write_entry(locks_dir, &lock)?;
+        expiring.push(lock);
+    }
+    Ok(expiring)
+}
+
+/// Find this session's active lock matching `file_path` that took over another
+/// session's expired lock and hasn't been reported yet; marks it reported.
+pub fn take_takeover(locks_dir: &Path, file_path: &str, session_id: &str) -> Result<Option<LockEntry>> {
+    for mut lock in list_active(locks_dir)? {
+        if lock.session_id != session_id || lock.previous_owner.is_none() || lock.takeover_warned {
+            continue;
+        }
+        let matches = matcher(&lock.glob).is_ok_and(|m| m.is_match(file_path));
+        if matches {
+            lock.takeover_warned = true;
+            write_entry(locks_dir, &lock)?;
+            return Ok(Some(lock));
+        }
+    }
+    Ok(None)
+}
+
+/// Record that `session_id` is messaging the owner of the lock on `glob`.
+/// Returns false if it already did during this lock's lifetime, so retried
+/// edits don't post the same notice again.
+pub fn take_notify(locks_dir: &Path, glob: &str, session_id: &str) -> Result<bool> {
+    let Some(mut lock) = read_entry(&lock_path(locks_dir, glob)) else {
+        return Ok(false);
+    };
+    if lock.notified_by.iter().any(|s| s == session_id) {
+        return Ok(false);
+    }
+    lock.notified_by.push(session_id.to_string());
+    write_entry(locks_dir, &lock)?;
+    Ok(true)
+}
+
 /// Check if a file path matches any active lock NOT owned by the given session.
 /// Returns the matching lock entry if found.
 pub fn check_file(locks_dir: &Path, file_path: &str, session_id: &str) -> Result<Option<LockEntry>> {
@@ -146,16 +363,194 @@ pub fn check_file(locks_dir: &Path, file_path: &str, session_id: &str) -> Result
         if lock.session_id == session_id {
             continue; // own lock
         }
-        if let Ok(glob) = Glob::new(&lock.glob) {
-            let matcher: GlobMatcher = glob.compile_matcher();
-            if matcher.is_match(file_path) {
-                return Ok(Some(lock));
-            }
+        if matcher(&lock.glob).is_ok_and(|m| m.is_match(file_path)) {
+            return Ok(Some(lock));
         }
     }
     Ok(None)
 }
 
+/// Files under `project_root` that `glob` would cover, as sorted
+/// project-relative paths. Honors `.gitignore` (even outside a git repo) and
+/// never descends into `.git/` or `.agent-chat/`.
+pub fn matching_files(project_root: &Path, glob: &str) -> Result<Vec<String>> {
+    validate_pattern(glob)?;
+    let set = matcher(glob).map_err(|e| AgentChatError::Other(e.to_string()))?;
+
+    let walker = WalkBuilder::new(project_root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| !matches!(entry.file_name().to_str(), Some(".git" | ".agent-chat")))
+        .build();
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(|e| AgentChatError::Other(e.to_string()))?;
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(project_root) else {
+            continue;
+        };
+        let parts: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+        let relative = parts.join("/");
+        if set.is_match(&relative) {
+            files.push(relative);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Expand `{a,b}` alternations (nested ones included) into plain globs, so
+/// `src/{api,models}/*.rs` becomes `src/api/*.rs` and `src/models/*.rs`.
+/// Braces inside `[...]` classes or escaped with a backslash are left alone, as is
+/// an unclosed `{`.
+fn expand_braces(glob: &str) -> Vec<String> {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut depth = 0;
+    let mut open = None;
+    let mut commas = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '[' => {
+                while i < chars.len() && chars[i] != ']' {
+                    i += 1;
+                }
+            }
+            '{' => {
+                if depth == 0 {
+                    open = Some(i);
+                }
+                depth += 1;
+            }
+            ',' if depth == 1 => commas.push(i),
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    let start = open.unwrap();
+                    let prefix: String = chars[..start].iter().collect();
+                    let suffix: String = chars[i + 1..].iter().collect();
+                    let mut bounds = vec![start];
+                    bounds.extend(&commas);
+                    bounds.push(i);
+                    return bounds
+                        .windows(2)
+                        .flat_map(|w| {
+                            let alternative: String = chars[w[0] + 1..w[1]].iter().collect();
+                            expand_braces(&format!("{}{}{}", prefix, alternative, suffix))
+                        })
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    vec![glob.to_string()]
+}
+
+/// Compile a lock pattern, brace alternatives included.
+fn matcher(glob: &str) -> std::result::Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for alternative in expand_braces(glob) {
+        builder.add(Glob::new(&alternative)?);
+    }
+    builder.build()
+}
+
+/// Reject patterns lock matching can't honor: `!` negations (globset has
+/// none, so the lock would claim nothing) and invalid globs.
+fn validate_pattern(glob: &str) -> Result<()> {
+    if glob.starts_with('!') {
+        return Err(AgentChatError::Other(format!(
+            "Negated lock patterns are not supported: {}. Lock what you will edit instead, e.g. src/{{api,models}}/**/*.rs",
+            glob
+        )));
+    }
+    matcher(glob)
+        .map(|_| ())
+        .map_err(|e| AgentChatError::Other(format!("Invalid lock pattern {}: {}", glob, e)))
+}
+
+/// One matching unit of a glob pattern.
+#[derive(Clone, Copy, PartialEq)]
+enum GlobToken {
+    Literal(char),
+    /// `?` or a `[...]` class: exactly one character
+    One,
+    /// `*`, `**`, or `**/`: any run of characters, `/` included
+    Star,
+}
+
+/// Tokenize a glob the way lock matching sees it: globset's default `*`
+/// crosses `/`, so `**` and `**/` collapse into a plain star. Character
+/// classes are treated as `?`, which can only over-report overlap.
+fn tokenize_glob(glob: &str) -> Vec<GlobToken> {
+    let mut tokens = Vec::new();
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '*' => {
+                while chars.peek() == Some(&'*') {
+                    chars.next();
+                }
+                // `a/**/b` also matches `a/b`, so the star swallows the slash
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                }
+                GlobToken::Star
+            }
+            '?' => GlobToken::One,
+            '[' => {
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                }
+                GlobToken::One
+            }
+            '\\' => GlobToken::Literal(chars.next().unwrap_or('\\')),
+            c => GlobToken::Literal(c),
+        };
+        if token == GlobToken::Star && tokens.last() == Some(&GlobToken::Star) {
+            continue;
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Whether some path could match both globs, trying every pair of brace
+/// alternatives.
+fn globs_overlap(a: &str, b: &str) -> bool {
+    let b_alternatives = expand_braces(b);
+    expand_braces(a)
+        .iter()
+        .any(|a| b_alternatives.iter().any(|b| plain_globs_overlap(a, b)))
+}
+
+/// `globs_overlap` for patterns without braces.
+fn plain_globs_overlap(a: &str, b: &str) -> bool {
+    let (a, b) = (tokenize_glob(a), tokenize_glob(b));
+    // overlap[i][j]: a[i..] and b[j..] can match a common string
+    let mut overlap = vec![vec![false; b.len() + 1]; a.len() + 1];
+    for i in (0..=a.len()).rev() {
+        for j in (0..=b.len()).rev() {
+            overlap[i][j] = match (a.get(i), b.get(j)) {
+                (None, None) => true,
+                (Some(GlobToken::Star), _) => overlap[i + 1][j] || (j < b.len() && overlap[i][j + 1]),
+                (_, Some(GlobToken::Star)) => overlap[i][j + 1] || (i < a.len() && overlap[i + 1][j]),
+                (None, _) | (_, None) => false,
+                (Some(GlobToken::Literal(x)), Some(GlobToken::Literal(y))) => x == y && overlap[i + 1][j + 1],
+                _ => overlap[i + 1][j + 1],
+            };
+        }
+    }
+    overlap[0][0]
+}
+
 /// Clean up expired lock files.
 fn cleanup_expired(locks_dir: &Path) -> Result<()> {
     if !locks_dir.exists() {
@@ -186,7 +581,7 @@ mod tests {
     #[test]
     fn acquire_and_list() {
         let tmp = TempDir::new().unwrap();
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300).unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
         let locks = list_active(tmp.path()).unwrap();
         assert_eq!(locks.len(), 1);
         assert_eq!(locks[0].glob, "src/*.rs");
@@ -196,23 +591,175 @@ mod tests {
     #[test]
     fn acquire_conflict() {
         let tmp = TempDir::new().unwrap();
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300).unwrap();
-        let result = acquire(tmp.path(), "src/*.rs", "bold-hawk", "sess2", 300);
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        let result = acquire(tmp.path(), "src/*.rs", "bold-hawk", "sess2", 300, 0);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn renew_own_expired_lock() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 0, 0).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        assert!(read_lock(tmp.path()).is_some_and(|l| l.is_expired()));
+
+        let ttl = renew(tmp.path(), "src/*.rs", "swift-fox", "sess1", Some(600), 300, 0).unwrap();
+        assert_eq!(ttl, 600);
+        let locks = list_active(tmp.path()).unwrap();
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].session_id, "sess1");
+    }
+
+    #[test]
+    fn acquire_over_expired_lock_records_takeover() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 0, 0).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+
+        acquire(tmp.path(), "src/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
+        let lock = read_lock(tmp.path()).unwrap();
+        assert_eq!(lock.previous_owner.as_deref(), Some("swift-fox"));
+        assert_eq!(lock.previous_session_id.as_deref(), Some("sess1"));
+
+        // Reported to the new owner once, and the record survives a refresh
+        assert!(take_takeover(tmp.path(), "src/main.rs", "sess2").unwrap().is_some());
+        assert!(take_takeover(tmp.path(), "src/main.rs", "sess2").unwrap().is_none());
+        acquire(tmp.path(), "src/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
+        let lock = read_lock(tmp.path()).unwrap();
+        assert_eq!(lock.previous_owner.as_deref(), Some("swift-fox"));
+        assert!(lock.takeover_warned);
+    }
+
+    #[test]
+    fn take_notify_once_per_session_and_lock() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        assert!(take_notify(tmp.path(), "src/*.rs", "sess2").unwrap());
+        assert!(!take_notify(tmp.path(), "src/*.rs", "sess2").unwrap());
+        assert!(take_notify(tmp.path(), "src/*.rs", "sess3").unwrap());
+
+        // Refreshing keeps the record; a new lock starts clean
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        assert!(!take_notify(tmp.path(), "src/*.rs", "sess2").unwrap());
+        release(tmp.path(), "src/*.rs", "sess1").unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        assert!(take_notify(tmp.path(), "src/*.rs", "sess2").unwrap());
+    }
+
+    #[test]
+    fn lock_entry_without_takeover_fields_still_parses() {
+        let lock: LockEntry = serde_json::from_str(
+            r#"{"glob":"a","owner":"o","session_id":"s","acquired_at":1,"ttl_secs":2}"#,
+        )
+        .unwrap();
+        assert!(lock.previous_owner.is_none());
+        assert!(!lock.takeover_warned);
+    }
+
+    #[test]
+    fn renew_keeps_previous_ttl() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 900, 0).unwrap();
+        assert_eq!(renew(tmp.path(), "src/*.rs", "swift-fox", "sess1", None, 300, 0).unwrap(), 900);
+    }
+
+    #[test]
+    fn renew_fails_when_other_session_owns() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
+        let err = renew(tmp.path(), "src/*.rs", "swift-fox", "sess1", None, 300, 0).unwrap_err();
+        assert!(err.to_string().contains("now locked by bold-hawk"));
+    }
+
+    #[test]
+    fn renew_without_own_lock_checks_overlap_and_limit() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
+        let err = renew(tmp.path(), "src/**", "swift-fox", "sess1", None, 300, 0).unwrap_err();
+        assert!(matches!(err, AgentChatError::LockOverlap { .. }), "{}", err);
+
+        acquire(tmp.path(), "lib/*", "swift-fox", "sess1", 300, 1).unwrap();
+        let err = renew(tmp.path(), "docs/*", "swift-fox", "sess1", None, 300, 1).unwrap_err();
+        assert!(err.to_string().contains("Lock limit reached"), "{}", err);
+        // Refreshing the lock already held doesn't count against the limit
+        renew(tmp.path(), "lib/*", "swift-fox", "sess1", None, 300, 1).unwrap();
+    }
+
+    fn read_lock(locks_dir: &Path) -> Option<LockEntry> {
+        let content = fs::read_to_string(lock_path(locks_dir, "src/*.rs")).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
     #[test]
     fn acquire_same_session_ok() {
         let tmp = TempDir::new().unwrap();
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300).unwrap();
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300).unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+    }
+
+    #[test]
+    fn overlapping_pattern_from_other_session_refused() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        let err = acquire(tmp.path(), "src/**/*.rs", "bold-hawk", "sess2", 300, 0).unwrap_err();
+        assert!(matches!(
+            err,
+            AgentChatError::LockOverlap { ref other_glob, ref owner, .. } if other_glob == "src/*.rs" && owner == "swift-fox"
+        ));
+
+        // Own overlapping locks are fine
+        acquire(tmp.path(), "src/lib.rs", "swift-fox", "sess1", 300, 0).unwrap();
+    }
+
+    #[test]
+    fn glob_overlap_detection() {
+        assert!(globs_overlap("src/*.rs", "src/**/*.rs"));
+        assert!(globs_overlap("src/**", "src/auth/login.rs"));
+        assert!(globs_overlap("src/**/mod.rs", "src/mod.rs"));
+        assert!(globs_overlap("src/a?.rs", "src/[ab]b.rs"));
+        assert!(globs_overlap("*.md", "docs/*"));
+        assert!(!globs_overlap("src/*.rs", "tests/*.rs"));
+        assert!(!globs_overlap("src/*.rs", "src/*.md"));
+        assert!(!globs_overlap("src/lib.rs", "src/main.rs"));
+    }
+
+    #[test]
+    fn brace_patterns_expand_for_matching_and_overlap() {
+        assert_eq!(
+            expand_braces("src/{api,models/{user,team}}/*.rs"),
+            vec!["src/api/*.rs", "src/models/user/*.rs", "src/models/team/*.rs"]
+        );
+        assert_eq!(expand_braces("src/[{]x.rs"), vec!["src/[{]x.rs"]);
+        assert_eq!(expand_braces("src/{oops"), vec!["src/{oops"]);
+
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/{api,models}/**/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        assert!(check_file(tmp.path(), "src/api/routes.rs", "sess2").unwrap().is_some());
+        assert!(check_file(tmp.path(), "src/models/user/mod.rs", "sess2").unwrap().is_some());
+        assert!(check_file(tmp.path(), "src/cli/main.rs", "sess2").unwrap().is_none());
+        assert!(check_file(tmp.path(), "src/api/README.md", "sess2").unwrap().is_none());
+
+        assert!(globs_overlap("src/{api,models}/**/*.rs", "src/models/*.rs"));
+        assert!(!globs_overlap("src/{api,models}/**/*.rs", "src/cli/*.rs"));
+        assert!(acquire(tmp.path(), "src/models/*.rs", "bold-hawk", "sess2", 300, 0).is_err());
+        acquire(tmp.path(), "src/{cli,bin}/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
+    }
+
+    #[test]
+    fn negated_and_invalid_patterns_are_refused() {
+        let tmp = TempDir::new().unwrap();
+        let err = acquire(tmp.path(), "!src/tests/**", "swift-fox", "sess1", 300, 0).unwrap_err();
+        assert!(err.to_string().contains("Negated lock patterns are not supported"), "{}", err);
+        let err = acquire(tmp.path(), "src/[a-.rs", "swift-fox", "sess1", 300, 0).unwrap_err();
+        assert!(err.to_string().contains("Invalid lock pattern"), "{}", err);
+        assert!(list_active(tmp.path()).unwrap().is_empty());
     }
 
     #[test]
     fn different_patterns_ok() {
         let tmp = TempDir::new().unwrap();
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300).unwrap();
-        acquire(tmp.path(), "tests/*.rs", "bold-hawk", "sess2", 300).unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        acquire(tmp.path(), "tests/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
         let locks = list_active(tmp.path()).unwrap();
         assert_eq!(locks.len(), 2);
     }
@@ -220,16 +767,49 @@ mod tests {
     #[test]
     fn release_lock() {
         let tmp = TempDir::new().unwrap();
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300).unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
         release(tmp.path(), "src/*.rs", "sess1").unwrap();
         let locks = list_active(tmp.path()).unwrap();
         assert_eq!(locks.len(), 0);
     }
 
+    #[test]
+    fn max_locks_refuses_until_released() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 2).unwrap();
+        acquire(tmp.path(), "docs/*.md", "swift-fox", "sess1", 300, 2).unwrap();
+        // Other sessions have their own budget
+        acquire(tmp.path(), "tests/*.rs", "bold-hawk", "sess2", 300, 2).unwrap();
+
+        let err = acquire(tmp.path(), "benches/*.rs", "swift-fox", "sess1", 300, 2).unwrap_err();
+        assert!(err.to_string().contains("Lock limit reached"));
+        // Refreshing a held pattern is still allowed at the limit
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 2).unwrap();
+
+        release(tmp.path(), "docs/*.md", "sess1").unwrap();
+        acquire(tmp.path(), "benches/*.rs", "swift-fox", "sess1", 300, 2).unwrap();
+    }
+
+    #[test]
+    fn release_all_only_touches_own_session() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
+        acquire(tmp.path(), "docs/*.md", "swift-fox", "sess1", 300, 0).unwrap();
+        acquire(tmp.path(), "tests/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
+
+        let released = release_all_for_session(tmp.path(), "sess1").unwrap();
+        assert_eq!(released.len(), 2);
+        let locks = list_active(tmp.path()).unwrap();
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].session_id, "sess2");
+
+        assert!(release_all_for_session(tmp.path(), "sess1").unwrap().is_empty());
+    }
+
     #[test]
     fn check_file_match() {
         let tmp = TempDir::new().unwrap();
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300).unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
 
         // Different session should see the lock
         let result = check_file(tmp.path(), "src/main.rs", "sess2").unwrap();
@@ -247,16 +827,29 @@ mod tests {
     #[test]
     fn glob_matching_recursive() {
         let tmp = TempDir::new().unwrap();
-        acquire(tmp.path(), "src/**/*.rs", "swift-fox", "sess1", 300).unwrap();
+        acquire(tmp.path(), "src/**/*.rs", "swift-fox", "sess1", 300, 0).unwrap();
         let result = check_file(tmp.path(), "src/commands/init.rs", "sess2").unwrap();
         assert!(result.is_some());
     }
 
+    #[test]
+    fn take_expiring_reports_once() {
+        let tmp = TempDir::new().unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 10, 0).unwrap();
+        acquire(tmp.path(), "tests/*.rs", "bold-hawk", "sess2", 300, 0).unwrap();
+
+        let expiring = take_expiring(tmp.path(), 30).unwrap();
+        assert_eq!(expiring.len(), 1);
+        assert_eq!(expiring[0].glob, "src/*.rs");
+
+        assert!(take_expiring(tmp.path(), 30).unwrap().is_empty());
+    }
+
     #[test]
     fn expired_lock_cleaned_up() {
         let tmp = TempDir::new().unwrap();
         // Create a lock with 0 TTL (immediately expired)
-        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 0).unwrap();
+        acquire(tmp.path(), "src/*.rs", "swift-fox", "sess1", 0, 0).unwrap();
 
         // Should be cleaned up on next list
         std::thread::sleep(std::time::Duration::from_millis(1100));
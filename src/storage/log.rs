@@ -1,26 +1,216 @@
+use std::collections::HashMap;
 use std::fs;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::path::Path;
 use std::time::SystemTime;
 use crate::error::Result;
+use crate::format;
+use crate::storage::atomic;
 
 /// Write a message to the log directory using tmp+rename for atomicity.
 /// Filename: {timestamp_ns}.md
 pub fn write_message(log_dir: &Path, name: &str, body: &str) -> Result<()> {
-    let timestamp_ns = SystemTime::now()
+    write_message_with_headers(log_dir, name, &[], body)
+}
+
+fn now_ns() -> u128 {
+    SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
-        .as_nanos();
+        .as_nanos()
+}
+
+/// Write a message with extra `key: value` header lines after the `name:` header.
+pub fn write_message_with_headers(
+    log_dir: &Path,
+    name: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> Result<()> {
+    write_message_at(log_dir, now_ns(), name, headers, body)?;
+    Ok(())
+}
+
+/// Write a message for delayed delivery `delay_secs` from now. The file is
+/// named by its delivery time and carries a `deliver_at` header, so readers
+/// skip it until then. Returns the delivery timestamp in nanoseconds.
+pub fn schedule_message(
+    log_dir: &Path,
+    delay_secs: u64,
+    name: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> Result<u128> {
+    let deliver_at = now_ns() + delay_secs as u128 * 1_000_000_000;
+    let deliver_header = deliver_at.to_string();
+    let mut headers = headers.to_vec();
+    headers.push(("deliver_at", &deliver_header));
+    write_message_at(log_dir, deliver_at, name, &headers, body)
+}
+
+/// Most later timestamps tried when the wanted filename is already taken.
+const MAX_NAME_ATTEMPTS: u128 = 1000;
 
-    let filename = format!("{}.md", timestamp_ns);
-    let target = log_dir.join(&filename);
-    let tmp = log_dir.join(format!(".tmp.{}", filename));
+/// Write a message named by `timestamp_ns`, or the next free nanosecond if
+/// another writer already used that name (coarse clocks can hand two
+/// writers the same reading). Returns the timestamp actually used.
+fn write_message_at(
+    log_dir: &Path,
+    timestamp_ns: u128,
+    name: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> Result<u128> {
+    static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+    // Unique per writer, so two writers with the same timestamp can't share a tmp file
+    let tmp = log_dir.join(format!(
+        ".tmp.{}.{}.{}",
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed),
+        timestamp_ns
+    ));
 
-    let content = format!("name: {}\n{}\n", name, body);
+    let mut content = format!("name: {}\n", name);
+    for (key, value) in headers {
+        content.push_str(&format!("{}: {}\n", key, value));
+    }
+    // Blank line ends the headers, so a body can't be mistaken for one
+    content.push('\n');
+    content.push_str(body);
+    content.push('\n');
     fs::write(&tmp, &content)?;
-    fs::rename(&tmp, &target)?;
+
+    let mut timestamp_ns = timestamp_ns;
+    let mut attempts = 0;
+    let filename = loop {
+        let filename = format!("{}.md", timestamp_ns);
+        match publish(&tmp, &log_dir.join(&filename)) {
+            Ok(()) => break filename,
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < MAX_NAME_ATTEMPTS => {
+                timestamp_ns += 1;
+                attempts += 1;
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&tmp);
+                return Err(e.into());
+            }
+        }
+    };
+
+    // Messages whose visibility depends on headers are always parsed, so only
+    // plain ones go into the index. Judged from the written file as readers
+    // will parse it, not from the caller's header list. Best-effort: readers
+    // fall back to parsing.
+    if crate::format::parse_message(&content).is_some_and(|m| m.is_plain()) {
+        let _ = append_index(log_dir, &filename, name);
+    }
+    Ok(timestamp_ns)
+}
+
+/// Move `tmp` to `target` without ever replacing an existing message:
+/// hard-linking fails atomically with `AlreadyExists` when the name is taken.
+/// Filesystems without hard links fall back to check-then-rename.
+fn publish(tmp: &Path, target: &Path) -> io::Result<()> {
+    match fs::hard_link(tmp, target) {
+        Ok(()) => {
+            let _ = fs::remove_file(tmp);
+            Ok(())
+        }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Err(e),
+        Err(_) => {
+            if target.exists() {
+                return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+            }
+            atomic::rename(tmp, target).map_err(|e| io::Error::other(e.to_string()))
+        }
+    }
+}
+
+/// Author index for a log directory: a `log.index` file beside it (kept out
+/// of `log/` so the directory holds only messages), with one
+/// `{filename} {size} {mtime_ns} {author}` line per message.
+fn index_path(log_dir: &Path) -> std::path::PathBuf {
+    log_dir.with_extension("index")
+}
+
+/// Size and modification time identifying the current version of a message.
+fn file_stamp(path: &Path) -> Option<(u64, u128)> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(SystemTime::UNIX_EPOCH).ok()?.as_nanos();
+    Some((meta.len(), mtime))
+}
+
+fn append_index(log_dir: &Path, filename: &str, author: &str) -> Result<()> {
+    let Some((size, mtime)) = file_stamp(&log_dir.join(filename)) else {
+        return Ok(());
+    };
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(index_path(log_dir))?;
+    // One write call per line so concurrent appends don't interleave
+    file.write_all(format!("{} {} {} {}\n", filename, size, mtime, author).as_bytes())?;
     Ok(())
 }
 
+/// Rewrite the index without entries for messages that are gone, so it
+/// shrinks along with the log. Best-effort like the appends: a line appended
+/// while this runs may be dropped, and readers then parse that message.
+pub fn prune_index(log_dir: &Path) -> Result<()> {
+    let path = index_path(log_dir);
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let kept: String = content
+        .lines()
+        .filter(|line| line.split(' ').next().is_some_and(|name| log_dir.join(name).is_file()))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    if kept.len() == content.len() {
+        return Ok(());
+    }
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = path.with_file_name(format!(".tmp.{}", file_name));
+    fs::write(&tmp, kept)?;
+    atomic::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Authors of plain broadcast messages, loaded from the log's index so
+/// filters can skip opening those files.
+#[derive(Debug, Default)]
+pub struct AuthorIndex {
+    entries: HashMap<String, ((u64, u128), String)>,
+}
+
+impl AuthorIndex {
+    /// Load the index; a missing or unreadable index is simply empty.
+    pub fn load(log_dir: &Path) -> AuthorIndex {
+        let entries = fs::read_to_string(index_path(log_dir))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(4, ' ');
+                let filename = parts.next()?;
+                let size = parts.next()?.parse().ok()?;
+                let mtime = parts.next()?.parse().ok()?;
+                let author = parts.next()?;
+                Some((filename.to_string(), ((size, mtime), author.to_string())))
+            })
+            .collect();
+        AuthorIndex { entries }
+    }
+
+    /// Indexed author of the message at `path`, or `None` when it isn't
+    /// indexed or its size or mtime no longer matches (edited since).
+    pub fn author_of(&self, path: &Path) -> Option<&str> {
+        let filename = path.file_name()?.to_str()?;
+        let (stamp, author) = self.entries.get(filename)?;
+        (file_stamp(path)? == *stamp).then_some(author.as_str())
+    }
+}
+
 /// List message files sorted by filename (chronological order).
 /// Returns (filename, full_path) pairs.
 pub fn list_messages(log_dir: &Path) -> Result<Vec<(String, std::path::PathBuf)>> {
@@ -42,6 +232,82 @@ pub fn list_messages(log_dir: &Path) -> Result<Vec<(String, std::path::PathBuf)>
     Ok(entries)
 }
 
+/// Delete ephemeral messages whose `expires_at` has passed. Reads every
+/// message, so it runs from full scans (`read --all`, `compact`) rather than
+/// the unread fast path. Returns the number of messages removed.
+pub fn prune_expired(log_dir: &Path) -> Result<usize> {
+    let mut pruned = 0;
+    for (_, path) in list_messages(log_dir)? {
+        let expired = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| format::parse_message(&content).map(|m| m.is_expired()))
+            .unwrap_or(false);
+        // Ignore ENOENT race with another pruner
+        if expired && fs::remove_file(&path).is_ok() {
+            pruned += 1;
+        }
+    }
+    Ok(pruned)
+}
+
+/// Delete messages with a filename timestamp older than `cutoff_ns`, and
+/// their index entries. Returns the number of messages removed.
+pub fn delete_older_than(log_dir: &Path, cutoff_ns: u128) -> Result<usize> {
+    let mut deleted = 0;
+    for (filename, path) in list_messages(log_dir)? {
+        let old = filename.trim_end_matches(".md").parse::<u128>().is_ok_and(|ts| ts < cutoff_ns);
+        // Ignore ENOENT race with a concurrent compaction
+        if old && fs::remove_file(&path).is_ok() {
+            deleted += 1;
+        }
+    }
+    if deleted > 0 {
+        let _ = prune_index(log_dir);
+    }
+    Ok(deleted)
+}
+
+/// Delete every message, scheduled ones included, and the author index.
+/// In-flight `.tmp.` files are left to their writers. Returns the number of
+/// messages removed.
+pub fn delete_all(log_dir: &Path) -> Result<usize> {
+    let deleted = delete_older_than(log_dir, u128::MAX)?;
+    match fs::remove_file(index_path(log_dir)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    Ok(deleted)
+}
+
+/// The newest already-delivered message by `author` posted within the last
+/// `window_secs`, if any. Walks the log backwards and stops at the window.
+pub fn latest_from(log_dir: &Path, author: &str, window_secs: u64) -> Result<Option<std::path::PathBuf>> {
+    let now = now_ns();
+    let cutoff = now.saturating_sub(window_secs as u128 * 1_000_000_000);
+    for (filename, path) in list_messages(log_dir)?.into_iter().rev() {
+        let Ok(ts) = filename.trim_end_matches(".md").parse::<u128>() else { continue };
+        if ts > now {
+            continue; // scheduled for later
+        }
+        if ts < cutoff {
+            break;
+        }
+        let by_author = fs::read_to_string(&path)
+            .ok()
+            .is_some_and(|content| format::parse_message(&content).is_some_and(|m| m.name == author));
+        if by_author {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// Find a message file by id (its timestamp filename stem).
+pub fn find_message(log_dir: &Path, id: &str) -> Option<std::path::PathBuf> {
+    let path = log_dir.join(format!("{}.md", id));
+    path.is_file().then_some(path)
+}
+
 /// Check if the log directory has any messages.
 pub fn has_any_messages(log_dir: &Path) -> Result<bool> {
     if !log_dir.exists() {
@@ -62,6 +328,44 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn colliding_timestamps_never_overwrite() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        // Force every writer onto the same clock reading
+        let ts = 1_700_000_000_000_000_000;
+        let used: Vec<u128> = (0..5)
+            .map(|i| write_message_at(&log, ts, "swift-fox", &[], &format!("msg {}", i)).unwrap())
+            .collect();
+        assert_eq!(used, vec![ts, ts + 1, ts + 2, ts + 3, ts + 4]);
+
+        let messages = list_messages(&log).unwrap();
+        assert_eq!(messages.len(), 5);
+        for (i, (_, path)) in messages.iter().enumerate() {
+            assert!(fs::read_to_string(path).unwrap().contains(&format!("msg {}", i)));
+        }
+        // No tmp files left behind
+        assert_eq!(fs::read_dir(&log).unwrap().count(), 5);
+    }
+
+    #[test]
+    fn concurrent_colliding_writers_all_survive() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        let ts = 1_700_000_000_000_000_000;
+        std::thread::scope(|scope| {
+            for i in 0..10 {
+                let log = &log;
+                scope.spawn(move || write_message_at(log, ts, "swift-fox", &[], &format!("msg {}", i)).unwrap());
+            }
+        });
+        assert_eq!(list_messages(&log).unwrap().len(), 10);
+    }
+
     #[test]
     fn write_and_list_messages() {
         let tmp = TempDir::new().unwrap();
@@ -78,6 +382,139 @@ mod tests {
         assert!(msgs[0].0 < msgs[1].0);
     }
 
+    #[test]
+    fn write_with_headers_and_find() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        write_message_with_headers(&log, "swift-fox", &[("reply_to", "123")], "ok").unwrap();
+        let (id, path) = list_messages(&log).unwrap().remove(0);
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "name: swift-fox\nreply_to: 123\n\nok\n");
+
+        let id = id.trim_end_matches(".md");
+        assert_eq!(find_message(&log, id), Some(path));
+        assert_eq!(find_message(&log, "42"), None);
+    }
+
+    #[test]
+    fn header_like_body_round_trips() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        let body = "to: whom it may concern, build is green\npriority: high";
+        write_message_with_headers(&log, "swift-fox", &[("status", "done")], body).unwrap();
+        write_message(&log, "bold-hawk", "refs: not a header").unwrap();
+
+        let messages = list_messages(&log).unwrap();
+        let first = fs::read_to_string(&messages[0].1).unwrap();
+        let msg = crate::format::parse_message(&first).unwrap();
+        assert_eq!(msg.body, body);
+        assert_eq!(msg.to, None);
+        assert_eq!(msg.priority, crate::format::Priority::Normal);
+        assert_eq!(msg.status, Some(crate::format::AgentStatus::Done));
+
+        let second = fs::read_to_string(&messages[1].1).unwrap();
+        let msg = crate::format::parse_message(&second).unwrap();
+        assert_eq!(msg.body, "refs: not a header");
+        assert!(msg.refs.is_empty());
+    }
+
+    #[test]
+    fn scheduled_message_named_by_delivery_time() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        let deliver_at = schedule_message(&log, 600, "swift-fox", &[], "later").unwrap();
+        let (name, path) = list_messages(&log).unwrap().remove(0);
+        assert_eq!(name, format!("{}.md", deliver_at));
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, format!("name: swift-fox\ndeliver_at: {}\n\nlater\n", deliver_at));
+    }
+
+    #[test]
+    fn prune_expired_keeps_durable_messages() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        write_message_with_headers(&log, "swift-fox", &[("expires_at", "1")], "gone").unwrap();
+        write_message_with_headers(&log, "swift-fox", &[("expires_at", &u128::MAX.to_string())], "later").unwrap();
+        write_message(&log, "swift-fox", "durable").unwrap();
+
+        assert_eq!(prune_expired(&log).unwrap(), 1);
+        assert_eq!(list_messages(&log).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn delete_older_than_keeps_newer_messages() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        fs::write(log.join("100.md"), "name: swift-fox
+old
+").unwrap();
+        fs::write(log.join("200.md"), "name: swift-fox
+new
+").unwrap();
+
+        assert_eq!(delete_older_than(&log, 150).unwrap(), 1);
+        assert_eq!(list_messages(&log).unwrap()[0].0, "200.md");
+    }
+
+    #[test]
+    fn index_records_plain_messages_only() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+
+        write_message(&log, "swift-fox", "hello").unwrap();
+        write_message_with_headers(&log, "bold-hawk", &[("to", "swift-fox")], "psst").unwrap();
+
+        let index = AuthorIndex::load(&log);
+        let msgs = list_messages(&log).unwrap();
+        assert_eq!(index.author_of(&msgs[0].1), Some("swift-fox"));
+        assert_eq!(index.author_of(&msgs[1].1), None);
+
+        // Indexing follows the parsed file: a header-like body is still a broadcast
+        write_message(&log, "calm-owl", "to: swift-fox only").unwrap();
+        let msgs = list_messages(&log).unwrap();
+        let parsed = fs::read_to_string(&msgs[2].1).unwrap();
+        assert!(crate::format::parse_message(&parsed).unwrap().is_plain());
+        assert_eq!(AuthorIndex::load(&log).author_of(&msgs[2].1), Some("calm-owl"));
+
+        // A hand-edited message no longer trusts its index entry, even when
+        // the rewrite keeps the same length
+        fs::write(&msgs[0].1, "name: calm-owl\nhello there\n").unwrap();
+        assert_eq!(AuthorIndex::load(&log).author_of(&msgs[0].1), None);
+        let before = fs::read_to_string(&msgs[2].1).unwrap();
+        fs::write(&msgs[2].1, before.replace("calm-owl", "bold-owl")).unwrap();
+        filetime::set_file_mtime(&msgs[2].1, filetime::FileTime::from_unix_time(1, 0)).unwrap();
+        assert_eq!(fs::metadata(&msgs[2].1).unwrap().len() as usize, before.len());
+        assert_eq!(AuthorIndex::load(&log).author_of(&msgs[2].1), None);
+    }
+
+    #[test]
+    fn delete_older_than_prunes_index() {
+        let tmp = TempDir::new().unwrap();
+        let log = tmp.path().join("log");
+        fs::create_dir(&log).unwrap();
+        write_message(&log, "swift-fox", "old").unwrap();
+        write_message(&log, "bold-hawk", "new").unwrap();
+        let msgs = list_messages(&log).unwrap();
+        let cutoff = msgs[1].0.trim_end_matches(".md").parse::<u128>().unwrap();
+
+        assert_eq!(delete_older_than(&log, cutoff).unwrap(), 1);
+        let index = fs::read_to_string(index_path(&log)).unwrap();
+        assert_eq!(index.lines().count(), 1, "{}", index);
+        assert!(index.starts_with(&msgs[1].0));
+        assert_eq!(AuthorIndex::load(&log).author_of(&msgs[1].1), Some("bold-hawk"));
+    }
+
     #[test]
     fn has_any_messages_empty() {
         let tmp = TempDir::new().unwrap();
@@ -1,4 +1,6 @@
 pub mod paths;
+pub mod archive;
+pub mod atomic;
 pub mod config;
 pub mod log;
 pub mod cursor;
@@ -1,64 +1,258 @@
 use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::OnceLock;
 use crate::error::{AgentChatError, Result};
+use crate::names;
+use crate::storage::config;
 
 const DIR_NAME: &str = ".agent-chat";
 
 /// Walk up from `start` to find the `.agent-chat/` directory.
 /// Returns the path to `.agent-chat/` or an error if not found.
+/// The walk stops at the repository root (the first directory holding a
+/// `.git` directory or file), so a nested checkout never picks up a parent
+/// project's chat; see `find_root_across_repos` for shared monorepo roots.
+/// A symlinked `.agent-chat/` is followed; the returned path keeps the link
+/// so tmp files stay next to their targets. A dangling link is an error rather
+/// than a reason to keep walking up into a parent project.
 pub fn find_root(start: &Path) -> Result<PathBuf> {
+    walk_up(start, true)
+}
+
+/// Like `find_root`, but keeps walking past repository roots.
+pub fn find_root_across_repos(start: &Path) -> Result<PathBuf> {
+    walk_up(start, false)
+}
+
+fn walk_up(start: &Path, stop_at_repo: bool) -> Result<PathBuf> {
     let mut current = start.to_path_buf();
     loop {
         let candidate = current.join(DIR_NAME);
         if candidate.is_dir() {
             return Ok(candidate);
         }
+        if candidate.is_symlink() {
+            return Err(AgentChatError::Other(format!(
+                "{} is a symlink to a missing directory",
+                candidate.display()
+            )));
+        }
+        if stop_at_repo && current.join(".git").exists() {
+            return Err(AgentChatError::NotInitialized);
+        }
         if !current.pop() {
             return Err(AgentChatError::NotInitialized);
         }
     }
 }
 
+/// Find the `.agent-chat/` directory, honoring `AGENT_CHAT_ROOT` before the
+/// upward walk from `start`. The env var may name the `.agent-chat/` directory
+/// itself or the project directory containing it; a value that is neither is
+/// ignored and discovery falls back to the walk. `AGENT_CHAT_CROSS_REPO=1`
+/// lets the walk continue past repository roots.
+pub fn discover_root(start: &Path) -> Result<PathBuf> {
+    match root_override(std::env::var("AGENT_CHAT_ROOT").ok()) {
+        Some(root) => Ok(root),
+        None if std::env::var("AGENT_CHAT_CROSS_REPO").is_ok_and(|v| v == "1") => find_root_across_repos(start),
+        None => find_root(start),
+    }
+}
+
+/// Resolve the `--root` flag. Like `AGENT_CHAT_ROOT` it may name the
+/// `.agent-chat/` directory or the project containing it, but a path that is
+/// neither is an error instead of falling back to discovery.
+pub fn explicit_root(path: &Path) -> Result<PathBuf> {
+    root_override(Some(path.to_string_lossy().into_owned()))
+        .ok_or_else(|| AgentChatError::Other(format!("No .agent-chat/ directory at {}", path.display())))
+}
+
+/// Project directory named by `--root` for commands that work on a project
+/// rather than an existing chat (`init`, `doctor`, `uninstall`): the path
+/// itself, or the parent of a path naming the `.agent-chat/` directory.
+pub fn project_dir(path: &Path) -> Result<PathBuf> {
+    let project = match path.parent() {
+        Some(parent) if path.file_name().is_some_and(|n| n == DIR_NAME) => parent,
+        _ => path,
+    };
+    if !project.is_dir() {
+        return Err(AgentChatError::Other(format!("No such directory: {}", project.display())));
+    }
+    Ok(project.to_path_buf())
+}
+
+fn root_override(value: Option<String>) -> Option<PathBuf> {
+    let path = PathBuf::from(value.filter(|v| !v.trim().is_empty())?);
+    if path.file_name().is_some_and(|n| n == DIR_NAME) && path.is_dir() {
+        return Some(path);
+    }
+    let nested = path.join(DIR_NAME);
+    nested.is_dir().then_some(nested)
+}
+
+/// Express `file_path` relative to the project that owns `root` (the
+/// `.agent-chat/` directory), so hook paths like `/home/me/proj/src/main.rs`
+/// match lock globs like `src/*.rs`. Relative paths are returned as given,
+/// minus a leading `./`. Returns `None` for an absolute path outside the
+/// project, after also comparing canonical forms to see through symlinks.
+pub fn project_relative(root: &Path, file_path: &str) -> Option<String> {
+    let path = Path::new(file_path);
+    if path.is_relative() {
+        return Some(file_path.trim_start_matches("./").to_string());
+    }
+    let project = std::path::absolute(root).ok()?.parent()?.to_path_buf();
+    let relative = path.strip_prefix(&project).map(Path::to_path_buf).or_else(|_| {
+        // The file may not exist yet (Write), so canonicalize its directory
+        let project = project.canonicalize()?;
+        let dir = path.parent().unwrap_or(path).canonicalize()?;
+        let full = path.file_name().map_or(dir.clone(), |name| dir.join(name));
+        full.strip_prefix(&project).map(Path::to_path_buf).map_err(std::io::Error::other)
+    });
+    let relative = relative.ok()?;
+    let parts: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+    Some(parts.join("/"))
+}
+
 /// Create the `.agent-chat/` directory structure at the given project root.
+/// If `.agent-chat` is a dangling symlink, its target directory is created.
 pub fn create_dirs(project_root: &Path) -> Result<()> {
     let base = project_root.join(DIR_NAME);
-    std::fs::create_dir_all(base.join("log"))?;
-    std::fs::create_dir_all(base.join("locks"))?;
-    std::fs::create_dir_all(base.join("cursors"))?;
+    if base.is_symlink() && !base.exists() {
+        std::fs::create_dir_all(project_root.join(std::fs::read_link(&base)?))?;
+    }
+    create_room_dirs(&base)?;
     std::fs::create_dir_all(base.join("sessions"))?;
-    std::fs::create_dir_all(base.join("focuses"))?;
+    Ok(())
+}
+
+/// Directory holding every named room: `.agent-chat/rooms/<name>/`.
+pub fn rooms_dir(root: &Path) -> PathBuf {
+    root.join("rooms")
+}
+
+/// Name of the active room, or `None` for the default room. Config is only
+/// consulted once a named room exists. An unreadable config, a malformed room
+/// name or a room with no directory behind it is an error, since guessing the
+/// room would send messages (or files, for a name like `../..`) to the wrong
+/// place.
+pub fn active_room(root: &Path) -> Result<Option<String>> {
+    if !rooms_dir(root).is_dir() {
+        return Ok(None);
+    }
+    let config = config::read_config(&config_path(root)).map_err(|e| {
+        AgentChatError::Other(format!("Cannot determine the active room from config.toml: {}", e))
+    })?;
+    if config.room.is_empty() {
+        return Ok(None);
+    }
+    names::validate_name(&config.room).map_err(|e| {
+        AgentChatError::Other(format!("Cannot determine the active room from config.toml: {}", e))
+    })?;
+    if !rooms_dir(root).join(&config.room).is_dir() {
+        return Err(AgentChatError::Other(format!(
+            "Cannot determine the active room: no such room {} (switch with `agent-chat room use <name>`)",
+            config.room
+        )));
+    }
+    Ok(Some(config.room))
+}
+
+/// The active room as resolved once at startup by `resolve_room`, with the
+/// root it belongs to.
+static RESOLVED_ROOM: OnceLock<(PathBuf, Option<String>)> = OnceLock::new();
+
+/// Resolve the active room for `root` and remember it for the rest of the
+/// process, so the per-room paths below don't re-read config.toml each time.
+pub fn resolve_room(root: &Path) -> Result<Option<String>> {
+    let room = active_room(root)?;
+    let _ = RESOLVED_ROOM.set((root.to_path_buf(), room.clone()));
+    Ok(room)
+}
+
+/// Base directory of the active room. The default room lives directly in
+/// `.agent-chat/`. Uses the room from `resolve_room` when it ran for `root`;
+/// otherwise looks it up, warning (once) and falling back to the default
+/// room if that fails.
+pub fn room_dir(root: &Path) -> PathBuf {
+    let room = match RESOLVED_ROOM.get() {
+        Some((resolved, room)) if resolved == root => Ok(room.clone()),
+        _ => active_room(root),
+    };
+    match room {
+        Ok(Some(room)) => rooms_dir(root).join(room),
+        Ok(None) => root.to_path_buf(),
+        Err(e) => {
+            static WARNED: std::sync::Once = std::sync::Once::new();
+            WARNED.call_once(|| eprintln!("WARNING: {}; using the default room", e));
+            root.to_path_buf()
+        }
+    }
+}
+
+/// Create a room's log, locks, cursors, and focuses directories under `base`.
+pub fn create_room_dirs(base: &Path) -> Result<()> {
+    for dir in ["log", "locks", "cursors", "focuses"] {
+        std::fs::create_dir_all(base.join(dir))?;
+    }
     Ok(())
 }
 
 pub fn log_dir(root: &Path) -> PathBuf {
-    root.join("log")
+    room_dir(root).join("log")
 }
 
 pub fn locks_dir(root: &Path) -> PathBuf {
-    root.join("locks")
+    room_dir(root).join("locks")
 }
 
 pub fn cursors_dir(root: &Path) -> PathBuf {
-    root.join("cursors")
+    room_dir(root).join("cursors")
 }
 
 pub fn sessions_dir(root: &Path) -> PathBuf {
     root.join("sessions")
 }
 
+/// Lock file serializing name assignment across concurrent registrations.
+pub fn sessions_lock_path(root: &Path) -> PathBuf {
+    root.join("sessions.lock")
+}
+
 pub fn focuses_dir(root: &Path) -> PathBuf {
-    root.join("focuses")
+    room_dir(root).join("focuses")
+}
+
+pub fn archive_dir(root: &Path) -> PathBuf {
+    room_dir(root).join("archive")
+}
+
+/// Optional explicit identity (`{"session_id": ..., "name": ...}`) for shells
+/// where exporting env vars is awkward.
+pub fn identity_path(root: &Path) -> PathBuf {
+    root.join("identity")
 }
 
 pub fn config_path(root: &Path) -> PathBuf {
     root.join("config.toml")
 }
 
-/// Return the user's home directory from `$HOME`.
+/// Return the user's home directory from `$HOME`, falling back to the
+/// Windows `%USERPROFILE%` or `%HOMEDRIVE%%HOMEPATH%` when it is unset.
 pub fn home_dir() -> Result<PathBuf> {
-    std::env::var("HOME")
-        .map(PathBuf::from)
-        .map_err(|_| AgentChatError::MissingEnv("HOME".into()))
+    home_dir_from(|key| std::env::var(key).ok())
+}
+
+/// Resolve the home directory from `lookup`: `HOME`, then `USERPROFILE`,
+/// then `HOMEDRIVE` + `HOMEPATH`. Empty values are treated as unset.
+fn home_dir_from(lookup: impl Fn(&str) -> Option<String>) -> Result<PathBuf> {
+    let var = |key: &str| lookup(key).filter(|v| !v.is_empty());
+    if let Some(home) = var("HOME").or_else(|| var("USERPROFILE")) {
+        return Ok(PathBuf::from(home));
+    }
+    match (var("HOMEDRIVE"), var("HOMEPATH")) {
+        (Some(drive), Some(path)) => Ok(PathBuf::from(format!("{}{}", drive, path))),
+        _ => Err(AgentChatError::MissingEnv("HOME".into())),
+    }
 }
 
 /// Append `pattern` to `.git/info/exclude` if not already present.
@@ -116,6 +310,141 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn find_root_stops_at_repo_boundary() {
+        let tmp = TempDir::new().unwrap();
+        // Outer project with its own chat
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        std::fs::create_dir(tmp.path().join(".agent-chat")).unwrap();
+        // Nested checkout without one; a worktree's `.git` is a file
+        let nested = tmp.path().join("vendor").join("lib");
+        std::fs::create_dir_all(nested.join("src")).unwrap();
+        std::fs::write(nested.join(".git"), "gitdir: ../../.git/worktrees/lib\n").unwrap();
+
+        assert!(matches!(find_root(&nested.join("src")), Err(AgentChatError::NotInitialized)));
+        assert_eq!(find_root_across_repos(&nested.join("src")).unwrap(), tmp.path().join(".agent-chat"));
+
+        // Inside the outer repo, but outside the nested one, the walk still finds it
+        let sibling = tmp.path().join("docs");
+        std::fs::create_dir(&sibling).unwrap();
+        assert_eq!(find_root(&sibling).unwrap(), tmp.path().join(".agent-chat"));
+    }
+
+    #[test]
+    fn find_root_finds_chat_at_repo_root() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        std::fs::create_dir(tmp.path().join(".agent-chat")).unwrap();
+        let nested = tmp.path().join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_root(&nested).unwrap(), tmp.path().join(".agent-chat"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_root_follows_symlinked_dir() {
+        let tmp = TempDir::new().unwrap();
+        let shared = tmp.path().join("shared-chat");
+        std::fs::create_dir(&shared).unwrap();
+        let project = tmp.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+        std::os::unix::fs::symlink(&shared, project.join(".agent-chat")).unwrap();
+
+        let found = find_root(&project).unwrap();
+        assert_eq!(found, project.join(".agent-chat"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_root_errors_on_dangling_symlink() {
+        let tmp = TempDir::new().unwrap();
+        // A parent project that must not be picked up
+        std::fs::create_dir(tmp.path().join(".agent-chat")).unwrap();
+        let project = tmp.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+        std::os::unix::fs::symlink(tmp.path().join("gone"), project.join(".agent-chat")).unwrap();
+
+        assert!(find_root(&project).is_err());
+    }
+
+    #[test]
+    fn project_relative_strips_project_root() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join(".agent-chat");
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+
+        let absolute = tmp.path().join("src").join("main.rs");
+        assert_eq!(project_relative(&root, absolute.to_str().unwrap()).as_deref(), Some("src/main.rs"));
+        assert_eq!(project_relative(&root, "./src/main.rs").as_deref(), Some("src/main.rs"));
+        assert_eq!(project_relative(&root, "src/main.rs").as_deref(), Some("src/main.rs"));
+
+        let outside = TempDir::new().unwrap();
+        let elsewhere = outside.path().join("main.rs");
+        assert_eq!(project_relative(&root, elsewhere.to_str().unwrap()), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn project_relative_sees_through_symlinked_project() {
+        let tmp = TempDir::new().unwrap();
+        let real = tmp.path().join("real");
+        std::fs::create_dir_all(real.join("src")).unwrap();
+        let link = tmp.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        // Root found through the link, file reported via the real path
+        let root = link.join(".agent-chat");
+        let file = real.join("src").join("new.rs");
+        assert_eq!(project_relative(&root, file.to_str().unwrap()).as_deref(), Some("src/new.rs"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn create_dirs_through_dangling_symlink() {
+        let tmp = TempDir::new().unwrap();
+        let target = tmp.path().join("shared-chat");
+        std::os::unix::fs::symlink(&target, tmp.path().join(".agent-chat")).unwrap();
+
+        create_dirs(tmp.path()).unwrap();
+        assert!(target.join("log").is_dir());
+        assert!(find_root(tmp.path()).is_ok());
+    }
+
+    #[test]
+    fn project_dir_strips_data_dir() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(project_dir(tmp.path()).unwrap(), tmp.path());
+        assert_eq!(project_dir(&tmp.path().join(".agent-chat")).unwrap(), tmp.path());
+        assert!(project_dir(&tmp.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn explicit_root_requires_agent_chat_dir() {
+        let tmp = TempDir::new().unwrap();
+        let base = tmp.path().join(".agent-chat");
+        std::fs::create_dir(&base).unwrap();
+
+        assert_eq!(explicit_root(tmp.path()).unwrap(), base);
+        assert_eq!(explicit_root(&base).unwrap(), base);
+        let err = explicit_root(&tmp.path().join("missing")).unwrap_err();
+        assert!(err.to_string().contains("No .agent-chat/ directory at"));
+    }
+
+    #[test]
+    fn root_override_accepts_data_dir_or_project_dir() {
+        let tmp = TempDir::new().unwrap();
+        let base = tmp.path().join(".agent-chat");
+        std::fs::create_dir(&base).unwrap();
+        let as_string = |p: &Path| Some(p.to_string_lossy().to_string());
+
+        assert_eq!(root_override(as_string(&base)), Some(base.clone()));
+        assert_eq!(root_override(as_string(tmp.path())), Some(base));
+        assert_eq!(root_override(as_string(&tmp.path().join("missing"))), None);
+        assert_eq!(root_override(Some(String::new())), None);
+        assert_eq!(root_override(None), None);
+    }
+
     #[test]
     fn create_dirs_makes_all_subdirs() {
         let tmp = TempDir::new().unwrap();
@@ -127,6 +456,49 @@ mod tests {
         assert!(tmp.path().join(".agent-chat/focuses").is_dir());
     }
 
+    #[test]
+    fn room_dir_follows_configured_room() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        assert_eq!(log_dir(root), root.join("log"));
+
+        std::fs::create_dir_all(rooms_dir(root).join("auth")).unwrap();
+        // No room selected yet: still the default room
+        assert_eq!(log_dir(root), root.join("log"));
+
+        std::fs::write(config_path(root), "room = \"auth\"\n").unwrap();
+        assert_eq!(log_dir(root), root.join("rooms/auth/log"));
+        assert_eq!(sessions_dir(root), root.join("sessions"));
+    }
+
+    #[test]
+    fn active_room_reports_unreadable_config() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::write(config_path(root), "room = [\n").unwrap();
+        // Without named rooms the config doesn't matter
+        assert_eq!(active_room(root).unwrap(), None);
+
+        std::fs::create_dir_all(rooms_dir(root).join("auth")).unwrap();
+        let err = active_room(root).unwrap_err();
+        assert!(err.to_string().contains("Cannot determine the active room"), "{}", err);
+    }
+
+    #[test]
+    fn active_room_rejects_escaping_or_missing_rooms() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        std::fs::create_dir_all(rooms_dir(root).join("auth")).unwrap();
+
+        for room in ["../..", "gone"] {
+            std::fs::write(config_path(root), format!("room = \"{}\"\n", room)).unwrap();
+            let err = active_room(root).unwrap_err();
+            assert!(err.to_string().contains("Cannot determine the active room"), "{}", err);
+            // Paths never leave `.agent-chat/`
+            assert_eq!(log_dir(root), root.join("log"));
+        }
+    }
+
     #[test]
     fn add_git_exclude_appends_pattern() {
         let tmp = TempDir::new().unwrap();
@@ -162,4 +534,27 @@ mod tests {
         add_git_exclude(tmp.path(), ".agent-chat/").unwrap();
         assert!(tmp.path().join(".git/info/exclude").exists());
     }
+
+    fn env_of<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |key| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string())
+    }
+
+    #[test]
+    fn home_dir_prefers_home() {
+        let home = home_dir_from(env_of(&[("HOME", "/home/fox"), ("USERPROFILE", r"C:\Users\fox")])).unwrap();
+        assert_eq!(home, PathBuf::from("/home/fox"));
+    }
+
+    #[test]
+    fn home_dir_falls_back_to_userprofile() {
+        let home = home_dir_from(env_of(&[("HOME", ""), ("USERPROFILE", r"C:\Users\fox")])).unwrap();
+        assert_eq!(home, PathBuf::from(r"C:\Users\fox"));
+    }
+
+    #[test]
+    fn home_dir_falls_back_to_homedrive_homepath() {
+        let home = home_dir_from(env_of(&[("HOMEDRIVE", "C:"), ("HOMEPATH", r"\Users\fox")])).unwrap();
+        assert_eq!(home, PathBuf::from(r"C:\Users\fox"));
+        assert!(matches!(home_dir_from(env_of(&[])), Err(AgentChatError::MissingEnv(_))));
+    }
 }
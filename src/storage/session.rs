@@ -1,13 +1,14 @@
 use std::fs;
 use std::path::Path;
 use crate::error::Result;
+use crate::storage::atomic;
 
 /// Write a session mapping: session_id -> friendly_name
 pub fn write_session(sessions_dir: &Path, session_id: &str, name: &str) -> Result<()> {
     let path = sessions_dir.join(session_id);
     let tmp = sessions_dir.join(format!(".tmp.{}", session_id));
     fs::write(&tmp, name)?;
-    fs::rename(&tmp, &path)?;
+    atomic::rename(&tmp, &path)?;
     Ok(())
 }
 
@@ -21,11 +22,78 @@ pub fn read_session(sessions_dir: &Path, session_id: &str) -> Result<Option<Stri
     Ok(Some(name))
 }
 
+/// List all registered sessions as `(session_id, name)` pairs, sorted by session id.
+pub fn list_sessions(sessions_dir: &Path) -> Result<Vec<(String, String)>> {
+    let mut sessions = Vec::new();
+    if !sessions_dir.exists() {
+        return Ok(sessions);
+    }
+    for entry in fs::read_dir(sessions_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let session_id = entry.file_name().to_string_lossy().to_string();
+        if session_id.starts_with(".tmp.") {
+            continue;
+        }
+        if let Ok(name) = fs::read_to_string(entry.path()) {
+            sessions.push((session_id, name.trim().to_string()));
+        }
+    }
+    sessions.sort();
+    Ok(sessions)
+}
+
+/// Check whether any session other than `except_session_id` is using `name`.
+pub fn name_in_use(sessions_dir: &Path, name: &str, except_session_id: &str) -> Result<bool> {
+    if !sessions_dir.exists() {
+        return Ok(false);
+    }
+    for entry in fs::read_dir(sessions_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with(".tmp.") || file_name == except_session_id {
+            continue;
+        }
+        if let Ok(existing) = fs::read_to_string(entry.path()) {
+            if existing.trim() == name {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Return `base` if no session other than `except_session_id` uses it,
+/// otherwise the first free `base-2`, `base-3`, ...
+pub fn first_free_name(sessions_dir: &Path, base: &str, except_session_id: &str) -> Result<String> {
+    let mut candidate = base.to_string();
+    let mut suffix = 2;
+    while name_in_use(sessions_dir, &candidate, except_session_id)? {
+        candidate = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    Ok(candidate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn first_free_name_appends_suffix() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(first_free_name(tmp.path(), "reviewer", "s1").unwrap(), "reviewer");
+        write_session(tmp.path(), "s1", "reviewer").unwrap();
+        // A session's own name doesn't count as taken
+        assert_eq!(first_free_name(tmp.path(), "reviewer", "s1").unwrap(), "reviewer");
+        assert_eq!(first_free_name(tmp.path(), "reviewer", "s2").unwrap(), "reviewer-2");
+        write_session(tmp.path(), "s2", "reviewer-2").unwrap();
+        assert_eq!(first_free_name(tmp.path(), "reviewer", "s3").unwrap(), "reviewer-3");
+    }
+
     #[test]
     fn write_and_read_session() {
         let tmp = TempDir::new().unwrap();
@@ -34,6 +102,30 @@ mod tests {
         assert_eq!(name, Some("swift-fox".to_string()));
     }
 
+    #[test]
+    fn list_sessions_sorted() {
+        let tmp = TempDir::new().unwrap();
+        write_session(tmp.path(), "b-sess", "bold-hawk").unwrap();
+        write_session(tmp.path(), "a-sess", "swift-fox").unwrap();
+        let sessions = list_sessions(tmp.path()).unwrap();
+        assert_eq!(
+            sessions,
+            vec![
+                ("a-sess".to_string(), "swift-fox".to_string()),
+                ("b-sess".to_string(), "bold-hawk".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn name_in_use_ignores_own_session() {
+        let tmp = TempDir::new().unwrap();
+        write_session(tmp.path(), "abc123", "swift-fox").unwrap();
+        assert!(name_in_use(tmp.path(), "swift-fox", "other").unwrap());
+        assert!(!name_in_use(tmp.path(), "swift-fox", "abc123").unwrap());
+        assert!(!name_in_use(tmp.path(), "bold-hawk", "other").unwrap());
+    }
+
     #[test]
     fn read_missing_session() {
         let tmp = TempDir::new().unwrap();
@@ -1,17 +1,32 @@
 use std::io::IsTerminal;
 
+/// Colors are on only for a terminal and when `NO_COLOR` is unset or empty
+/// (https://no-color.org). Machine output (hook JSON, `--json`, porcelain)
+/// never goes through this module.
+fn colors_enabled(is_terminal: bool, no_color: Option<std::ffi::OsString>) -> bool {
+    is_terminal && no_color.is_none_or(|v| v.is_empty())
+}
+
 fn enabled() -> bool {
-    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+    colors_enabled(std::io::stdout().is_terminal(), std::env::var_os("NO_COLOR"))
 }
 
-fn paint(s: &str, code: &str) -> String {
-    if enabled() {
+fn stderr_enabled() -> bool {
+    colors_enabled(std::io::stderr().is_terminal(), std::env::var_os("NO_COLOR"))
+}
+
+fn paint_if(on: bool, s: &str, code: &str) -> String {
+    if on {
         format!("\x1b[{}m{}\x1b[0m", code, s)
     } else {
         s.to_string()
     }
 }
 
+fn paint(s: &str, code: &str) -> String {
+    paint_if(enabled(), s, code)
+}
+
 pub fn success_line(label: &str, value: &str) -> String {
     let mark = paint("✓", "32");
     let label = paint(label, "1;32");
@@ -24,11 +39,38 @@ pub fn info_line(label: &str, value: &str) -> String {
     format!("{} {} {}", mark, label, value)
 }
 
+/// A warning for stderr (lock conflicts, focus overlaps), yellow on a terminal.
+pub fn warning(text: &str) -> String {
+    paint_if(stderr_enabled(), text, "33")
+}
+
 pub fn table_header(a: &str, b: &str, c: Option<&str>) -> String {
-    let a = paint(a, "1");
-    let b = paint(b, "1");
+    // Pad before painting so escape codes don't throw off the column widths
     match c {
-        Some(c) => format!("{:<30} {:<15} {}", a, b, paint(c, "1")),
-        None => format!("{:<15} {}", a, b),
+        Some(c) => paint(&format!("{:<30} {:<15} {}", a, b, c), "2"),
+        None => paint(&format!("{:<15} {}", a, b), "2"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_color_yields_plain_strings() {
+        assert!(!colors_enabled(true, Some("1".into())));
+        assert!(colors_enabled(true, Some("".into())));
+        assert!(colors_enabled(true, None));
+        assert!(!colors_enabled(false, None));
+
+        assert_eq!(paint_if(false, "Locked:", "1;32"), "Locked:");
+        assert_eq!(paint_if(true, "Locked:", "1;32"), "\x1b[1;32mLocked:\x1b[0m");
+    }
+
+    #[test]
+    fn lines_are_plain_off_a_terminal() {
+        // Test output is captured, so stdout is never a terminal here
+        assert_eq!(success_line("Locked:", "src/*.rs"), "✓ Locked: src/*.rs");
+        assert_eq!(table_header("NAME", "FOCUS", None), format!("{:<15} {}", "NAME", "FOCUS"));
     }
 }
@@ -0,0 +1,97 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use filetime::FileTime;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+/// Seed a session with explicit registration and last-activity times (unix secs).
+fn seed_agent(tmp: &TempDir, session_id: &str, name: &str, joined: i64, last_seen: Option<i64>) {
+    let root = tmp.path().join(".agent-chat");
+    let session_file = root.join("sessions").join(session_id);
+    std::fs::write(&session_file, name).unwrap();
+    filetime::set_file_mtime(&session_file, FileTime::from_unix_time(joined, 0)).unwrap();
+    if let Some(seen) = last_seen {
+        let cursor = root.join("cursors").join(session_id);
+        std::fs::write(&cursor, "").unwrap();
+        filetime::set_file_mtime(&cursor, FileTime::from_unix_time(seen, 0)).unwrap();
+    }
+}
+
+fn roster(tmp: &TempDir, sort: Option<&str>) -> Vec<String> {
+    let mut args = vec!["agents", "--porcelain"];
+    if let Some(sort) = sort {
+        args.extend(["--sort", sort]);
+    }
+    let output = cmd().args(&args).current_dir(tmp.path()).output().unwrap();
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.split('\t').nth(1).unwrap().to_string())
+        .collect()
+}
+
+fn seeded() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    seed_agent(&tmp, "s1", "calm-owl", 1_000, Some(5_000));
+    seed_agent(&tmp, "s2", "bold-hawk", 3_000, Some(9_000));
+    seed_agent(&tmp, "s3", "swift-fox", 2_000, None);
+    tmp
+}
+
+#[test]
+fn agents_sort_defaults_to_name() {
+    let tmp = seeded();
+    assert_eq!(roster(&tmp, None), ["bold-hawk", "calm-owl", "swift-fox"]);
+    assert_eq!(roster(&tmp, Some("name")), ["bold-hawk", "calm-owl", "swift-fox"]);
+}
+
+#[test]
+fn agents_sort_by_activity_uses_presence_mtime() {
+    let tmp = seeded();
+    assert_eq!(roster(&tmp, Some("activity")), ["bold-hawk", "calm-owl", "swift-fox"]);
+
+    // calm-owl becomes the most recently active
+    let cursor = tmp.path().join(".agent-chat/cursors/s1");
+    filetime::set_file_mtime(&cursor, FileTime::from_unix_time(20_000, 0)).unwrap();
+    assert_eq!(roster(&tmp, Some("activity")), ["calm-owl", "bold-hawk", "swift-fox"]);
+}
+
+#[test]
+fn agents_sort_by_joined() {
+    let tmp = seeded();
+    assert_eq!(roster(&tmp, Some("joined")), ["calm-owl", "swift-fox", "bold-hawk"]);
+}
+
+#[test]
+fn agents_table_shows_focus_and_idle() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    seed_agent(&tmp, "s1", "calm-owl", 1_000, Some(now));
+    seed_agent(&tmp, "s2", "bold-hawk", 1_000, Some(now - 3_600));
+
+    cmd()
+        .args(["focus", "CI pipeline"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "s1")
+        .assert()
+        .success();
+
+    let output = cmd().arg("agents").current_dir(tmp.path()).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let owl = stdout.lines().find(|l| l.contains("calm-owl")).unwrap();
+    let hawk = stdout.lines().find(|l| l.contains("bold-hawk")).unwrap();
+    assert!(owl.contains("CI pipeline"), "got: {}", stdout);
+    assert!(!owl.contains("(idle)"), "got: {}", stdout);
+    assert!(hawk.contains("(idle)"), "got: {}", stdout);
+}
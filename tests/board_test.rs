@@ -0,0 +1,76 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+fn as_agent(tmp: &TempDir, name: &str, sid: &str, args: &[&str]) {
+    cmd()
+        .args(args)
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", name)
+        .env("AGENT_CHAT_SESSION_ID", sid)
+        .assert()
+        .success();
+}
+
+#[test]
+fn board_shows_lock_focus_and_status_under_agent() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    as_agent(&tmp, "swift-fox", "sess1", &["lock", "src/auth/*.rs"]);
+    as_agent(&tmp, "swift-fox", "sess1", &["focus", "auth middleware"]);
+    as_agent(&tmp, "swift-fox", "sess1", &["say", "--status", "working", "token refresh"]);
+    as_agent(&tmp, "bold-hawk", "sess2", &["focus", "docs"]);
+
+    let out = cmd().arg("board").current_dir(tmp.path()).output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+
+    let fox = stdout.split("\n\n").find(|b| b.starts_with("swift-fox")).expect(&stdout);
+    assert!(fox.contains("src/auth/*.rs"), "board: {}", stdout);
+    assert!(fox.contains("focus:  auth middleware"), "board: {}", stdout);
+    assert!(fox.contains("🚧 working: token refresh"), "board: {}", stdout);
+
+    let hawk = stdout.split("\n\n").find(|b| b.starts_with("bold-hawk")).expect(&stdout);
+    assert!(hawk.contains("focus:  docs"));
+    assert!(hawk.contains("locks:  -"));
+}
+
+#[test]
+fn board_json() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    as_agent(&tmp, "swift-fox", "sess1", &["lock", "src/*.rs"]);
+    as_agent(&tmp, "swift-fox", "sess1", &["focus", "CI pipeline"]);
+
+    let out = cmd().args(["board", "--json"]).current_dir(tmp.path()).output().unwrap();
+    let val: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let agent = &val["agents"][0];
+    assert_eq!(agent["name"], "swift-fox");
+    assert_eq!(agent["session_id"], "sess1");
+    assert_eq!(agent["focus"], "CI pipeline");
+    assert_eq!(agent["locks"][0]["glob"], "src/*.rs");
+    assert!(agent["status"].is_null());
+}
+
+#[test]
+fn board_empty() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .arg("board")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No registered agents"));
+}
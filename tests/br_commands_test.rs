@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 /// Set up a temp dir with .agent-chat/ initialized
@@ -78,3 +78,173 @@ fn br_complete_fails_without_agent_chat_dir() {
         .failure()
         .stderr(predicate::str::contains("Not initialized"));
 }
+
+// ── br-list annotates ready beads ────────────────────────────────────
+
+/// Put a fake `br` running `body` (plain sh, builtins only) on PATH; returns the PATH.
+#[cfg(unix)]
+fn fake_br_script(tmp: &TempDir, body: &str) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let bin = tmp.path().join("bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let script = bin.join("br");
+    std::fs::write(&script, format!("#!/bin/sh\n{}\nexit 0\n", body)).unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    bin.display().to_string()
+}
+
+/// Put a fake `br` on PATH that prints `ready_json` for `br ready --json`.
+#[cfg(unix)]
+fn fake_br(tmp: &TempDir, ready_json: &str) -> String {
+    fake_br_script(tmp, &format!("if [ \"$1\" = ready ]; then echo '{}'; fi", ready_json))
+}
+
+#[cfg(unix)]
+#[test]
+fn br_list_shows_claims_and_focus_overlaps() {
+    let tmp = setup_initialized();
+    std::fs::create_dir_all(tmp.path().join(".agent-chat/focuses")).unwrap();
+    let path = fake_br(
+        &tmp,
+        r#"[{"id":"bd-1","title":"Fix CI pipeline"},{"id":"bd-2","title":"Write docs"},{"id":"bd-3","title":"Refactor parser"}]"#,
+    );
+
+    for (name, sid, msg) in [
+        ("swift-fox", "sess1", "starting br-bd-2: Write docs"),
+        ("bold-hawk", "sess2", "starting br-bd-3: Refactor parser"),
+        ("bold-hawk", "sess2", "completed br-bd-3: Refactor parser"),
+    ] {
+        cmd()
+            .args(["say", msg])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", name)
+            .env("AGENT_CHAT_SESSION_ID", sid)
+            .assert()
+            .success();
+    }
+    cmd()
+        .args(["focus", "CI pipeline"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    let output = cmd()
+        .arg("br-list")
+        .current_dir(tmp.path())
+        .env("PATH", &path)
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |id: &str| stdout.lines().find(|l| l.starts_with(id)).unwrap_or_else(|| panic!("no {} in {}", id, stdout)).to_string();
+
+    assert!(line("bd-1").contains("[bold-hawk focused on 'CI pipeline', 100%]"), "{}", stdout);
+    assert!(line("bd-2").contains("swift-fox"), "{}", stdout);
+    assert!(!line("bd-3").contains("bold-hawk"), "completed claims are released: {}", stdout);
+}
+
+#[cfg(unix)]
+#[test]
+fn br_list_handles_no_ready_issues() {
+    let tmp = setup_initialized();
+    let path = fake_br(&tmp, "[]");
+
+    cmd()
+        .arg("br-list")
+        .current_dir(tmp.path())
+        .env("PATH", &path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No ready beads."));
+}
+
+#[test]
+fn br_list_errors_when_br_not_in_path() {
+    let tmp = setup_initialized();
+
+    cmd()
+        .arg("br-list")
+        .env("PATH", "/nonexistent")
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("br (beads_rust) not found"));
+}
+
+// ── br-complete exports the JSONL ────────────────────────────────────
+
+#[cfg(unix)]
+fn fake_br_for_complete(tmp: &TempDir, sync_exit: u8) -> String {
+    let calls = tmp.path().join("br-calls");
+    fake_br_script(
+        tmp,
+        &format!(
+            "echo \"$@\" >> '{}'\n\
+             if [ \"$1\" = show ]; then echo '{{\"title\":\"Fix CI\"}}'; fi\n\
+             if [ \"$1\" = sync ]; then echo 'db locked' >&2; exit {}; fi",
+            calls.display(),
+            sync_exit
+        ),
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn br_complete_runs_sync_flush() {
+    let tmp = setup_initialized();
+    let path = fake_br_for_complete(&tmp, 0);
+
+    cmd()
+        .args(["br-complete", "bd-1"])
+        .current_dir(tmp.path())
+        .env("PATH", &path)
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    let calls = std::fs::read_to_string(tmp.path().join("br-calls")).unwrap();
+    assert!(calls.contains("close bd-1\nsync --flush-only"), "{}", calls);
+}
+
+#[cfg(unix)]
+#[test]
+fn br_complete_sync_failure_is_only_a_warning() {
+    let tmp = setup_initialized();
+    let path = fake_br_for_complete(&tmp, 1);
+
+    cmd()
+        .args(["br-complete", "bd-1"])
+        .current_dir(tmp.path())
+        .env("PATH", &path)
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("br sync failed: db locked"));
+
+    // Completion is still announced
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn br_complete_skips_sync_when_disabled() {
+    let tmp = setup_initialized();
+    std::fs::write(tmp.path().join(".agent-chat/config.toml"), "br_auto_sync = false\n").unwrap();
+    let path = fake_br_for_complete(&tmp, 0);
+
+    cmd()
+        .args(["br-complete", "bd-1"])
+        .current_dir(tmp.path())
+        .env("PATH", &path)
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let calls = std::fs::read_to_string(tmp.path().join("br-calls")).unwrap();
+    assert!(!calls.contains("sync"), "{}", calls);
+}
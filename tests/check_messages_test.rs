@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -136,7 +136,7 @@ fn check_messages_includes_message_content() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     // Parse the JSON to check structure
     let json: serde_json::Value = serde_json::from_str(&stdout)
-        .expect(&format!("Expected valid JSON but got: {}", stdout));
+        .unwrap_or_else(|_| panic!("Expected valid JSON but got: {}", stdout));
 
     let context = json["hookSpecificOutput"]["additionalContext"]
         .as_str()
@@ -147,3 +147,112 @@ fn check_messages_includes_message_content() {
     assert!(context.contains("second message"), "Expected 'second message' in: {}", context);
     assert!(context.contains("swift-fox"), "Expected 'swift-fox' in: {}", context);
 }
+
+#[test]
+fn check_messages_digests_large_backlog() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let check = || {
+        let output = cmd()
+            .arg("check-messages")
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", "sess2")
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["hookSpecificOutput"]["additionalContext"].as_str().unwrap().to_string()
+    };
+    let write_backlog = |count: usize| {
+        let log_dir = tmp.path().join(".agent-chat/log");
+        let start = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let authors = ["swift-fox", "calm-owl", "keen-lynx"];
+        for i in 0..count {
+            let content = format!("name: {}\nbacklog message {}\n", authors[i % 3], i);
+            std::fs::write(log_dir.join(format!("{}.md", start + i as u128)), content).unwrap();
+        }
+    };
+
+    // Establish a cursor so the backlog isn't capped by the first-read window
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+    std::thread::sleep(std::time::Duration::from_millis(10));
+
+    // Small backlog: full bodies
+    write_backlog(2);
+    let context = check();
+    assert!(context.contains("backlog message 0"), "got: {}", context);
+    assert!(context.contains("backlog message 1"), "got: {}", context);
+    std::thread::sleep(std::time::Duration::from_millis(10));
+
+    // Large backlog: one-line digest, left unread
+    write_backlog(12);
+    let context = check();
+    assert_eq!(
+        context,
+        "[agent-chat: 12 unread messages from 3 agents — run `agent-chat read` to see them]"
+    );
+
+    // Shown once per backlog: the next call is silent until more arrives
+    let output = cmd()
+        .arg("check-messages")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    assert!(output.stdout.is_empty(), "{}", String::from_utf8_lossy(&output.stdout));
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    write_backlog(1);
+    assert!(check().starts_with("[agent-chat: 13 unread messages"));
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("backlog message 11"));
+}
+
+#[test]
+fn check_messages_digest_threshold_is_configurable() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("check_messages_digest_threshold = 10", "check_messages_digest_threshold = 1"),
+    )
+    .unwrap();
+
+    for msg in ["one", "two"] {
+        cmd()
+            .args(["say", msg])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    }
+
+    cmd()
+        .arg("check-messages")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 unread messages from 1 agent "));
+}
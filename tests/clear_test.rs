@@ -0,0 +1,144 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+fn as_fox(tmp: &TempDir, args: &[&str]) {
+    cmd()
+        .args(args)
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+}
+
+fn count_files(dir: &std::path::Path) -> usize {
+    std::fs::read_dir(dir).map(|d| d.count()).unwrap_or(0)
+}
+
+fn count_focuses(tmp: &TempDir) -> usize {
+    std::fs::read_dir(tmp.path().join(".agent-chat/focuses"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().ends_with(".focus"))
+        .count()
+}
+
+/// A project with one registered session, a few messages, a lock, a focus, and a cursor.
+fn busy_project() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    cmd()
+        .args(["register", "--session-id", "sess1", "--name", "swift-fox"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    as_fox(&tmp, &["say", "first"]);
+    as_fox(&tmp, &["say", "second"]);
+    as_fox(&tmp, &["lock", "src/*.rs"]);
+    as_fox(&tmp, &["focus", "parser"]);
+    as_fox(&tmp, &["mute", "chatty-jay"]);
+    tmp
+}
+
+#[test]
+fn clear_messages_keeps_sessions_and_config() {
+    let tmp = busy_project();
+    let data = tmp.path().join(".agent-chat");
+    let config = std::fs::read_to_string(data.join("config.toml")).unwrap();
+    std::fs::write(data.join("log/.tmp.inflight.md"), "partial").unwrap();
+
+    cmd()
+        .args(["clear", "--messages", "--yes"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared: 3 message(s)"));
+
+    assert_eq!(count_files(&data.join("log")), 1, "only the .tmp. file is left");
+    assert!(!data.join("log.index").exists());
+    assert_eq!(std::fs::read_to_string(data.join("sessions/sess1")).unwrap().trim(), "swift-fox");
+    assert_eq!(std::fs::read_to_string(data.join("config.toml")).unwrap(), config);
+    assert_eq!(count_files(&data.join("locks")), 1);
+    assert_eq!(count_focuses(&tmp), 1);
+
+    cmd()
+        .args(["read", "--all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("first").not());
+
+    // Posting still works afterwards
+    as_fox(&tmp, &["say", "fresh start"]);
+}
+
+#[test]
+fn clear_each_subsystem_independently() {
+    let tmp = busy_project();
+    let data = tmp.path().join(".agent-chat");
+
+    cmd()
+        .args(["clear", "--locks", "--focuses", "-y"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared: 1 lock(s)"))
+        .stdout(predicate::str::contains("Cleared: 1 focus(es)"));
+    assert_eq!(count_files(&data.join("locks")), 0);
+    assert_eq!(count_focuses(&tmp), 0);
+    assert!(data.join("focuses/sess1.history").exists());
+    assert!(count_files(&data.join("log")) > 0);
+
+    cmd().args(["clear", "--cursors", "--yes"]).current_dir(tmp.path()).assert().success();
+    assert!(!data.join("cursors/sess1").exists());
+    assert!(data.join("cursors/sess1.mutes").exists());
+}
+
+#[test]
+fn clear_asks_for_confirmation() {
+    let tmp = busy_project();
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let before = count_files(&log_dir);
+
+    for answer in ["n\n", ""] {
+        cmd()
+            .args(["clear", "--messages"])
+            .current_dir(tmp.path())
+            .write_stdin(answer)
+            .assert()
+            .success()
+            .stderr(predicate::str::contains("Clear messages in"))
+            .stdout(predicate::str::contains("Aborted"));
+        assert_eq!(count_files(&log_dir), before);
+    }
+
+    cmd()
+        .args(["clear", "--messages"])
+        .current_dir(tmp.path())
+        .write_stdin("y\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared:"));
+    assert_eq!(count_files(&log_dir), 0);
+}
+
+#[test]
+fn clear_requires_a_target() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    cmd()
+        .args(["clear", "--yes"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("Specify what to clear"));
+}
@@ -0,0 +1,120 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+#[test]
+fn compact_archives_old_messages_and_history_shows_them() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // An old message (January 2025) and a fresh one
+    let log_dir = tmp.path().join(".agent-chat/log");
+    std::fs::write(log_dir.join("1736950200000000000.md"), "name: swift-fox\nancient news\n").unwrap();
+    cmd()
+        .args(["say", "fresh news"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["compact", "--older-than", "1d"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 message(s) archived"));
+
+    assert!(!log_dir.join("1736950200000000000.md").exists());
+    assert!(tmp.path().join(".agent-chat/archive/2025-01-15.md").exists());
+
+    cmd()
+        .arg("history")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fresh news"))
+        .stdout(predicate::str::contains("ancient news").not());
+
+    let output = cmd()
+        .args(["history", "--include-archive"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains("ancient news"));
+    assert!(lines[1].contains("fresh news"));
+}
+
+#[test]
+fn compact_rejects_bad_duration() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["compact", "--older-than", "soon"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("invalid duration"));
+}
+
+#[test]
+fn compact_keeps_messages_unread_by_a_registered_session() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["register", "--session-id", "sess1"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // Two old messages; sess1 has read only the first
+    let log_dir = tmp.path().join(".agent-chat/log");
+    std::fs::write(log_dir.join("1736950200000000000.md"), "name: bold-hawk\nread already\n").unwrap();
+    std::fs::write(log_dir.join("1736950300000000000.md"), "name: bold-hawk\nstill unread\n").unwrap();
+    std::fs::write(tmp.path().join(".agent-chat/cursors/sess1"), "1736950250000000000\n").unwrap();
+
+    cmd()
+        .args(["compact", "--older-than", "1d"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 message(s) archived"))
+        .stdout(predicate::str::contains("Kept 1 message(s)"));
+
+    assert!(!log_dir.join("1736950200000000000.md").exists());
+    assert!(log_dir.join("1736950300000000000.md").exists());
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("still unread"))
+        .stdout(predicate::str::contains("read already").not());
+
+    // --force ignores cursors; --delete skips the archive
+    std::fs::write(tmp.path().join(".agent-chat/cursors/sess1"), "1736950250000000000\n").unwrap();
+    cmd()
+        .args(["compact", "--older-than", "1d", "--delete", "--force"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 message(s) deleted"));
+
+    assert!(!log_dir.join("1736950300000000000.md").exists());
+    let archive = std::fs::read_to_string(tmp.path().join(".agent-chat/archive/2025-01-15.md")).unwrap();
+    assert!(!archive.contains("still unread"));
+}
@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use std::thread;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -20,12 +20,11 @@ fn concurrent_say_no_corruption() {
         .map(|i| {
             let d = dir.clone();
             thread::spawn(move || {
-                Command::cargo_bin("agent-chat")
-                    .unwrap()
+                cmd()
                     .args(["say", &format!("message from thread {}", i)])
                     .current_dir(&d)
-                    .env("AGENT_CHAT_NAME", &format!("agent-{}", i))
-                    .env("AGENT_CHAT_SESSION_ID", &format!("sess-{}", i))
+                    .env("AGENT_CHAT_NAME", format!("agent-{}", i))
+                    .env("AGENT_CHAT_SESSION_ID", format!("sess-{}", i))
                     .assert()
                     .success();
             })
@@ -66,12 +65,11 @@ fn concurrent_lock_race() {
         .map(|i| {
             let d = dir.clone();
             thread::spawn(move || {
-                let output = Command::cargo_bin("agent-chat")
-                    .unwrap()
+                let output = cmd()
                     .args(["lock", "src/*.rs"])
                     .current_dir(&d)
-                    .env("AGENT_CHAT_NAME", &format!("agent-{}", i))
-                    .env("AGENT_CHAT_SESSION_ID", &format!("lock-sess-{}", i))
+                    .env("AGENT_CHAT_NAME", format!("agent-{}", i))
+                    .env("AGENT_CHAT_SESSION_ID", format!("lock-sess-{}", i))
                     .output()
                     .unwrap();
                 let stdout = String::from_utf8_lossy(&output.stdout);
@@ -127,12 +125,11 @@ fn concurrent_read_write_no_panic() {
     for i in 0..5 {
         let d = dir.clone();
         threads.push(thread::spawn(move || {
-            Command::cargo_bin("agent-chat")
-                .unwrap()
+            cmd()
                 .args(["say", &format!("concurrent-msg-{}", i)])
                 .current_dir(&d)
-                .env("AGENT_CHAT_NAME", &format!("writer-{}", i))
-                .env("AGENT_CHAT_SESSION_ID", &format!("writer-sess-{}", i))
+                .env("AGENT_CHAT_NAME", format!("writer-{}", i))
+                .env("AGENT_CHAT_SESSION_ID", format!("writer-sess-{}", i))
                 .assert()
                 .success();
         }));
@@ -140,12 +137,11 @@ fn concurrent_read_write_no_panic() {
     for i in 0..5 {
         let d = dir.clone();
         threads.push(thread::spawn(move || {
-            Command::cargo_bin("agent-chat")
-                .unwrap()
+            cmd()
                 .arg("read")
                 .current_dir(&d)
-                .env("AGENT_CHAT_NAME", &format!("reader-{}", i))
-                .env("AGENT_CHAT_SESSION_ID", &format!("reader-sess-{}", i))
+                .env("AGENT_CHAT_NAME", format!("reader-{}", i))
+                .env("AGENT_CHAT_SESSION_ID", format!("reader-sess-{}", i))
                 .assert()
                 .success();
         }));
@@ -155,3 +151,94 @@ fn concurrent_read_write_no_panic() {
         t.join().unwrap();
     }
 }
+
+#[test]
+fn concurrent_register_assigns_distinct_names() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let dir = tmp.path().to_path_buf();
+    let threads: Vec<_> = (0..20)
+        .map(|i| {
+            let d = dir.clone();
+            thread::spawn(move || {
+                // Everyone asks for the same name, the worst case for collisions
+                cmd()
+                    .args(["register", "--session-id", &format!("sess-{}", i), "--name", "worker"])
+                    .current_dir(&d)
+                    .assert()
+                    .success();
+            })
+        })
+        .collect();
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    let generated: Vec<_> = (0..20)
+        .map(|i| {
+            let d = dir.clone();
+            thread::spawn(move || {
+                cmd()
+                    .args(["register", "--session-id", &format!("gen-{}", i)])
+                    .current_dir(&d)
+                    .env_remove("AGENT_CHAT_PREFERRED_NAME")
+                    .assert()
+                    .success();
+            })
+        })
+        .collect();
+    for t in generated {
+        t.join().unwrap();
+    }
+
+    let sessions_dir = tmp.path().join(".agent-chat/sessions");
+    let names: Vec<String> = std::fs::read_dir(&sessions_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_name().to_string_lossy().starts_with(".tmp."))
+        .map(|e| std::fs::read_to_string(e.path()).unwrap().trim().to_string())
+        .collect();
+    assert_eq!(names.len(), 40);
+    let unique: std::collections::HashSet<_> = names.iter().collect();
+    assert_eq!(unique.len(), 40, "duplicate names assigned: {:?}", names);
+    assert!(!tmp.path().join(".agent-chat/sessions.lock").exists());
+}
+
+#[test]
+fn numbered_name_style_is_sequential_under_concurrency() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("name_style = \"animal\"", "name_style = \"numbered\"")).unwrap();
+
+    let dir = tmp.path().to_path_buf();
+    let threads: Vec<_> = (0..10)
+        .map(|i| {
+            let d = dir.clone();
+            thread::spawn(move || {
+                cmd()
+                    .args(["register", "--session-id", &format!("sess-{}", i)])
+                    .current_dir(&d)
+                    .env_remove("AGENT_CHAT_PREFERRED_NAME")
+                    .assert()
+                    .success();
+            })
+        })
+        .collect();
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    let sessions_dir = tmp.path().join(".agent-chat/sessions");
+    let mut names: Vec<String> = std::fs::read_dir(&sessions_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| std::fs::read_to_string(e.path()).unwrap().trim().to_string())
+        .collect();
+    names.sort_by_key(|n| n.trim_start_matches("agent-").parse::<usize>().unwrap());
+    let expected: Vec<String> = (1..=10).map(|n| format!("agent-{}", n)).collect();
+    assert_eq!(names, expected);
+}
@@ -0,0 +1,47 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+#[test]
+fn diff_shows_only_messages_in_window() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    std::fs::write(log_dir.join("1000000000000000000.md"), "name: swift-fox\nbefore\n").unwrap();
+    std::fs::write(log_dir.join("1000000001000000000.md"), "name: swift-fox\nstart\n").unwrap();
+    std::fs::write(log_dir.join("1000000002000000000.md"), "name: bold-hawk\nmiddle\n").unwrap();
+    std::fs::write(log_dir.join("1000000003000000000.md"), "name: swift-fox\nend\n").unwrap();
+    std::fs::write(log_dir.join("1000000004000000000.md"), "name: bold-hawk\nafter\n").unwrap();
+
+    cmd()
+        .args(["diff", "1000000001000000000", "1000000003000000000"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("start"))
+        .stdout(predicate::str::contains("middle"))
+        .stdout(predicate::str::contains("end"))
+        .stdout(predicate::str::contains("before").not())
+        .stdout(predicate::str::contains("after").not());
+}
+
+#[test]
+fn diff_rejects_bad_point() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["diff", "whenever", "1000000003000000000"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("invalid point"));
+}
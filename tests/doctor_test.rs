@@ -0,0 +1,118 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+#[test]
+fn doctor_passes_on_fresh_install() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .arg("doctor")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[pass] data"))
+        .stdout(predicate::str::contains("[pass] config"))
+        .stdout(predicate::str::contains("[pass] hooks"))
+        .stdout(predicate::str::contains("[pass] claude.md"))
+        .stdout(predicate::str::contains("FAIL").not());
+}
+
+#[test]
+fn doctor_fails_without_data_dir() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd()
+        .arg("doctor")
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_ROOT")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[FAIL] data"))
+        .stdout(predicate::str::contains("agent-chat init"));
+}
+
+#[test]
+fn doctor_fails_on_missing_subdir_and_bad_config() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    std::fs::remove_dir_all(tmp.path().join(".agent-chat/cursors")).unwrap();
+    std::fs::write(tmp.path().join(".agent-chat/config.toml"), "lock_ttl_secs = \"soon\"\n").unwrap();
+
+    cmd()
+        .arg("doctor")
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[FAIL] data: missing cursors"))
+        .stdout(predicate::str::contains("[FAIL] config"))
+        .stderr(predicate::str::contains("2 check(s) failed"));
+}
+
+#[test]
+fn doctor_fails_when_hook_binary_is_gone() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let path = tmp.path().join(".claude/settings.local.json");
+    let settings = std::fs::read_to_string(&path).unwrap();
+    let bin = assert_cmd::cargo::cargo_bin!("agent-chat").display().to_string();
+    std::fs::write(&path, settings.replace(&bin, "/nonexistent/bin/agent-chat")).unwrap();
+
+    cmd()
+        .arg("doctor")
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[FAIL] hooks: hook binary /nonexistent/bin/agent-chat does not exist"));
+}
+
+#[test]
+fn doctor_warns_without_hooks_and_flags_broken_claude_md() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    std::fs::remove_file(tmp.path().join(".claude/settings.local.json")).unwrap();
+    std::fs::write(tmp.path().join("CLAUDE.md"), "<!-- agent-chat:start -->\n# Agent Chat\n").unwrap();
+
+    cmd()
+        .arg("doctor")
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[warn] hooks"))
+        .stdout(predicate::str::contains("[FAIL] claude.md: agent-chat section has mismatched"))
+        .stderr(predicate::str::contains("1 check(s) failed"));
+}
+
+#[test]
+fn doctor_root_flag_checks_that_project() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let elsewhere = TempDir::new().unwrap();
+
+    cmd()
+        .args(["doctor", "--root", tmp.path().to_str().unwrap()])
+        .current_dir(elsewhere.path())
+        .env_remove("AGENT_CHAT_ROOT")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[pass] data"))
+        .stdout(predicate::str::contains("[pass] claude.md"));
+
+    // The flag wins over AGENT_CHAT_ROOT, even when it names a project without a chat
+    cmd()
+        .args(["--root", elsewhere.path().to_str().unwrap(), "doctor"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_ROOT", tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("[FAIL] data"));
+}
@@ -0,0 +1,115 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+#[test]
+fn export_anonymize_uses_stable_aliases() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    std::fs::write(log_dir.join("1736950200000000000.md"), "name: swift-fox\nstarting on /home/kim/app/src/auth.rs\n").unwrap();
+    std::fs::write(log_dir.join("1736950260000000000.md"), "name: bold-hawk\n@swift-fox I'll take billing\n").unwrap();
+    std::fs::write(log_dir.join("1736950320000000000.md"), "name: swift-fox\nthanks bold-hawk, cc calm-owl\n").unwrap();
+    std::fs::write(log_dir.join("1736950380000000000.md"), "name: calm-owl\nhi all\n").unwrap();
+
+    let output = cmd()
+        .args(["export", "--anonymize", "--redact", "/home/**"])
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env_remove("AGENT_CHAT_SESSION_ID")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!stdout.contains("swift-fox"), "got: {}", stdout);
+    assert!(!stdout.contains("bold-hawk"), "got: {}", stdout);
+    assert!(!stdout.contains("calm-owl"), "got: {}", stdout);
+    assert!(!stdout.contains("/home/kim"), "got: {}", stdout);
+
+    let headings: Vec<&str> = stdout.lines().filter(|l| l.starts_with("### ")).collect();
+    assert_eq!(headings.len(), 4);
+    assert!(headings[0].starts_with("### agent-1 — "));
+    assert!(headings[1].starts_with("### agent-2 — "));
+    assert!(headings[2].starts_with("### agent-1 — "));
+    assert!(stdout.contains("starting on [redacted]"));
+    assert!(stdout.contains("@agent-1 I'll take billing"));
+    assert!(stdout.contains("thanks agent-2, cc agent-3"));
+    assert!(headings[3].starts_with("### agent-3 — "));
+}
+
+#[test]
+fn export_keeps_names_by_default() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    std::fs::write(log_dir.join("1736950200000000000.md"), "name: swift-fox\nhello\n").unwrap();
+
+    let output = cmd().arg("export").current_dir(tmp.path()).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("# agent-chat transcript\n"));
+    assert!(stdout.contains("### swift-fox — 2025-01-1"));
+    assert!(stdout.contains("\nhello\n"));
+}
+
+#[test]
+fn export_json_to_file_drops_joins_unless_asked() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    std::fs::write(log_dir.join("1736950200000000000.md"), "name: swift-fox\njoined the chat\n").unwrap();
+    std::fs::write(log_dir.join("1736950260000000000.md"), "name: swift-fox\npriority: high\nstatus: working\nauth refactor\n").unwrap();
+    std::fs::write(log_dir.join("1736950320000000000.md"), "name: bold-hawk\nreply_to: 1736950260000000000\nlooks good\n").unwrap();
+
+    let out = tmp.path().join("chat.json");
+    cmd()
+        .args(["export", "--json", "--out"])
+        .arg(&out)
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env_remove("AGENT_CHAT_SESSION_ID")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Exported: 2 message(s)"));
+
+    let exported: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+    let entries = exported.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0]["id"], "1736950260000000000");
+    assert_eq!(entries[0]["name"], "swift-fox");
+    assert_eq!(entries[0]["timestamp"], "2025-01-15T14:11:00Z");
+    assert_eq!(entries[0]["body"], "auth refactor");
+    assert_eq!(entries[0]["priority"], "high");
+    assert_eq!(entries[0]["status"], "working");
+    assert_eq!(entries[1]["reply_to"], "1736950260000000000");
+
+    let output = cmd()
+        .args(["export", "--json", "--include-joins"])
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env_remove("AGENT_CHAT_SESSION_ID")
+        .output()
+        .unwrap();
+    let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(exported.as_array().unwrap().len(), 3);
+    assert_eq!(exported[0]["body"], "joined the chat");
+}
+
+#[test]
+fn export_json_of_empty_chat_is_an_empty_array() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let output = cmd().args(["export", "--json"]).current_dir(tmp.path()).output().unwrap();
+    let exported: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(exported, serde_json::json!([]));
+}
@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -147,3 +147,240 @@ fn focuses_empty() {
         .success()
         .stdout(predicate::str::contains("No active focuses"));
 }
+
+#[test]
+fn focus_set_warns_about_overlapping_focus() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["focus", "CI pipeline"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    cmd()
+        .args(["focus", "pipeline caching"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "swift-fox is focused on 'CI pipeline' — 50% overlap with 'pipeline caching'",
+        ));
+}
+
+#[test]
+fn focuses_history_keeps_past_areas() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for args in [vec!["focus", "CI pipeline"], vec!["focus", "API work"], vec!["focus", "--clear"]] {
+        cmd()
+            .args(&args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    }
+
+    let output = cmd()
+        .args(["focuses", "--history"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "swift-fox (sess1)");
+    assert!(lines[1].ends_with("  CI pipeline"), "got: {}", stdout);
+    assert!(lines[2].ends_with("  API work"), "got: {}", stdout);
+    assert!(lines[3].ends_with("  (cleared)"), "got: {}", stdout);
+
+    // The active list is unchanged: nothing is focused now
+    cmd()
+        .arg("focuses")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No active focuses"));
+}
+
+#[test]
+fn focus_overlap_min_tokens_silences_incidental_matches() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("focus_overlap_min_tokens = 1", "focus_overlap_min_tokens = 2"),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["focus", "CI pipeline"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["focus", "pipeline refactor"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("WARNING").not());
+
+    cmd()
+        .args(["focus", "CI pipeline flakes"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("WARNING: swift-fox is focused on 'CI pipeline'"));
+}
+
+#[test]
+fn focus_ttl_overrides_config() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["focus", "CI pipeline", "--ttl", "3600"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let output = cmd().args(["focuses", "--json"]).current_dir(tmp.path()).output().unwrap();
+    let focuses: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(focuses[0]["ttl_secs"], 3600);
+
+    cmd()
+        .args(["focus", "CI pipeline", "--ttl", "0"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn focus_zero_ttl_in_config_is_a_clear_error() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("focus_ttl_secs = 300", "focus_ttl_secs = 0")).unwrap();
+
+    cmd()
+        .args(["focus", "CI pipeline"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .stderr(predicate::str::contains("focus_ttl_secs must be a positive number of seconds"));
+
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/focuses")).unwrap().count(), 0);
+}
+
+fn log_bodies(tmp: &TempDir) -> Vec<String> {
+    let mut entries: Vec<_> = std::fs::read_dir(tmp.path().join(".agent-chat/log"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    entries.sort();
+    entries.iter().map(|p| std::fs::read_to_string(p).unwrap()).collect()
+}
+
+#[test]
+fn focus_is_announced_when_enabled() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("announce_focus = false", "announce_focus = true")).unwrap();
+
+    for args in [&["focus", "CI pipeline"][..], &["focus", "--clear"][..]] {
+        cmd()
+            .args(args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    }
+
+    let bodies = log_bodies(&tmp);
+    assert_eq!(bodies.len(), 2);
+    assert!(bodies[0].starts_with("name: swift-fox\n\nfocusing on: CI pipeline"), "{}", bodies[0]);
+    assert!(bodies[1].starts_with("name: swift-fox\n\ncleared focus: CI pipeline"), "{}", bodies[1]);
+}
+
+#[test]
+fn focus_is_not_announced_by_default() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["focus", "CI pipeline"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    assert!(log_bodies(&tmp).is_empty());
+}
+
+#[test]
+fn focus_overlap_min_score_drops_weak_overlaps() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("focus_overlap_min_score = 0.0", "focus_overlap_min_score = 0.6"),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["focus", "CI pipeline"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    // One of two words shared: 50%, below the threshold
+    cmd()
+        .args(["focus", "pipeline refactor"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("swift-fox").not());
+
+    cmd()
+        .args(["focus", "CI pipeline flakes"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("swift-fox is focused on 'CI pipeline' — 100% overlap"));
+}
@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -39,6 +39,115 @@ fn check_lock_warns_on_locked_file() {
         .stdout(predicate::str::contains("swift-fox"));
 }
 
+#[test]
+fn check_lock_warns_on_every_locked_file_in_multiedit() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    cmd()
+        .args(["lock", "docs/*.md"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success();
+
+    let stdin_json = r#"{"tool_name": "MultiEdit", "tool_input": {"file_path": "src/main.rs", "edits": [
+        {"old_string": "foo", "new_string": "bar"},
+        {"file_path": "docs/guide.md", "old_string": "a", "new_string": "b"},
+        {"file_path": "notes.txt", "old_string": "c", "new_string": "d"}
+    ]}}"#;
+
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/main.rs is locked by swift-fox"))
+        .stdout(predicate::str::contains("docs/guide.md is locked by calm-owl"))
+        .stdout(predicate::str::contains("notes.txt").not());
+}
+
+#[test]
+fn check_lock_matches_absolute_path_against_relative_glob() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    std::fs::create_dir(tmp.path().join("src")).unwrap();
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let absolute = tmp.path().join("src").join("main.rs");
+    let stdin_json = serde_json::json!({
+        "tool_name": "Edit",
+        "tool_input": {"file_path": absolute, "old_string": "foo", "new_string": "bar"}
+    })
+    .to_string();
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/main.rs is locked by swift-fox"));
+
+    // A file outside the project is left alone
+    let outside = TempDir::new().unwrap();
+    let stdin_json = serde_json::json!({
+        "tool_name": "Write",
+        "tool_input": {"file_path": outside.path().join("src").join("main.rs"), "content": ""}
+    })
+    .to_string();
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn check_lock_warns_on_locked_notebook() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "notebooks/*.ipynb"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let stdin_json = r#"{"tool_name": "NotebookEdit", "tool_input": {"notebook_path": "notebooks/eda.ipynb", "new_source": "x = 1"}}"#;
+
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("notebooks/eda.ipynb is locked by swift-fox"));
+}
+
 #[test]
 fn check_lock_silent_on_own_lock() {
     let tmp = TempDir::new().unwrap();
@@ -112,3 +221,272 @@ fn hook_stdin_stop_format() {
         .assert()
         .success();
 }
+
+#[test]
+fn check_lock_notifies_owner_when_enabled() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("checklock_notify_owner = false", "checklock_notify_owner = true"),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    // Retried edits warn every time but ping the owner once per lock
+    let stdin_json = r#"{"tool_name": "Edit", "tool_input": {"file_path": "src/main.rs", "old_string": "foo", "new_string": "bar"}}"#;
+    for _ in 0..3 {
+        cmd()
+            .arg("check-lock")
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", "sess2")
+            .write_stdin(stdin_json)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("WARNING"));
+    }
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[bold-hawk"))
+        .stdout(predicate::str::contains("@swift-fox I need to edit src/main.rs").count(1));
+
+    // A fresh lock on the same pattern gets its own notice
+    for args in [&["unlock", "src/*.rs"][..], &["lock", "src/*.rs"][..]] {
+        cmd()
+            .args(args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    }
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success();
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@swift-fox I need to edit src/main.rs").count(1));
+}
+
+#[test]
+fn check_lock_does_not_notify_by_default() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let stdin_json = r#"{"tool_name": "Edit", "tool_input": {"file_path": "src/main.rs", "old_string": "foo", "new_string": "bar"}}"#;
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success();
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    assert_eq!(std::fs::read_dir(log_dir).unwrap().count(), 0);
+}
+
+#[test]
+fn check_lock_blocks_when_enabled() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("check_lock_block = false", "check_lock_block = true"),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let stdin_json = r#"{"tool_name": "Edit", "tool_input": {"file_path": "src/main.rs", "old_string": "foo", "new_string": "bar"}}"#;
+    let output = cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["decision"], "block");
+    assert!(json["reason"].as_str().unwrap().contains("locked by swift-fox"));
+    assert!(json.get("hookSpecificOutput").is_none());
+
+    // Unlocked files stay silent
+    let stdin_json = r#"{"tool_name": "Edit", "tool_input": {"file_path": "docs/a.md", "old_string": "foo", "new_string": "bar"}}"#;
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn agent_chat_root_overrides_cwd_for_hooks() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let elsewhere = TempDir::new().unwrap();
+
+    cmd()
+        .args(["say", "hello from the project"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    // Hook-style invocation from an unrelated cwd
+    let output = cmd()
+        .arg("check-messages")
+        .current_dir(elsewhere.path())
+        .env("AGENT_CHAT_ROOT", tmp.path().join(".agent-chat"))
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("hello from the project"), "got: {}", stdout);
+
+    // An invalid value falls back to the normal walk
+    cmd()
+        .args(["history"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_ROOT", elsewhere.path().join("nope"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello from the project"));
+}
+
+#[test]
+fn nested_checkout_does_not_join_parent_chat() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let nested = tmp.path().join("vendor").join("lib");
+    std::fs::create_dir_all(nested.join(".git")).unwrap();
+
+    cmd()
+        .args(["say", "hello"])
+        .current_dir(&nested)
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .env_remove("AGENT_CHAT_ROOT")
+        .env_remove("AGENT_CHAT_CROSS_REPO")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not initialized"));
+
+    // Opting out shares the parent's chat
+    cmd()
+        .args(["say", "hello"])
+        .current_dir(&nested)
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .env_remove("AGENT_CHAT_ROOT")
+        .env("AGENT_CHAT_CROSS_REPO", "1")
+        .assert()
+        .success();
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 1);
+}
+
+#[test]
+fn check_lock_warns_both_sides_of_expired_lock_takeover() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    // Let swift-fox's lock lapse
+    for entry in std::fs::read_dir(tmp.path().join(".agent-chat/locks")).unwrap() {
+        let path = entry.unwrap().path();
+        let mut lock: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        lock["acquired_at"] = serde_json::json!(0);
+        std::fs::write(&path, lock.to_string()).unwrap();
+    }
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    let stdin_json = r#"{"tool_name": "Edit", "tool_input": {"file_path": "src/main.rs"}}"#;
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("swift-fox's lock on src/*.rs expired and was taken over by bold-hawk"));
+
+    // The new owner hears about it once
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("NOTE: swift-fox's lock on src/*.rs expired"));
+    cmd()
+        .arg("check-lock")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .write_stdin(stdin_json)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
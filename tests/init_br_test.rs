@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 // ── --project ────────────────────────────────────────────────────────
@@ -214,3 +214,28 @@ fn init_br_no_flags_no_stdin_shows_prompt() {
         .stderr(predicate::str::contains("Where should br guidance"))
         .stderr(predicate::str::contains("no input"));
 }
+
+#[test]
+fn init_br_dry_run_writes_nothing() {
+    let tmp = TempDir::new().unwrap();
+    let fake_home = TempDir::new().unwrap();
+    std::fs::create_dir_all(fake_home.path().join(".claude")).unwrap();
+    std::fs::write(
+        fake_home.path().join(".claude/CLAUDE.md"),
+        "<!-- agent-chat-br:start -->\nold\n<!-- agent-chat-br:end -->\n",
+    )
+    .unwrap();
+
+    cmd()
+        .args(["init-br", "--project", "--dry-run"])
+        .env("HOME", fake_home.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would create"))
+        .stdout(predicate::str::contains("would remove the agent-chat section from"))
+        .stdout(predicate::str::contains("Dry run: nothing was written."));
+
+    assert!(!tmp.path().join("CLAUDE.md").exists());
+    assert!(std::fs::read_to_string(fake_home.path().join(".claude/CLAUDE.md")).unwrap().contains("old"));
+}
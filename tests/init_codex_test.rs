@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 #[test]
@@ -75,3 +75,23 @@ fn init_codex_preserves_existing_agents_md() {
     assert!(content.contains("Keep me."));
     assert!(content.contains("<!-- agent-chat-codex:start -->"));
 }
+
+#[test]
+fn init_codex_dry_run_writes_nothing() {
+    let tmp = TempDir::new().unwrap();
+    let fake_home = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init-codex", "--both", "--dry-run"])
+        .env("HOME", fake_home.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would create"))
+        .stdout(predicate::str::contains("AGENTS.md"))
+        .stdout(predicate::str::contains("Dry run: nothing was written."));
+
+    assert!(!tmp.path().join(".agent-chat").exists());
+    assert!(!tmp.path().join("AGENTS.md").exists());
+    assert!(!fake_home.path().join(".codex").exists());
+}
@@ -0,0 +1,103 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+#[test]
+fn init_gemini_project_creates_gemini_md_and_not_claude_files() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--project", "--gemini"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Gemini guidance"));
+
+    assert!(tmp.path().join(".agent-chat/log").is_dir());
+    assert!(tmp.path().join(".agent-chat/config.toml").exists());
+
+    let content = std::fs::read_to_string(tmp.path().join("GEMINI.md")).unwrap();
+    assert!(content.contains("<!-- agent-chat-gemini:start -->"));
+    assert!(content.contains("agent-chat register --session-id"));
+
+    // Gemini init should not install Claude or Codex files.
+    assert!(!tmp.path().join(".claude/settings.local.json").exists());
+    assert!(!tmp.path().join("CLAUDE.md").exists());
+    assert!(!tmp.path().join("AGENTS.md").exists());
+}
+
+#[test]
+fn init_gemini_user_creates_user_gemini_md() {
+    let tmp = TempDir::new().unwrap();
+    let fake_home = TempDir::new().unwrap();
+    std::fs::create_dir(tmp.path().join(".git")).unwrap();
+
+    cmd()
+        .args(["init", "--user", "--gemini"])
+        .env("HOME", fake_home.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    assert!(fake_home.path().join(".gemini/GEMINI.md").exists());
+    assert!(!fake_home.path().join(".claude/settings.json").exists());
+    assert!(!fake_home.path().join(".codex/AGENTS.md").exists());
+
+    let exclude = std::fs::read_to_string(tmp.path().join(".git/info/exclude")).unwrap();
+    assert!(exclude.contains(".agent-chat/"));
+}
+
+#[test]
+fn init_gemini_is_idempotent() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd().args(["init", "--project", "--gemini"]).current_dir(tmp.path()).assert().success();
+    cmd().args(["init", "--project", "--gemini"]).current_dir(tmp.path()).assert().success();
+
+    let content = std::fs::read_to_string(tmp.path().join("GEMINI.md")).unwrap();
+    assert_eq!(content.matches("<!-- agent-chat-gemini:start -->").count(), 1);
+    assert_eq!(content.matches("<!-- agent-chat-gemini:end -->").count(), 1);
+}
+
+#[test]
+fn init_gemini_preserves_existing_gemini_md() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join("GEMINI.md"), "# Existing\n\nKeep me.\n").unwrap();
+
+    cmd().args(["init", "--project", "--gemini"]).current_dir(tmp.path()).assert().success();
+
+    let content = std::fs::read_to_string(tmp.path().join("GEMINI.md")).unwrap();
+    assert!(content.contains("# Existing"));
+    assert!(content.contains("Keep me."));
+    assert!(content.contains("<!-- agent-chat-gemini:start -->"));
+}
+
+#[test]
+fn init_gemini_alongside_both_tools_and_uninstall() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--project", "--both-tools", "--gemini"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed Gemini guidance (project)."));
+
+    assert!(tmp.path().join("CLAUDE.md").exists());
+    assert!(tmp.path().join("AGENTS.md").exists());
+    assert!(tmp.path().join("GEMINI.md").exists());
+
+    cmd()
+        .args(["uninstall-tool", "gemini", "--project"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed Gemini integration (project)."));
+
+    assert!(!tmp.path().join("GEMINI.md").exists());
+    assert!(tmp.path().join("AGENTS.md").exists());
+}
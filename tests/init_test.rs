@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 // ── --project (old default behavior) ────────────────────────────────
@@ -174,6 +174,24 @@ fn init_user_creates_user_level_files() {
     assert!(content.contains("# Agent Chat"));
 }
 
+#[test]
+fn init_user_uses_userprofile_when_home_unset() {
+    let tmp = TempDir::new().unwrap();
+    let fake_profile = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--user"])
+        .env_remove("HOME")
+        .env("USERPROFILE", fake_profile.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("user"));
+
+    assert!(fake_profile.path().join(".claude/settings.json").exists());
+    assert!(fake_profile.path().join(".claude/CLAUDE.md").exists());
+}
+
 #[test]
 fn init_user_does_not_create_project_claude_md() {
     let tmp = TempDir::new().unwrap();
@@ -361,3 +379,227 @@ fn init_no_flags_enter_defaults_to_both_tools_and_user_target() {
     assert!(fake_home.path().join(".claude/CLAUDE.md").exists());
     assert!(fake_home.path().join(".codex/AGENTS.md").exists());
 }
+
+// ── --continue ──────────────────────────────────────────────────────
+
+#[test]
+fn init_continue_project_installs_only_continue_rules() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--project", "--continue"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Continue rules (project)"));
+
+    let rules = tmp.path().join(".continue/rules/agent-chat.md");
+    let content = std::fs::read_to_string(&rules).unwrap();
+    assert!(content.contains("<!-- agent-chat-continue:start -->"));
+    assert!(content.contains("agent-chat register --session-id"));
+    assert!(!tmp.path().join(".claude/settings.local.json").exists());
+    assert!(!tmp.path().join("CLAUDE.md").exists());
+}
+
+#[test]
+fn init_continue_alongside_claude() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--project", "--claude", "--continue"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("installed hooks (project)"))
+        .stdout(predicate::str::contains("Installed Continue rules (project)"));
+
+    assert!(tmp.path().join(".claude/settings.local.json").exists());
+    assert!(tmp.path().join(".continue/rules/agent-chat.md").exists());
+}
+
+// ── --cursor ────────────────────────────────────────────────────────
+
+#[test]
+fn init_cursor_project_writes_mdc_rule() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--project", "--cursor"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("installed Cursor rules (project)"));
+
+    let rule = tmp.path().join(".cursor/rules/agent-chat.mdc");
+    let content = std::fs::read_to_string(&rule).unwrap();
+    assert!(content.starts_with("---\n"));
+    assert!(content.contains("alwaysApply: true"));
+    assert!(content.contains("<!-- agent-chat-cursor:start -->"));
+    assert!(!tmp.path().join(".claude/settings.local.json").exists());
+
+    // Re-install is idempotent
+    cmd().args(["init", "--project", "--cursor"]).current_dir(tmp.path()).assert().success();
+    assert_eq!(std::fs::read_to_string(&rule).unwrap(), content);
+
+    cmd()
+        .args(["uninstall-tool", "cursor", "--project"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    assert!(!rule.exists());
+}
+
+#[test]
+fn init_cursor_user_writes_home_rule() {
+    let tmp = TempDir::new().unwrap();
+    let fake_home = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--user", "--cursor"])
+        .env("HOME", fake_home.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    assert!(fake_home.path().join(".cursor/rules/agent-chat.mdc").exists());
+    assert!(!tmp.path().join(".cursor").exists());
+}
+
+// ── --dry-run ─────────────────────────────────────────────────────────
+
+/// Every file under `dir`, relative, sorted.
+fn files_under(dir: &std::path::Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(d) = stack.pop() {
+        for entry in std::fs::read_dir(&d).unwrap().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                files.push(path.strip_prefix(dir).unwrap().display().to_string());
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[test]
+fn init_dry_run_writes_nothing() {
+    let tmp = TempDir::new().unwrap();
+    let fake_home = TempDir::new().unwrap();
+    std::fs::create_dir(tmp.path().join(".git")).unwrap();
+
+    cmd()
+        .args(["init", "--both", "--both-tools", "--dry-run"])
+        .env("HOME", fake_home.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would create"))
+        .stdout(predicate::str::contains("config.toml"))
+        .stdout(predicate::str::contains("settings.local.json with agent-chat hooks"))
+        .stdout(predicate::str::contains("CLAUDE.md"))
+        .stdout(predicate::str::contains("AGENTS.md"))
+        .stdout(predicate::str::contains(".git/info/exclude"))
+        .stdout(predicate::str::contains("Dry run: nothing was written."));
+
+    assert!(!tmp.path().join(".agent-chat").exists());
+    assert!(files_under(tmp.path()).is_empty(), "{:?}", files_under(tmp.path()));
+    assert!(files_under(fake_home.path()).is_empty(), "{:?}", files_under(fake_home.path()));
+}
+
+#[test]
+fn init_dry_run_describes_changes_to_existing_files() {
+    let tmp = TempDir::new().unwrap();
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+    std::fs::write(tmp.path().join("AGENTS.md"), "# Team notes\n").unwrap();
+    let before = files_under(tmp.path());
+    let claude_md = std::fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap();
+
+    cmd()
+        .args(["init", "--project", "--both-tools", "--dry-run"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would merge agent-chat hooks into"))
+        .stdout(predicate::str::contains("would update the agent-chat section in"))
+        .stdout(predicate::str::contains("would append the agent-chat section to"))
+        .stdout(predicate::str::contains("config.toml").not());
+
+    assert_eq!(files_under(tmp.path()), before);
+    assert_eq!(std::fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap(), claude_md);
+    assert_eq!(std::fs::read_to_string(tmp.path().join("AGENTS.md")).unwrap(), "# Team notes\n");
+}
+
+// ── --label ───────────────────────────────────────────────────────────
+
+#[test]
+fn init_label_scopes_claude_md_section() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(
+        tmp.path().join("CLAUDE.md"),
+        "# Monorepo\n\n<!-- agent-chat:web:start -->\nweb setup\n<!-- agent-chat:web:end -->\n",
+    )
+    .unwrap();
+
+    cmd()
+        .args(["init", "--project", "--label", "api"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    let config = std::fs::read_to_string(tmp.path().join(".agent-chat/config.toml")).unwrap();
+    assert!(config.contains("instance_label = \"api\""));
+
+    // Re-running without --label keeps using the stored label
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+
+    let content = std::fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap();
+    assert_eq!(content.matches("<!-- agent-chat:api:start -->").count(), 1);
+    assert!(content.contains("web setup"));
+    assert!(!content.contains("<!-- agent-chat:start -->"));
+
+    cmd()
+        .arg("doctor")
+        .current_dir(tmp.path())
+        .assert()
+        .stdout(predicate::str::contains("agent-chat section present"));
+
+    cmd()
+        .args(["uninstall-tool", "claude", "--project"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap(),
+        "# Monorepo\n\n<!-- agent-chat:web:start -->\nweb setup\n<!-- agent-chat:web:end -->\n"
+    );
+}
+
+#[test]
+fn init_rejects_invalid_label() {
+    let tmp = TempDir::new().unwrap();
+    cmd()
+        .args(["init", "--project", "--label", "Bad Label"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("invalid name"));
+    assert!(!tmp.path().join(".agent-chat").exists());
+}
+
+#[test]
+fn init_root_flag_sets_up_that_project() {
+    let tmp = TempDir::new().unwrap();
+    let elsewhere = TempDir::new().unwrap();
+
+    cmd()
+        .args(["--root", tmp.path().to_str().unwrap(), "init", "--project"])
+        .current_dir(elsewhere.path())
+        .assert()
+        .success();
+
+    assert!(tmp.path().join(".agent-chat/log").is_dir());
+    assert!(tmp.path().join("CLAUDE.md").exists());
+    assert!(!elsewhere.path().join(".agent-chat").exists());
+}
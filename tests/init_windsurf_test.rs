@@ -0,0 +1,97 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+const START: &str = "<!-- agent-chat-windsurf:start -->";
+const END: &str = "<!-- agent-chat-windsurf:end -->";
+
+#[test]
+fn init_windsurf_project_creates_rules_and_not_claude_files() {
+    let tmp = TempDir::new().unwrap();
+
+    cmd()
+        .args(["init", "--project", "--windsurf"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Windsurf rules"));
+
+    assert!(tmp.path().join(".agent-chat/config.toml").exists());
+    let content = std::fs::read_to_string(tmp.path().join(".windsurfrules")).unwrap();
+    assert!(content.contains(START));
+    assert!(content.contains("agent-chat register --session-id"));
+
+    assert!(!tmp.path().join(".claude/settings.local.json").exists());
+    assert!(!tmp.path().join("CLAUDE.md").exists());
+    assert!(!tmp.path().join("AGENTS.md").exists());
+}
+
+#[test]
+fn init_windsurf_user_creates_global_rules() {
+    let tmp = TempDir::new().unwrap();
+    let fake_home = TempDir::new().unwrap();
+    std::fs::create_dir(tmp.path().join(".git")).unwrap();
+
+    cmd()
+        .args(["init", "--user", "--windsurf"])
+        .env("HOME", fake_home.path())
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let global = fake_home.path().join(".codeium/windsurf/memories/global_rules.md");
+    assert!(std::fs::read_to_string(global).unwrap().contains(START));
+    assert!(!tmp.path().join(".windsurfrules").exists());
+
+    let exclude = std::fs::read_to_string(tmp.path().join(".git/info/exclude")).unwrap();
+    assert!(exclude.contains(".agent-chat/"));
+}
+
+#[test]
+fn init_windsurf_is_idempotent_and_keeps_existing_rules() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join(".windsurfrules"), "Use 4-space indents.\n").unwrap();
+
+    cmd().args(["init", "--project", "--windsurf"]).current_dir(tmp.path()).assert().success();
+    let once = std::fs::read_to_string(tmp.path().join(".windsurfrules")).unwrap();
+    cmd().args(["init", "--project", "--windsurf"]).current_dir(tmp.path()).assert().success();
+    let twice = std::fs::read_to_string(tmp.path().join(".windsurfrules")).unwrap();
+
+    assert_eq!(once, twice);
+    assert!(twice.starts_with("Use 4-space indents.\n"));
+    assert_eq!(twice.matches(START).count(), 1);
+    assert_eq!(twice.matches(END).count(), 1);
+}
+
+#[test]
+fn init_windsurf_alongside_both_tools_and_uninstall() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::write(tmp.path().join(".windsurfrules"), "Use 4-space indents.\n").unwrap();
+
+    cmd()
+        .args(["init", "--project", "--both-tools", "--windsurf"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Installed Windsurf rules (project)."));
+
+    assert!(tmp.path().join("CLAUDE.md").exists());
+    assert!(tmp.path().join("AGENTS.md").exists());
+
+    cmd()
+        .args(["uninstall-tool", "windsurf", "--project"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed Windsurf integration (project)."));
+
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join(".windsurfrules")).unwrap(),
+        "Use 4-space indents.\n"
+    );
+    assert!(tmp.path().join("AGENTS.md").exists());
+}
@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -65,6 +65,54 @@ fn unlock_removes_lockfile() {
         .stdout(predicate::str::contains("No active locks"));
 }
 
+#[test]
+fn unlock_force_breaks_another_sessions_lock() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    // A plain unlock from another session is still refused
+    cmd()
+        .args(["unlock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Lock conflict"));
+
+    cmd()
+        .args(["unlock", "--force", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Force-unlocked: src/*.rs (held by swift-fox, session sess1)"));
+
+    cmd()
+        .arg("locks")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No active locks"));
+    cmd()
+        .args(["read", "--all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("force-released swift-fox's lock on src/*.rs"));
+}
+
 #[test]
 fn lock_conflict_errors() {
     let tmp = TempDir::new().unwrap();
@@ -89,6 +137,70 @@ fn lock_conflict_errors() {
         .stderr(predicate::str::contains("Lock conflict"));
 }
 
+#[test]
+fn lock_wait_acquires_once_holder_releases() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    // Release the lock while the waiter is polling
+    let root = tmp.path().to_path_buf();
+    let releaser = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(700));
+        cmd()
+            .args(["unlock", "src/*.rs"])
+            .current_dir(&root)
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    });
+
+    cmd()
+        .args(["lock", "src/*.rs", "--wait", "10"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked: src/*.rs"))
+        .stderr(predicate::str::is_empty());
+    releaser.join().unwrap();
+}
+
+#[test]
+fn lock_wait_times_out_with_conflict() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let started = std::time::Instant::now();
+    cmd()
+        .args(["lock", "src/*.rs", "--wait", "1"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Lock conflict"));
+    let waited = started.elapsed();
+    assert!(waited >= std::time::Duration::from_secs(1), "returned after {:?}", waited);
+}
+
 #[test]
 fn different_patterns_ok() {
     let tmp = TempDir::new().unwrap();
@@ -111,3 +223,354 @@ fn different_patterns_ok() {
         .success()
         .stdout(predicate::str::contains("Locked: tests/*.rs"));
 }
+
+#[test]
+fn near_expiry_lock_posts_single_warning() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // Short TTL so the lock is inside the default 30s warning window right away
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("lock_ttl_secs = 300", "lock_ttl_secs = 20")).unwrap();
+
+    cmd()
+        .args(["register", "--session-id", "sess1"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    cmd()
+        .args(["lock", "src/auth/**"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    for _ in 0..2 {
+        cmd()
+            .arg("locks")
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+
+    let output = cmd()
+        .arg("history")
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("lock on src/auth/** expires in").count(), 1, "got: {}", stdout);
+}
+
+#[test]
+fn near_expiry_warning_can_be_disabled() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("lock_expiry_warning_secs = 30", "lock_expiry_warning_secs = 0"),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["register", "--session-id", "sess1"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    cmd()
+        .args(["lock", "src/auth/**", "--ttl", "5"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    cmd()
+        .arg("locks")
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    cmd()
+        .arg("history")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("expires in").not());
+}
+
+#[test]
+fn lock_ttl_overrides_config_default() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked: src/*.rs (ttl 300s)"));
+
+    cmd()
+        .args(["lock", "docs/*.md", "--ttl", "1800"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked: docs/*.md (ttl 1800s)"));
+
+    let output = cmd().args(["locks", "--porcelain"]).current_dir(tmp.path()).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let docs = stdout.lines().find(|l| l.ends_with("docs/*.md")).unwrap();
+    let remaining: u64 = docs.split('\t').nth(2).unwrap().parse().unwrap();
+    assert!(remaining > 300 && remaining <= 1800, "got: {}", docs);
+}
+
+#[test]
+fn lock_ttl_rejects_zero() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs", "--ttl", "0"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .failure();
+
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/locks")).unwrap().count(), 0);
+}
+
+#[test]
+fn lock_renew_refreshes_own_lock_and_rejects_others() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs", "--ttl", "1"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    std::thread::sleep(std::time::Duration::from_millis(2100));
+
+    cmd()
+        .args(["lock", "src/*.rs", "--renew", "--ttl", "600"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renewed: src/*.rs (ttl 600s)"));
+
+    cmd()
+        .args(["lock", "src/*.rs", "--renew"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .stderr(predicate::str::contains("Cannot renew src/*.rs: it is now locked by swift-fox"));
+}
+
+#[test]
+fn unlock_all_releases_only_own_locks() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for (glob, name, sid) in [
+        ("src/*.rs", "swift-fox", "sess1"),
+        ("docs/*.md", "swift-fox", "sess1"),
+        ("tests/*.rs", "bold-hawk", "sess2"),
+    ] {
+        cmd()
+            .args(["lock", glob])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", name)
+            .env("AGENT_CHAT_SESSION_ID", sid)
+            .assert()
+            .success();
+    }
+
+    cmd()
+        .args(["unlock", "--all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unlocked: src/*.rs"))
+        .stdout(predicate::str::contains("Unlocked: docs/*.md"))
+        .stdout(predicate::str::contains("Released 2 lock(s)."));
+
+    cmd()
+        .arg("locks")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("tests/*.rs"))
+        .stdout(predicate::str::contains("src/*.rs").not());
+
+    cmd()
+        .args(["unlock", "--all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No locks to release"));
+}
+
+#[test]
+fn max_locks_per_session_refuses_extra_locks() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("max_locks_per_session = 0", "max_locks_per_session = 1"),
+    )
+    .unwrap();
+
+    let lock = |glob: &str| {
+        cmd()
+            .args(["lock", glob])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success()
+    };
+
+    lock("src/*.rs").stdout(predicate::str::contains("Locked: src/*.rs"));
+    lock("docs/*.md")
+        .stdout(predicate::str::contains("Locked").not())
+        .stderr(predicate::str::contains("Lock limit reached"));
+
+    cmd()
+        .args(["unlock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    lock("docs/*.md").stdout(predicate::str::contains("Locked: docs/*.md"));
+}
+
+#[test]
+fn lock_uses_identity_file_without_env() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    std::fs::write(
+        tmp.path().join(".agent-chat/identity"),
+        r#"{"session_id": "sess1", "name": "swift-fox"}"#,
+    )
+    .unwrap();
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env_remove("AGENT_CHAT_SESSION_ID")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked: src/*.rs"));
+
+    cmd()
+        .args(["locks", "--porcelain"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("swift-fox\tsess1\t"));
+
+    // Env still wins over the file
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .stderr(predicate::str::contains("locked by swift-fox"));
+}
+
+#[test]
+fn overlapping_lock_names_owner_and_pattern() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["lock", "src/**/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked").not())
+        .stderr(predicate::str::contains("Lock overlap: src/**/*.rs overlaps src/*.rs locked by swift-fox"));
+}
+
+#[test]
+fn lock_list_files_previews_matches_without_locking() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    for file in [
+        "src/main.rs",
+        "src/api/routes.rs",
+        "src/api/README.md",
+        "src/models/user.rs",
+        "target/debug/build.rs",
+        ".git/hooks/pre-commit.rs",
+        "docs/api.md",
+    ] {
+        let path = tmp.path().join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+    }
+    std::fs::write(tmp.path().join(".gitignore"), "target/\n").unwrap();
+
+    let output = cmd()
+        .args(["lock", "--list-files", "**/*.rs"])
+        .current_dir(tmp.path().join("src"))
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "src/api/routes.rs\nsrc/main.rs\nsrc/models/user.rs\n"
+    );
+
+    cmd()
+        .args(["lock", "--list-files", "src/{api,models}/*.rs"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout("src/api/routes.rs\nsrc/models/user.rs\n");
+
+    cmd()
+        .args(["lock", "--list-files", "lib/**"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No files match: lib/**"));
+
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/locks")).unwrap().count(), 0);
+}
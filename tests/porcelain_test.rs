@@ -0,0 +1,163 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+fn porcelain_lines(tmp: &TempDir, subcommand: &str) -> Vec<Vec<String>> {
+    let output = cmd()
+        .args([subcommand, "--porcelain"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.split('\t').map(String::from).collect())
+        .collect()
+}
+
+#[test]
+fn locks_porcelain_layout() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/**/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let lines = porcelain_lines(&tmp, "locks");
+    assert_eq!(lines.len(), 1);
+    let fields = &lines[0];
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[0], "swift-fox");
+    assert_eq!(fields[1], "sess1");
+    assert!(fields[2].parse::<u64>().unwrap() <= 300);
+    assert_eq!(fields[3], "src/**/*.rs");
+}
+
+#[test]
+fn focuses_porcelain_layout() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["focus", "CI pipeline work"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    let lines = porcelain_lines(&tmp, "focuses");
+    assert_eq!(lines.len(), 1);
+    let fields = &lines[0];
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields[0], "bold-hawk");
+    assert_eq!(fields[1], "sess2");
+    assert!(fields[2].parse::<u64>().unwrap() <= 300);
+    assert_eq!(fields[3], "CI pipeline work");
+}
+
+#[test]
+fn agents_porcelain_layout() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for sid in ["sess-b", "sess-a"] {
+        cmd()
+            .args(["register", "--session-id", sid])
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+
+    let lines = porcelain_lines(&tmp, "agents");
+    assert_eq!(lines.len(), 2);
+    for fields in &lines {
+        assert_eq!(fields.len(), 2);
+        let name = std::fs::read_to_string(tmp.path().join(".agent-chat/sessions").join(&fields[0])).unwrap();
+        assert_eq!(fields[1], name.trim());
+    }
+}
+
+#[test]
+fn porcelain_empty_prints_nothing() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for sub in ["locks", "focuses", "agents"] {
+        assert!(porcelain_lines(&tmp, sub).is_empty());
+    }
+}
+
+fn json_output(tmp: &TempDir, subcommand: &str) -> serde_json::Value {
+    let output = cmd()
+        .args([subcommand, "--json"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn locks_and_focuses_json() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for args in [["lock", "src/**/*.rs"], ["focus", "auth refactor"]] {
+        cmd()
+            .args(args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    }
+
+    let locks = json_output(&tmp, "locks");
+    let lock = &locks.as_array().unwrap()[0];
+    assert_eq!(lock["glob"], "src/**/*.rs");
+    assert_eq!(lock["owner"], "swift-fox");
+    assert_eq!(lock["session_id"], "sess1");
+    assert_eq!(lock["ttl_secs"], 300);
+    assert!(lock["acquired_at"].as_str().unwrap().ends_with('Z'));
+    assert!(lock["expires_at"].as_str().unwrap() > lock["acquired_at"].as_str().unwrap());
+    assert!(lock["remaining_secs"].as_u64().unwrap() <= 300);
+
+    let focuses = json_output(&tmp, "focuses");
+    let focus = &focuses.as_array().unwrap()[0];
+    assert_eq!(focus["focus"], "auth refactor");
+    assert_eq!(focus["owner"], "swift-fox");
+    assert!(focus["set_at"].as_str().unwrap().contains('T'));
+}
+
+#[test]
+fn locks_json_remaining_secs_counts_down() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["lock", "src/*.rs", "--ttl", "60"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let remaining = || json_output(&tmp, "locks")[0]["remaining_secs"].as_u64().unwrap();
+    let before = remaining();
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let after = remaining();
+
+    assert!(before <= 60);
+    assert!(after < before, "{} should be below {}", after, before);
+}
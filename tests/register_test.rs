@@ -2,7 +2,7 @@ use assert_cmd::Command;
 use assert_fs::TempDir;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -112,6 +112,31 @@ fn register_writes_claude_env_file() {
     assert!(content.contains("export AGENT_CHAT_SESSION_ID=env-test"));
 }
 
+#[test]
+fn register_still_prints_identity_when_env_file_write_fails() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // A path under a missing directory can't be written, even by root
+    let env_file = tmp.path().join("missing-dir").join("env_output");
+
+    let output = cmd()
+        .arg("register")
+        .current_dir(tmp.path())
+        .env("CLAUDE_ENV_FILE", env_file.to_str().unwrap())
+        .write_stdin(r#"{"session_id": "env-fail"}"#)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to write CLAUDE_ENV_FILE"), "{}", stderr);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let context = json["hookSpecificOutput"]["additionalContext"].as_str().unwrap();
+    assert!(context.starts_with("You are "), "{}", context);
+    assert!(tmp.path().join(".agent-chat/sessions/env-fail").exists());
+}
+
 #[test]
 fn register_posts_join_message() {
     let tmp = TempDir::new().unwrap();
@@ -185,6 +210,74 @@ fn register_no_duplicate_join_on_resume() {
     assert_eq!(join_count, 1, "Should have exactly 1 join message, not {}", join_count);
 }
 
+/// Bodies of every message in the log, oldest first.
+fn log_bodies(tmp: &TempDir) -> Vec<String> {
+    let mut entries: Vec<_> = std::fs::read_dir(tmp.path().join(".agent-chat/log"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    entries.sort();
+    entries.iter().map(|p| std::fs::read_to_string(p).unwrap()).collect()
+}
+
+fn register_with(tmp: &TempDir, stdin_json: &str) {
+    cmd()
+        .arg("register")
+        .current_dir(tmp.path())
+        .write_stdin(stdin_json.to_string())
+        .assert()
+        .success();
+    std::thread::sleep(std::time::Duration::from_millis(10));
+}
+
+#[test]
+fn register_resume_is_quiet_by_default() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    register_with(&tmp, r#"{"session_id": "typed", "session_type": "startup"}"#);
+    register_with(&tmp, r#"{"session_id": "typed", "session_type": "resume"}"#);
+
+    let bodies = log_bodies(&tmp);
+    assert_eq!(bodies.len(), 1, "only the join message: {:?}", bodies);
+    assert!(bodies[0].contains("joined the chat"));
+}
+
+#[test]
+fn register_resume_announces_when_enabled() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("announce_resume = false", "announce_resume = true")).unwrap();
+
+    register_with(&tmp, r#"{"session_id": "typed", "session_type": "startup"}"#);
+    // Claude Code names the field `source`
+    register_with(&tmp, r#"{"session_id": "typed", "source": "resume"}"#);
+
+    let bodies = log_bodies(&tmp);
+    assert_eq!(bodies.len(), 2, "{:?}", bodies);
+    assert!(bodies[1].contains("resumed the chat"));
+}
+
+#[test]
+fn register_startup_for_known_session_rejoins() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    register_with(&tmp, r#"{"session_id": "reused", "session_type": "startup"}"#);
+    register_with(&tmp, r#"{"session_id": "reused", "session_type": "startup"}"#);
+
+    let bodies = log_bodies(&tmp);
+    assert_eq!(bodies.len(), 2, "{:?}", bodies);
+    assert!(bodies[0].contains("joined the chat"));
+    assert!(bodies[1].contains("rejoined the chat"));
+    // Still one session, same name
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/sessions")).unwrap().count(), 1);
+}
+
 #[test]
 fn register_injects_existing_messages() {
     let tmp = TempDir::new().unwrap();
@@ -279,3 +372,349 @@ fn register_rejects_empty_session_id_flag() {
         .success()
         .stderr(predicates::str::contains("session_id cannot be empty"));
 }
+
+#[test]
+fn register_replace_name_updates_session_and_posts_notice() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["register", "--session-id", "rename-me"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    let old_name =
+        std::fs::read_to_string(tmp.path().join(".agent-chat/sessions/rename-me")).unwrap();
+
+    cmd()
+        .args(["register", "--session-id", "rename-me", "--replace-name", "reviewer"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let name = std::fs::read_to_string(tmp.path().join(".agent-chat/sessions/rename-me")).unwrap();
+    assert_eq!(name.trim(), "reviewer");
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let notice = format!("renamed from {} to reviewer", old_name.trim());
+    let found = std::fs::read_dir(&log_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .any(|e| {
+            std::fs::read_to_string(e.path())
+                .map(|c| c.contains(&notice))
+                .unwrap_or(false)
+        });
+    assert!(found, "expected rename notice '{}'", notice);
+}
+
+#[test]
+fn register_replace_name_enforces_uniqueness() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for sid in ["sess-a", "sess-b"] {
+        cmd()
+            .args(["register", "--session-id", sid])
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+    cmd()
+        .args(["register", "--session-id", "sess-a", "--replace-name", "reviewer"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    cmd()
+        .args(["register", "--session-id", "sess-b", "--replace-name", "reviewer"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("already taken"));
+
+    let name = std::fs::read_to_string(tmp.path().join(".agent-chat/sessions/sess-b")).unwrap();
+    assert_ne!(name.trim(), "reviewer");
+}
+
+#[test]
+fn register_replace_name_rejects_invalid_name() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["register", "--session-id", "sess-a"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    cmd()
+        .args(["register", "--session-id", "sess-a", "--replace-name", "Not Valid"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("invalid name"));
+}
+
+fn set_consumes_backlog(tmp: &TempDir, on: bool) {
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace(
+            "register_consumes_backlog = false",
+            &format!("register_consumes_backlog = {}", on),
+        ),
+    )
+    .unwrap();
+}
+
+/// Resume a session after it posted a message of its own, and report whether
+/// the cursor now sits past that message.
+fn cursor_past_own_message_after_resume(tmp: &TempDir) -> bool {
+    cmd()
+        .args(["register", "--session-id", "backlog-sess"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    cmd()
+        .args(["say", "own progress note"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "backlog-sess")
+        .assert()
+        .success();
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    cmd()
+        .args(["register", "--session-id", "backlog-sess"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let cursor = tmp.path().join(".agent-chat/cursors/backlog-sess");
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let cursor_mtime = std::fs::metadata(cursor).unwrap().modified().unwrap();
+    let log_mtime = std::fs::metadata(log_dir).unwrap().modified().unwrap();
+    cursor_mtime > log_mtime
+}
+
+#[test]
+fn register_consumes_backlog_on_advances_cursor_on_resume() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    set_consumes_backlog(&tmp, true);
+
+    assert!(cursor_past_own_message_after_resume(&tmp));
+}
+
+#[test]
+fn register_consumes_backlog_off_leaves_cursor_on_resume() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    set_consumes_backlog(&tmp, false);
+
+    assert!(!cursor_past_own_message_after_resume(&tmp));
+}
+
+#[test]
+fn register_honors_preferred_name_and_suffixes_clashes() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let sessions = tmp.path().join(".agent-chat/sessions");
+
+    let output = cmd()
+        .args(["register", "--session-id", "s1", "--name", "reviewer"])
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_PREFERRED_NAME")
+        .output()
+        .unwrap();
+    assert!(extract_context(&output.stdout).contains("You are reviewer."));
+
+    // Env fallback; the name is taken, so it gets a suffix
+    let output = cmd()
+        .args(["register", "--session-id", "s2"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_PREFERRED_NAME", "reviewer")
+        .output()
+        .unwrap();
+    assert!(extract_context(&output.stdout).contains("You are reviewer-2."));
+    assert_eq!(std::fs::read_to_string(sessions.join("s2")).unwrap(), "reviewer-2");
+
+    // Re-registering keeps the existing name
+    let output = cmd()
+        .args(["register", "--session-id", "s1", "--name", "other"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    assert!(extract_context(&output.stdout).contains("You are reviewer."));
+
+    cmd()
+        .args(["register", "--session-id", "s3", "--name", "Bad Name"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicates::str::contains("invalid name"));
+    assert!(!sessions.join("s3").exists());
+}
+
+#[test]
+fn rename_changes_name_for_later_messages_only() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let env_file = tmp.path().join("claude.env");
+
+    cmd()
+        .args(["register", "--session-id", "sess-a", "--name", "swift-fox"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    cmd()
+        .args(["say", "starting on the api"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess-a")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["rename", "api-builder"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess-a")
+        .env("CLAUDE_ENV_FILE", &env_file)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("swift-fox -> api-builder"))
+        .stdout(predicates::str::contains("export AGENT_CHAT_NAME=api-builder"));
+    let env = std::fs::read_to_string(&env_file).unwrap();
+    assert!(env.contains("export AGENT_CHAT_NAME=api-builder\n"), "{}", env);
+
+    cmd()
+        .args(["say", "endpoints done"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess-a")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["read", "--all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess-b")
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match(r"\[swift-fox \d\d:\d\d\]: starting on the api").unwrap())
+        .stdout(predicates::str::contains("swift-fox is now known as api-builder"))
+        .stdout(predicates::str::is_match(r"\[api-builder \d\d:\d\d\]: endpoints done").unwrap());
+
+    // Own messages are skipped by author name, so only those sent under the
+    // new name stay out of the renamed session's reads; the old-name ones
+    // are history from another author as far as the log can tell
+    cmd()
+        .args(["read", "--all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess-a")
+        .assert()
+        .success()
+        .stdout(predicates::str::is_match(r"\[swift-fox \d\d:\d\d\]: starting on the api").unwrap())
+        .stdout(predicates::boolean::PredicateBooleanExt::not(predicates::str::contains("endpoints done")));
+}
+
+#[test]
+fn rename_rejects_a_taken_name() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    for (sid, name) in [("sess-a", "swift-fox"), ("sess-b", "bold-hawk")] {
+        cmd()
+            .args(["register", "--session-id", sid, "--name", name])
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+
+    cmd()
+        .args(["rename", "bold-hawk"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess-a")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("already taken"));
+    let name = std::fs::read_to_string(tmp.path().join(".agent-chat/sessions/sess-a")).unwrap();
+    assert_eq!(name.trim(), "swift-fox");
+}
+
+/// `(key, value)` pairs from `export KEY=value` lines, unquoting single quotes.
+fn parse_exports(stdout: &str) -> Vec<(String, String)> {
+    stdout
+        .lines()
+        .map(|line| {
+            let assignment = line.strip_prefix("export ").unwrap_or_else(|| panic!("not an export: {}", line));
+            let (key, value) = assignment.split_once('=').unwrap();
+            let value = match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                Some(quoted) => quoted.replace(r"'\''", "'"),
+                None => value.to_string(),
+            };
+            (key.to_string(), value)
+        })
+        .collect()
+}
+
+#[test]
+fn register_print_env_emits_only_exports() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    cmd()
+        .args(["say", "backlog before joining"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess-b")
+        .assert()
+        .success();
+
+    let output = cmd()
+        .args(["register", "--session-id", "codex-1", "--name", "swift-fox", "--print-env"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        parse_exports(&stdout),
+        vec![
+            ("AGENT_CHAT_NAME".to_string(), "swift-fox".to_string()),
+            ("AGENT_CHAT_SESSION_ID".to_string(), "codex-1".to_string()),
+        ]
+    );
+
+    // The backlog wasn't injected, so it is still unread
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "codex-1")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("backlog before joining"));
+}
+
+#[test]
+fn register_print_env_quotes_unusual_session_ids() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let output = cmd()
+        .args(["register", "--session-id", "it's a $session", "--print-env"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(r"export AGENT_CHAT_SESSION_ID='it'\''s a $session'"), "{}", stdout);
+    let exports = parse_exports(&stdout);
+    assert_eq!(exports[1].1, "it's a $session");
+
+    // And a real shell agrees
+    let echoed = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{}printf %s \"$AGENT_CHAT_SESSION_ID\"", stdout))
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&echoed.stdout), "it's a $session");
+}
@@ -0,0 +1,174 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+fn say(tmp: &TempDir, text: &str) {
+    cmd()
+        .args(["say", text])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+}
+
+fn history(tmp: &TempDir) -> String {
+    let output = cmd().arg("history").current_dir(tmp.path()).output().unwrap();
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn rooms_lists_created_rooms() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd().args(["room", "new", "auth"]).current_dir(tmp.path()).assert().success();
+    cmd().args(["room", "new", "billing"]).current_dir(tmp.path()).assert().success();
+
+    cmd()
+        .arg("rooms")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout("* default\n  auth\n  billing\n");
+
+    cmd().args(["room", "use", "billing"]).current_dir(tmp.path()).assert().success();
+    cmd()
+        .arg("rooms")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout("  default\n  auth\n* billing\n");
+
+    // The active room can't be removed; others can
+    cmd()
+        .args(["room", "rm", "billing"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("is active"));
+    cmd().args(["room", "rm", "auth"]).current_dir(tmp.path()).assert().success();
+    cmd()
+        .arg("rooms")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout("  default\n* billing\n");
+}
+
+#[test]
+fn rooms_isolate_messages_and_locks() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    say(&tmp, "in the lobby");
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    cmd().args(["room", "new", "auth"]).current_dir(tmp.path()).assert().success();
+    cmd().args(["room", "use", "auth"]).current_dir(tmp.path()).assert().success();
+    say(&tmp, "auth only");
+
+    let auth = history(&tmp);
+    assert!(auth.contains("auth only"));
+    assert!(!auth.contains("in the lobby"));
+    cmd()
+        .arg("locks")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No active locks"));
+    assert!(tmp.path().join(".agent-chat/rooms/auth/cursors").is_dir());
+
+    cmd().args(["room", "use", "default"]).current_dir(tmp.path()).assert().success();
+    let lobby = history(&tmp);
+    assert!(lobby.contains("in the lobby"));
+    assert!(!lobby.contains("auth only"));
+    cmd()
+        .arg("locks")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/*.rs"));
+}
+
+#[test]
+fn room_use_rejects_unknown_room() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["room", "use", "nowhere"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("No such room: nowhere"));
+}
+
+#[test]
+fn malformed_config_with_rooms_refuses_to_post() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    cmd().args(["room", "new", "auth"]).current_dir(tmp.path()).assert().success();
+    cmd().args(["room", "use", "auth"]).current_dir(tmp.path()).assert().success();
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, format!("{}\nroom = [\n", config)).unwrap();
+
+    cmd()
+        .args(["say", "where does this go"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot determine the active room"));
+
+    for log in [".agent-chat/log", ".agent-chat/rooms/auth/log"] {
+        assert_eq!(std::fs::read_dir(tmp.path().join(log)).unwrap().count(), 0, "{}", log);
+    }
+}
+
+#[test]
+fn unresolvable_room_leaves_hooks_advisory() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    cmd().args(["room", "new", "auth"]).current_dir(tmp.path()).assert().success();
+
+    // A config that fails validation, pointing outside `rooms/`
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("room = \"\"", "room = \"../..\"")).unwrap();
+
+    for hook in ["status", "check-messages", "check-lock"] {
+        cmd()
+            .arg(hook)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .write_stdin("{}")
+            .assert()
+            .success()
+            .stdout("")
+            .stderr(predicate::str::contains("Cannot determine the active room"));
+    }
+
+    cmd()
+        .arg("history")
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot determine the active room"));
+}
@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -391,3 +391,1471 @@ fn read_works_without_env_when_single_session_registered() {
         .success()
         .stdout(predicate::str::contains("from sender"));
 }
+
+fn write_raw_message(tmp: &TempDir, content: &str) {
+    let ns = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let path = tmp.path().join(format!(".agent-chat/log/{}.md", ns));
+    std::fs::write(path, content).unwrap();
+}
+
+#[test]
+fn read_min_priority_filters_but_advances_cursor() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // Establish a cursor for the reader
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    write_raw_message(&tmp, "name: swift-fox\nfyi only\n");
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    write_raw_message(&tmp, "name: swift-fox\npriority: high\nneed review now\n");
+
+    let output = cmd()
+        .args(["read", "--min-priority", "high"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("need review now"));
+    assert!(!stdout.contains("fyi only"));
+    assert!(!stdout.contains("priority:"));
+
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    // Normal-priority message was skipped but is no longer unread
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn own_messages_filtered_when_name_only_in_session_file() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // Two registered sessions, so the session id can't be inferred — only the name can
+    for sid in ["sess-a", "sess-b"] {
+        cmd()
+            .args(["register", "--session-id", sid])
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    cmd()
+        .args(["say", "my own note"])
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env("AGENT_CHAT_SESSION_ID", "sess-a")
+        .assert()
+        .success();
+
+    for sub in ["status", "check-messages", "read"] {
+        cmd()
+            .arg(sub)
+            .current_dir(tmp.path())
+            .env_remove("AGENT_CHAT_NAME")
+            .env("AGENT_CHAT_SESSION_ID", "sess-a")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("my own note").not());
+    }
+}
+
+#[test]
+fn read_notes_when_name_unresolvable() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env("AGENT_CHAT_SESSION_ID", "unregistered")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("own messages are not filtered"));
+}
+
+#[test]
+fn say_reply_with_quote_includes_snippet_and_link() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let long = format!("can someone review the auth middleware changes {}", "x".repeat(100));
+    cmd()
+        .args(["say", &long])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let parent = std::fs::read_dir(&log_dir).unwrap().next().unwrap().unwrap().path();
+    let parent_id = parent.file_stem().unwrap().to_string_lossy().to_string();
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    cmd()
+        .args(["say", "--reply-to", &parent_id, "--quote", "on it"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    let reply = std::fs::read_dir(&log_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|p| *p != parent)
+        .unwrap();
+    let content = std::fs::read_to_string(reply).unwrap();
+    assert!(content.contains(&format!("reply_to: {}", parent_id)), "got: {}", content);
+    assert!(content.contains("> swift-fox: can someone review the auth middleware"), "got: {}", content);
+    assert!(content.contains('…'), "quote should be truncated: {}", content);
+    assert!(!content.contains(&"x".repeat(100)));
+    assert!(content.trim_end().ends_with("on it"));
+}
+
+#[test]
+fn say_reply_to_unknown_message_errors() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "--reply-to", "12345", "hi"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .stderr(predicate::str::contains("Message not found"));
+}
+
+#[test]
+fn read_watch_unblocks_when_message_arrives() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let dir = tmp.path().to_path_buf();
+    let watcher = std::thread::spawn(move || {
+        cmd()
+            .args(["read", "--watch", "--timeout", "20"])
+            .current_dir(&dir)
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", "sess2")
+            .output()
+            .unwrap()
+    });
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    cmd()
+        .args(["say", "schema is ready"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let output = watcher.join().unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("schema is ready"));
+
+    // Cursor advanced: a plain read has nothing new
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn read_watch_times_out_with_nonzero_exit() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // Own messages do not end the wait
+    cmd()
+        .args(["say", "talking to myself"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["read", "--watch", "--timeout", "1"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("No new messages within 1s"));
+}
+
+#[test]
+fn reply_threads_under_parent_in_read_and_check_messages() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "who owns the schema?"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let parent = std::fs::read_dir(&log_dir).unwrap().next().unwrap().unwrap().path();
+    let parent_id = parent.file_stem().unwrap().to_string_lossy().to_string();
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    cmd()
+        .args(["reply", &parent_id, "I do"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[swift-fox"))
+        .stdout(predicate::str::contains(format!("↳ re {}: [bold-hawk", parent_id)));
+
+    cmd()
+        .arg("check-messages")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "quiet-elk")
+        .env("AGENT_CHAT_SESSION_ID", "sess4")
+        .write_stdin("{}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("↳ re {}", parent_id)));
+}
+
+#[test]
+fn reply_to_unknown_message_errors() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["reply", "12345", "hi"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .stderr(predicate::str::contains("Message not found: 12345"));
+}
+
+#[test]
+fn say_status_shortcuts_store_header_and_render_prefix() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let cases = [
+        ("working", "auth middleware", "🚧 working: auth middleware"),
+        ("done", "auth middleware", "✅ done: auth middleware"),
+        ("blocked", "need DB schema", "⛔ blocked: need DB schema"),
+    ];
+
+    for (status, text, rendered) in cases {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cmd()
+            .args(["say", "--status", status, text])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+
+        let log_dir = tmp.path().join(".agent-chat/log");
+        let mut files: Vec<_> = std::fs::read_dir(&log_dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .collect();
+        files.sort();
+        let content = std::fs::read_to_string(files.last().unwrap()).unwrap();
+        assert!(content.contains(&format!("status: {}\n", status)), "got: {}", content);
+
+        cmd()
+            .arg("read")
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", "sess2")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(rendered));
+    }
+}
+
+#[test]
+fn say_to_delivers_only_to_recipient() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "--to", "bold-hawk", "can you take the migration?"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    cmd()
+        .args(["say", "broadcast to all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let stored: Vec<String> = std::fs::read_dir(&log_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| std::fs::read_to_string(e.path()).unwrap())
+        .collect();
+    assert!(stored.iter().any(|c| c.contains("to: bold-hawk\n")));
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(to bold-hawk) can you take the migration?"))
+        .stdout(predicate::str::contains("broadcast to all"));
+
+    for args in [vec!["read"], vec!["read", "--all"], vec!["history"]] {
+        cmd()
+            .args(&args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "calm-owl")
+            .env("AGENT_CHAT_SESSION_ID", "sess3")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("broadcast to all"))
+            .stdout(predicate::str::contains("migration").not());
+    }
+
+    cmd()
+        .arg("check-messages")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "quiet-elk")
+        .env("AGENT_CHAT_SESSION_ID", "sess4")
+        .write_stdin("{}")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("migration").not());
+}
+
+#[test]
+fn read_by_author_groups_into_sections() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for (name, sid, text) in [
+        ("swift-fox", "sess1", "fox one"),
+        ("bold-hawk", "sess2", "hawk one"),
+        ("swift-fox", "sess1", "fox two"),
+        ("bold-hawk", "sess2", "hawk two"),
+    ] {
+        cmd()
+            .args(["say", text])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", name)
+            .env("AGENT_CHAT_SESSION_ID", sid)
+            .assert()
+            .success();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    let output = cmd()
+        .args(["read", "--all", "--by-author"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let sections: Vec<&str> = stdout.split("\n\n").collect();
+    assert_eq!(sections.len(), 2, "got: {}", stdout);
+
+    assert!(sections[0].starts_with("=== swift-fox (2) ==="), "got: {}", stdout);
+    let fox_one = sections[0].find("fox one").unwrap();
+    let fox_two = sections[0].find("fox two").unwrap();
+    assert!(fox_one < fox_two);
+    assert!(!sections[0].contains("hawk"));
+
+    assert!(sections[1].starts_with("=== bold-hawk (2) ==="), "got: {}", stdout);
+    assert!(sections[1].find("hawk one").unwrap() < sections[1].find("hawk two").unwrap());
+}
+
+#[test]
+fn scheduled_message_hidden_until_delivery() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "--in", "2s", "check CI results"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Scheduled:"));
+
+    let read = || {
+        cmd()
+            .arg("read")
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", "sess2")
+            .assert()
+            .success()
+    };
+
+    read().stdout(predicate::str::contains("check CI results").not());
+    cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("check CI results").not());
+
+    std::thread::sleep(std::time::Duration::from_millis(2200));
+
+    cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("check CI results"));
+    read().stdout(predicate::str::contains("check CI results"));
+}
+
+#[test]
+fn say_in_rejects_bad_duration() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "--in", "soon", "hello"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .stderr(predicate::str::contains("Invalid delay 'soon'"));
+}
+
+#[test]
+fn read_first_session_honors_configured_count() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("first_read_count = 5", "first_read_count = 3")).unwrap();
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    for i in 0..8u128 {
+        let content = format!("name: swift-fox\nmsg-{}\n", i);
+        std::fs::write(log_dir.join(format!("{}.md", 1_000_000_000_000_000_000 + i)), content).unwrap();
+    }
+
+    let read = |sid: &str, extra: &[&str]| {
+        let output = cmd()
+            .arg("read")
+            .args(extra)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", sid)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let stdout = read("sess2", &[]);
+    assert_eq!(stdout.lines().count(), 3, "got: {}", stdout);
+    assert!(stdout.contains("msg-5") && stdout.contains("msg-7"));
+    assert!(!stdout.contains("msg-4"));
+
+    // --count overrides the config
+    let stdout = read("sess3", &["--count", "6"]);
+    assert_eq!(stdout.lines().count(), 6, "got: {}", stdout);
+    assert!(stdout.contains("msg-2"));
+}
+
+#[test]
+fn history_for_one_agent_includes_own_messages_with_dates() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    std::fs::write(log_dir.join("1736950200000000000.md"), "name: swift-fox\nclaimed auth\n").unwrap();
+    std::fs::write(log_dir.join("1736950260000000000.md"), "name: bold-hawk\nclaimed billing\n").unwrap();
+    std::fs::write(log_dir.join("1736950320000000000.md"), "name: swift-fox\nfinished auth\n").unwrap();
+
+    let output = cmd()
+        .args(["history", "swift-fox"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "got: {}", stdout);
+    assert!(lines[0].contains("claimed auth"));
+    assert!(lines[1].contains("finished auth"));
+    // Full date, not just HH:MM
+    assert!(lines[0].starts_with("[swift-fox 2025-01-1"), "got: {}", lines[0]);
+    assert!(!stdout.contains("billing"));
+    assert!(!tmp.path().join(".agent-chat/cursors/sess1").exists());
+}
+
+#[test]
+fn say_uses_identity_file_without_env() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // Two registered sessions, so single-session inference can't kick in
+    for sid in ["sess1", "sess2"] {
+        cmd()
+            .args(["register", "--session-id", sid])
+            .current_dir(tmp.path())
+            .env_remove("AGENT_CHAT_NAME")
+            .env_remove("AGENT_CHAT_SESSION_ID")
+            .assert()
+            .success();
+    }
+    std::fs::write(
+        tmp.path().join(".agent-chat/identity"),
+        r#"{"session_id": "sess1", "name": "swift-fox"}"#,
+    )
+    .unwrap();
+
+    cmd()
+        .args(["say", "from the identity file"])
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env_remove("AGENT_CHAT_SESSION_ID")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    cmd()
+        .arg("history")
+        .current_dir(tmp.path())
+        .env_remove("AGENT_CHAT_NAME")
+        .env_remove("AGENT_CHAT_SESSION_ID")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[swift-fox"))
+        .stdout(predicate::str::contains("from the identity file"));
+}
+
+#[test]
+fn ephemeral_message_expires_and_is_pruned() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for args in [vec!["say", "--ttl", "1", "still working"], vec!["say", "durable note"]] {
+        cmd()
+            .args(&args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    let log_dir = tmp.path().join(".agent-chat/log");
+    assert_eq!(std::fs::read_dir(&log_dir).unwrap().count(), 2);
+
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+
+    cmd()
+        .args(["read", "--all"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("durable note"))
+        .stdout(predicate::str::contains("still working").not());
+
+    let remaining: Vec<_> = std::fs::read_dir(&log_dir).unwrap().collect();
+    assert_eq!(remaining.len(), 1);
+}
+
+#[test]
+fn read_json_emits_message_objects() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for msg in ["first", "second"] {
+        cmd()
+            .args(["say", msg])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    let output = cmd()
+        .args(["read", "--json"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    let messages: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let messages = messages.as_array().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0]["name"], "swift-fox");
+    assert_eq!(messages[0]["body"], "first");
+    assert!(messages[0]["timestamp"].as_str().unwrap().ends_with('Z'));
+    assert!(messages[0].get("reply_to").is_none());
+
+    let id = messages[1]["id"].as_str().unwrap().to_string();
+    cmd()
+        .args(["reply", &id, "ack"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let output = cmd()
+        .args(["read", "--json"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    let messages: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(messages[0]["reply_to"], id.as_str());
+}
+
+#[test]
+fn ack_collapses_under_parent() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "can you take src/api.rs?"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let entry = std::fs::read_dir(&log_dir).unwrap().next().unwrap().unwrap();
+    let id = entry.path().file_stem().unwrap().to_string_lossy().to_string();
+
+    for (name, sid) in [("bold-hawk", "sess2"), ("calm-owl", "sess3")] {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cmd()
+            .args(["ack", &id])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", name)
+            .env("AGENT_CHAT_SESSION_ID", sid)
+            .assert()
+            .success();
+    }
+
+    let output = cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "keen-lynx")
+        .env("AGENT_CHAT_SESSION_ID", "sess4")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1, "got: {}", stdout);
+    assert!(lines[0].contains("can you take src/api.rs?"));
+    assert!(lines[0].ends_with("(👍 ack: bold-hawk, calm-owl)"), "got: {}", lines[0]);
+
+    cmd()
+        .args(["ack", "1234"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .stderr(predicate::str::contains("Message not found: 1234"));
+    assert_eq!(std::fs::read_dir(&log_dir).unwrap().count(), 3);
+}
+
+#[test]
+fn read_from_filters_by_sender_and_advances_cursor() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    for (name, sid, msg) in [
+        ("swift-fox", "sess1", "from the fox"),
+        ("bold-hawk", "sess2", "from the hawk"),
+        ("calm-owl", "sess3", "my own note"),
+    ] {
+        cmd()
+            .args(["say", msg])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", name)
+            .env("AGENT_CHAT_SESSION_ID", sid)
+            .assert()
+            .success();
+    }
+
+    cmd()
+        .args(["read", "--from", "swift-fox"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from the fox"))
+        .stdout(predicate::str::contains("from the hawk").not())
+        .stdout(predicate::str::contains("my own note").not());
+
+    // Own messages stay hidden even when asked for by name
+    cmd()
+        .args(["read", "--all", "--from", "calm-owl"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    // The hawk's message was skipped, not left unread
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn say_rejects_message_over_size_limit() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let huge = "x".repeat(5000);
+
+    cmd()
+        .args(["say", &huge])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .stderr(predicate::str::contains("5000 bytes, over the 4096-byte limit"));
+
+    let count = std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count();
+    assert_eq!(count, 0, "oversized message must not be written");
+}
+
+#[test]
+fn say_truncate_cuts_body_to_limit() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let huge = "é".repeat(3000);
+
+    cmd()
+        .args(["say", "--truncate", &huge])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let entry = std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().next().unwrap().unwrap();
+    let content = std::fs::read_to_string(entry.path()).unwrap();
+    let body = content.strip_prefix("name: swift-fox\n\n").unwrap();
+    assert!(body.len() <= 4096, "body is {} bytes", body.len());
+    assert!(body.trim_end().ends_with("… [truncated]"));
+}
+
+#[test]
+fn say_truncate_respects_a_limit_smaller_than_the_marker() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("max_message_bytes = 4096", "max_message_bytes = 4")).unwrap();
+
+    cmd()
+        .args(["say", "--truncate", "a much longer message"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let entry = std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().next().unwrap().unwrap();
+    let content = std::fs::read_to_string(entry.path()).unwrap();
+    let body = content.strip_prefix("name: swift-fox\n\n").unwrap().trim_end();
+    assert_eq!(body, "a…");
+}
+
+#[test]
+fn say_size_limit_zero_is_unlimited() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("max_message_bytes = 4096", "max_message_bytes = 0")).unwrap();
+
+    cmd()
+        .args(["say", &"x".repeat(10_000)])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 1);
+}
+
+#[test]
+fn say_file_posts_multiline_body() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let note = tmp.path().join("note.md");
+    std::fs::write(&note, "line one\nline two\n").unwrap();
+
+    cmd()
+        .args(["say", "--file", note.to_str().unwrap()])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let entry = std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().next().unwrap().unwrap();
+    let content = std::fs::read_to_string(entry.path()).unwrap();
+    assert!(content.contains("line one\nline two"));
+}
+
+#[test]
+fn say_file_rejects_missing_and_non_utf8_files() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let binary = tmp.path().join("blob.bin");
+    std::fs::write(&binary, [0xff, 0xfe, 0x00]).unwrap();
+
+    cmd()
+        .args(["say", "--file", "missing.txt"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot read missing.txt"));
+    cmd()
+        .args(["say", "--file", binary.to_str().unwrap()])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not valid UTF-8"));
+
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 0);
+}
+
+#[test]
+fn say_dash_reads_message_from_stdin() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "-"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .write_stdin("build: ok\ntests: 42 passed\n")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["read"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("build: ok"))
+        .stdout(predicate::str::contains("tests: 42 passed"));
+}
+
+fn enable_dedup(tmp: &TempDir, secs: u64) {
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(&config_path, config.replace("dedup_window_secs = 0", &format!("dedup_window_secs = {}", secs))).unwrap();
+}
+
+fn say_as_fox(tmp: &TempDir, msg: &str) {
+    cmd()
+        .args(["say", msg])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn say_dedups_repeated_message_within_window() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    enable_dedup(&tmp, 60);
+
+    say_as_fox(&tmp, "build failed, retrying");
+    say_as_fox(&tmp, "build failed, retrying");
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 1);
+
+    // A different body is still posted, and then the old one is no longer the latest
+    say_as_fox(&tmp, "build passed");
+    say_as_fox(&tmp, "build failed, retrying");
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 3);
+}
+
+#[test]
+fn say_dedup_keeps_acks_of_different_messages() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    say_as_fox(&tmp, "first");
+    say_as_fox(&tmp, "second");
+    let mut ids: Vec<String> = std::fs::read_dir(tmp.path().join(".agent-chat/log"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().trim_end_matches(".md").to_string())
+        .collect();
+    ids.sort();
+    enable_dedup(&tmp, 60);
+
+    for id in &ids {
+        cmd()
+            .args(["ack", id])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", "sess2")
+            .assert()
+            .success();
+    }
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 4);
+
+    // Same text with a different status or priority is not a repeat either
+    say_as_fox(&tmp, "deploying");
+    for extra in [["--status", "done"], ["--priority", "high"]] {
+        cmd()
+            .args(["say", "deploying"])
+            .args(extra)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    }
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 7);
+}
+
+#[test]
+fn say_keeps_duplicates_when_dedup_is_off() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    say_as_fox(&tmp, "ping");
+    say_as_fox(&tmp, "ping");
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 2);
+}
+
+#[test]
+fn read_since_shows_window_without_touching_cursor() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let now_ns = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos();
+    let hour_ns = 3_600_000_000_000u128;
+    std::fs::write(log_dir.join(format!("{}.md", now_ns - 2 * hour_ns)), "name: swift-fox\ntwo hours ago\n").unwrap();
+    std::fs::write(log_dir.join(format!("{}.md", now_ns - hour_ns / 6)), "name: swift-fox\nten minutes ago\n").unwrap();
+    std::fs::write(log_dir.join(format!("{}.md", now_ns - hour_ns / 12)), "name: bold-hawk\nmy own note\n").unwrap();
+
+    // Mark everything read first: --since must still show the window
+    cmd()
+        .arg("mark-read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+    let cursor = tmp.path().join(".agent-chat/cursors/sess2");
+    let before = std::fs::read_to_string(&cursor).unwrap();
+
+    cmd()
+        .args(["read", "--since", "30m"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ten minutes ago"))
+        .stdout(predicate::str::contains("two hours ago").not())
+        .stdout(predicate::str::contains("my own note").not());
+
+    assert_eq!(std::fs::read_to_string(&cursor).unwrap(), before);
+
+    cmd()
+        .args(["read", "--since", "soon"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .stderr(predicate::str::contains("invalid duration: soon"));
+}
+
+fn say_as(tmp: &TempDir, name: &str, session: &str, message: &str) {
+    cmd()
+        .args(["say", message])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", name)
+        .env("AGENT_CHAT_SESSION_ID", session)
+        .assert()
+        .success();
+}
+
+#[test]
+fn muted_senders_disappear_for_the_muting_session_only() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["mute", "chatty-jay"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Muted: chatty-jay"));
+
+    say_as(&tmp, "chatty-jay", "sess2", "progress 1%");
+    say_as(&tmp, "bold-hawk", "sess3", "tests are green");
+    say_as(&tmp, "chatty-jay", "sess2", "progress 2%");
+
+    // The muting session still sees everyone else, in every read mode
+    for args in [vec!["read"], vec!["read", "--all"], vec!["read", "--since", "1h"]] {
+        cmd()
+            .args(&args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("tests are green"))
+            .stdout(predicate::str::contains("progress").not());
+    }
+
+    // Other sessions are unaffected
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess4")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("progress 2%"));
+}
+
+#[test]
+fn muted_only_backlog_leaves_status_and_check_messages_quiet() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["mute", "chatty-jay"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    say_as(&tmp, "chatty-jay", "sess2", "progress 3%");
+
+    for command in ["status", "check-messages"] {
+        cmd()
+            .arg(command)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+    }
+
+    cmd()
+        .args(["unmute", "chatty-jay"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Unmuted: chatty-jay"));
+    cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("progress 3%"));
+}
+
+#[test]
+fn notify_sends_high_priority_direct_message() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    std::fs::write(tmp.path().join(".agent-chat/sessions/sess2"), "bold-hawk").unwrap();
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("status_block_priority = \"normal\"", "status_block_priority = \"high\""),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["notify", "bold-hawk", "blocked on your schema change"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let stored: Vec<String> = std::fs::read_dir(&log_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| std::fs::read_to_string(e.path()).unwrap())
+        .collect();
+    assert_eq!(stored.len(), 1);
+    assert!(stored[0].contains("to: bold-hawk\n"), "{}", stored[0]);
+    assert!(stored[0].contains("priority: high\n"), "{}", stored[0]);
+
+    // High priority blocks the recipient's status even with the threshold raised
+    let output = cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["decision"], "block");
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "calm-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("schema").not());
+}
+
+#[test]
+fn notify_unknown_recipient_warns_but_posts() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["notify", "ghost-owl", "are you there?"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("no registered session is named 'ghost-owl'"));
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "ghost-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(to ghost-owl) are you there?"));
+}
+
+fn register_as(tmp: &TempDir, session: &str, name: &str) {
+    cmd()
+        .args(["register", "--session-id", session, "--name", name])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+}
+
+fn latest_message_id(tmp: &TempDir) -> String {
+    let mut ids: Vec<String> = std::fs::read_dir(tmp.path().join(".agent-chat/log"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter_map(|n| n.strip_suffix(".md").map(str::to_string))
+        .collect();
+    ids.sort();
+    ids.pop().unwrap()
+}
+
+#[test]
+fn receipts_report_who_has_read_a_message() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    for (sid, name) in [("sess1", "swift-fox"), ("sess2", "bold-hawk"), ("sess3", "quiet-owl"), ("sess4", "calm-elk")] {
+        register_as(&tmp, sid, name);
+    }
+    // A session whose cursor file was never written counts as unread
+    std::fs::write(tmp.path().join(".agent-chat/sessions/sess5"), "lazy-yak").unwrap();
+
+    say_as(&tmp, "swift-fox", "sess1", "schema is merged");
+    let id = latest_message_id(&tmp);
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("schema is merged"));
+    cmd()
+        .args(["mark-read", "--to", &id])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess4")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["receipts", &id])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout("read by bold-hawk, calm-elk; unread by lazy-yak, quiet-owl\n");
+
+    // A later message is unread by calm-elk, whose cursor stopped at `id`
+    say_as(&tmp, "bold-hawk", "sess2", "thanks");
+    cmd()
+        .args(["receipts", &latest_message_id(&tmp)])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout("read by nobody; unread by calm-elk, lazy-yak, quiet-owl, swift-fox\n");
+}
+
+#[test]
+fn receipts_for_direct_message_list_only_recipient() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    for (sid, name) in [("sess1", "swift-fox"), ("sess2", "bold-hawk"), ("sess3", "quiet-owl")] {
+        register_as(&tmp, sid, name);
+    }
+    cmd()
+        .args(["notify", "bold-hawk", "need your review"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    let id = latest_message_id(&tmp);
+
+    cmd()
+        .args(["receipts", &id])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout("read by nobody; unread by bold-hawk\n");
+
+    cmd()
+        .args(["receipts", "123"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("Message not found: 123"));
+}
+
+#[test]
+fn read_with_root_flag_from_unrelated_cwd() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let elsewhere = TempDir::new().unwrap();
+
+    cmd()
+        .args(["--root", tmp.path().to_str().unwrap(), "say", "from afar"])
+        .current_dir(elsewhere.path())
+        .env_remove("AGENT_CHAT_ROOT")
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    // The flag also works after the subcommand and wins over AGENT_CHAT_ROOT
+    cmd()
+        .args(["read", "--root", tmp.path().join(".agent-chat").to_str().unwrap()])
+        .current_dir(elsewhere.path())
+        .env("AGENT_CHAT_ROOT", elsewhere.path())
+        .env("AGENT_CHAT_NAME", "bold-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("from afar"));
+}
+
+#[test]
+fn root_flag_without_agent_chat_dir_fails() {
+    let elsewhere = TempDir::new().unwrap();
+
+    cmd()
+        .args(["--root", elsewhere.path().to_str().unwrap(), "read"])
+        .current_dir(elsewhere.path())
+        .env("AGENT_CHAT_NAME", "bold-owl")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No .agent-chat/ directory at"));
+}
+
+#[test]
+fn say_ref_records_and_renders_paths() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "fixed the parser bug", "--ref", "./src/format.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    cmd()
+        .args(["say", "unlocked", "--ref", "src/cli.rs", "--ref", "src/main.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let id = latest_message_id(&tmp);
+    let content = std::fs::read_to_string(tmp.path().join(format!(".agent-chat/log/{}.md", id))).unwrap();
+    assert!(content.contains("\nrefs: src/cli.rs, src/main.rs\n"), "{}", content);
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fixed the parser bug (re: src/format.rs)"))
+        .stdout(predicate::str::contains("unlocked (re: src/cli.rs, src/main.rs)"));
+}
+
+#[test]
+fn say_ref_rejects_commas() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "hi", "--ref", "a.rs,b.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .stderr(predicate::str::contains("Invalid --ref 'a.rs,b.rs'"));
+    assert_eq!(std::fs::read_dir(tmp.path().join(".agent-chat/log")).unwrap().count(), 0);
+}
+
+#[test]
+fn say_body_that_looks_like_a_header_stays_in_the_body() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "to: whom it may concern, build is green"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[swift-fox").and(predicate::str::contains("]: to: whom it may concern, build is green")));
+}
+
+#[test]
+fn header_like_body_gets_the_same_view_in_read_and_history() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "to: calm-owl deliver_at: 1"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    for args in [&["read", "--all"][..], &["history"][..], &["search", "calm-owl"][..]] {
+        cmd()
+            .args(args)
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "bold-hawk")
+            .env("AGENT_CHAT_SESSION_ID", "sess2")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("to: calm-owl deliver_at: 1"));
+    }
+}
@@ -0,0 +1,102 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+fn now_ns() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
+
+#[test]
+fn search_matches_case_insensitively_and_filters() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let log_dir = tmp.path().join(".agent-chat/log");
+    let day_ago = now_ns() - 24 * 3600 * 1_000_000_000;
+    std::fs::write(log_dir.join(format!("{}.md", day_ago)), "name: swift-fox\nold Postgres decision\n").unwrap();
+    let now = now_ns();
+    std::fs::write(log_dir.join(format!("{}.md", now)), "name: bold-hawk\nswitching to postgres pool\n").unwrap();
+    std::fs::write(log_dir.join(format!("{}.md", now + 1)), "name: swift-fox\nunrelated\n").unwrap();
+
+    cmd()
+        .args(["search", "POSTGRES"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[swift-fox"))
+        .stdout(predicate::str::contains("old Postgres decision"))
+        .stdout(predicate::str::contains("switching to postgres pool"))
+        .stdout(predicate::str::contains("unrelated").not());
+
+    cmd()
+        .args(["search", "postgres", "--author", "swift-fox"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("old Postgres decision"))
+        .stdout(predicate::str::contains("pool").not());
+
+    cmd()
+        .args(["search", "postgres", "--since", "2h"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("switching to postgres pool"))
+        .stdout(predicate::str::contains("decision").not());
+}
+
+#[test]
+fn search_does_not_advance_cursor() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["say", "needle in the log"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    cmd()
+        .args(["search", "needle"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("needle in the log"));
+    assert!(!tmp.path().join(".agent-chat/cursors/sess2").exists());
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("needle in the log"));
+}
+
+#[test]
+fn search_rejects_bad_since() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["search", "x", "--since", "lately"])
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("invalid duration"));
+}
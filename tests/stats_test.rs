@@ -0,0 +1,74 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+fn seed(tmp: &TempDir) {
+    for (name, sid) in [("swift-fox", "sess1"), ("bold-hawk", "sess2")] {
+        cmd()
+            .args(["register", "--session-id", sid, "--name", name])
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+    for (name, sid, msg) in [
+        ("swift-fox", "sess1", "one"),
+        ("swift-fox", "sess1", "two"),
+        ("bold-hawk", "sess2", "three"),
+    ] {
+        cmd()
+            .args(["say", msg])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", name)
+            .env("AGENT_CHAT_SESSION_ID", sid)
+            .assert()
+            .success();
+    }
+    cmd()
+        .args(["lock", "src/*.rs"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+}
+
+#[test]
+fn stats_json_counts_everything() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    seed(&tmp);
+
+    let output = cmd().args(["stats", "--json"]).current_dir(tmp.path()).output().unwrap();
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["agents"], 2);
+    assert_eq!(stats["locks"], 1);
+    assert_eq!(stats["focuses"], 0);
+    // Each agent's join notice from `register` counts too
+    assert_eq!(stats["messages"], 5);
+    assert_eq!(stats["messages_by_author"]["swift-fox"], 3);
+    assert_eq!(stats["messages_by_author"]["bold-hawk"], 2);
+}
+
+#[test]
+fn stats_report_lists_busiest_agent_first() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    seed(&tmp);
+
+    cmd()
+        .arg("stats")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Agents: 2"))
+        .stdout(predicate::str::contains("Locks: 1"))
+        .stdout(predicate::str::is_match(r"(?s)swift-fox\s+3.*bold-hawk\s+2").unwrap());
+}
@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use predicates::prelude::*;
 
 fn cmd() -> Command {
-    Command::cargo_bin("agent-chat").unwrap()
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
 }
 
 fn init_project(tmp: &TempDir) {
@@ -48,7 +48,7 @@ fn status_shows_unread_when_new_messages() {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let json: serde_json::Value = serde_json::from_str(&stdout)
-        .expect(&format!("Expected valid JSON but got: {}", stdout));
+        .unwrap_or_else(|_| panic!("Expected valid JSON but got: {}", stdout));
 
     assert_eq!(json["decision"], "block", "Expected decision:block but got: {}", stdout);
     let reason = json["reason"].as_str().expect("Expected reason string");
@@ -159,10 +159,11 @@ fn status_counts_only_others() {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let json: serde_json::Value = serde_json::from_str(&stdout)
-        .expect(&format!("Expected valid JSON but got: {}", stdout));
+        .unwrap_or_else(|_| panic!("Expected valid JSON but got: {}", stdout));
 
     assert_eq!(json["decision"], "block");
     let reason = json["reason"].as_str().expect("Expected reason string");
+    assert!(reason.starts_with("1 unread from bold-hawk\n"), "Expected a sender summary first but got: {}", reason);
     assert!(reason.contains("1 unread message"), "Expected '1 unread message' but got: {}", reason);
     assert!(reason.contains("from B"), "Expected 'from B' but got: {}", reason);
     assert!(reason.contains("bold-hawk"), "Expected 'bold-hawk' but got: {}", reason);
@@ -215,3 +216,331 @@ fn status_noop_when_identity_missing() {
         .success()
         .stdout(predicate::str::is_empty());
 }
+
+#[test]
+fn status_report_clear_emits_context_without_block() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("status_report_clear = false", "status_report_clear = true"),
+    )
+    .unwrap();
+
+    let output = cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|_| panic!("Expected valid JSON but got: {}", stdout));
+
+    assert!(json.get("decision").is_none(), "Must not block: {}", stdout);
+    assert_eq!(
+        json["hookSpecificOutput"]["additionalContext"],
+        "No unread messages."
+    );
+}
+
+fn status_decision(tmp: &TempDir) -> serde_json::Value {
+    let output = cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn status_always_blocks_for_listed_author_past_repeat_limit() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    let config = config
+        .replace("status_block_limit = 0", "status_block_limit = 1")
+        .replace("always_block_from = []", "always_block_from = [\"lead\"]");
+    std::fs::write(&config_path, config).unwrap();
+
+    cmd()
+        .args(["say", "routine update"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    // An ordinary backlog blocks once, then downgrades to context
+    assert_eq!(status_decision(&tmp)["decision"], "block");
+    let downgraded = status_decision(&tmp);
+    assert!(downgraded.get("decision").is_none());
+    assert!(downgraded["hookSpecificOutput"]["additionalContext"]
+        .as_str()
+        .unwrap()
+        .contains("routine update"));
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    cmd()
+        .args(["say", "stop and rebase"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "lead")
+        .env("AGENT_CHAT_SESSION_ID", "sess3")
+        .assert()
+        .success();
+
+    // A message from the lead keeps blocking past the limit
+    for _ in 0..3 {
+        let json = status_decision(&tmp);
+        assert_eq!(json["decision"], "block", "got: {}", json);
+        assert!(json["reason"].as_str().unwrap().contains("stop and rebase"));
+    }
+}
+
+#[test]
+fn status_stays_fast_with_large_read_backlog() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    // Thousands of already-read messages, then one new one
+    let log_dir = tmp.path().join(".agent-chat/log");
+    for i in 0..3000u64 {
+        std::fs::write(
+            log_dir.join(format!("{}.md", 1_700_000_000_000_000_000u64 + i)),
+            "name: swift-fox\nold news\n",
+        )
+        .unwrap();
+    }
+    std::fs::write(tmp.path().join(".agent-chat/cursors/sess2"), "1700000000000005000\n").unwrap();
+    cmd()
+        .args(["say", "fresh news"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    let start = std::time::Instant::now();
+    let output = cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    let elapsed = start.elapsed();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("fresh news"), "got: {}", stdout);
+    assert!(!stdout.contains("old news"));
+    assert!(elapsed.as_millis() < 100, "Status took {}ms, expected <100ms", elapsed.as_millis());
+}
+
+fn say_with_priority(tmp: &TempDir, msg: &str, priority: &str) {
+    cmd()
+        .args(["say", "--priority", priority, msg])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+}
+
+fn status_json(tmp: &TempDir) -> serde_json::Value {
+    let output = cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .output()
+        .unwrap();
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn status_low_priority_messages_only_add_context() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    say_with_priority(&tmp, "fyi: docs rebuilt", "low");
+
+    let json = status_json(&tmp);
+    assert!(json.get("decision").is_none(), "low priority must not block: {}", json);
+    let context = json["hookSpecificOutput"]["additionalContext"].as_str().unwrap();
+    assert!(context.contains("fyi: docs rebuilt"));
+}
+
+#[test]
+fn status_blocks_when_any_message_meets_threshold() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    say_with_priority(&tmp, "fyi", "low");
+    say_with_priority(&tmp, "need a decision", "normal");
+
+    assert_eq!(status_json(&tmp)["decision"], "block");
+}
+
+#[test]
+fn status_block_priority_high_lets_normal_messages_through() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("status_block_priority = \"normal\"", "status_block_priority = \"high\""),
+    )
+    .unwrap();
+
+    cmd()
+        .args(["say", "routine update"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+    assert!(status_json(&tmp).get("decision").is_none());
+
+    say_with_priority(&tmp, "prod is down", "high");
+    assert_eq!(status_json(&tmp)["decision"], "block");
+}
+
+#[test]
+fn mark_read_clears_status_silently() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    cmd()
+        .args(["say", "hello"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "swift-fox")
+        .env("AGENT_CHAT_SESSION_ID", "sess1")
+        .assert()
+        .success();
+
+    cmd()
+        .arg("mark-read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    cmd()
+        .arg("status")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn mark_read_to_stops_at_given_message() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    for msg in ["first", "second"] {
+        cmd()
+            .args(["say", msg])
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .assert()
+            .success();
+    }
+    let mut ids: Vec<String> = std::fs::read_dir(tmp.path().join(".agent-chat/log"))
+        .unwrap()
+        .map(|e| e.unwrap().path().file_stem().unwrap().to_string_lossy().to_string())
+        .collect();
+    ids.sort();
+
+    cmd()
+        .args(["mark-read", "--to", &ids[0]])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+
+    cmd()
+        .arg("read")
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("second"))
+        .stdout(predicate::str::contains("first").not());
+
+    cmd()
+        .args(["mark-read", "--to", "123"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .stderr(predicate::str::contains("Message not found: 123"));
+}
+
+fn lock_as(tmp: &TempDir, glob: &str, ttl: &str, name: &str, session_id: &str) {
+    cmd()
+        .args(["lock", glob, "--ttl", ttl])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", name)
+        .env("AGENT_CHAT_SESSION_ID", session_id)
+        .assert()
+        .success();
+}
+
+#[test]
+fn status_warns_about_own_expiring_locks_when_enabled() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+    lock_as(&tmp, "src/*.rs", "30", "swift-fox", "sess1");
+    lock_as(&tmp, "docs/*", "3600", "swift-fox", "sess1");
+    lock_as(&tmp, "tests/*", "30", "bold-hawk", "sess2");
+
+    // Off by default
+    let status = || {
+        cmd()
+            .arg("status")
+            .current_dir(tmp.path())
+            .env("AGENT_CHAT_NAME", "swift-fox")
+            .env("AGENT_CHAT_SESSION_ID", "sess1")
+            .output()
+            .unwrap()
+    };
+    assert!(status().stdout.is_empty());
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replace("status_lock_expiry_secs = 0", "status_lock_expiry_secs = 60"),
+    )
+    .unwrap();
+
+    let output = status();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("decision").is_none(), "Must not block: {}", json);
+    let context = json["hookSpecificOutput"]["additionalContext"].as_str().unwrap();
+    assert!(context.starts_with("your lock on src/*.rs expires in "), "{}", context);
+    assert!(context.ends_with("s — renew or unlock"), "{}", context);
+    assert!(!context.contains("docs/*"), "{}", context);
+    assert!(!context.contains("tests/*"), "{}", context);
+
+    // Appended to the reason when unread messages block
+    cmd()
+        .args(["say", "hello"])
+        .current_dir(tmp.path())
+        .env("AGENT_CHAT_NAME", "bold-hawk")
+        .env("AGENT_CHAT_SESSION_ID", "sess2")
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&status().stdout).unwrap();
+    assert_eq!(json["decision"], "block");
+    let reason = json["reason"].as_str().unwrap();
+    assert!(reason.contains("hello"), "{}", reason);
+    assert!(reason.contains("your lock on src/*.rs expires in"), "{}", reason);
+}
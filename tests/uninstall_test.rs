@@ -0,0 +1,141 @@
+use assert_cmd::Command;
+use assert_fs::TempDir;
+use predicates::prelude::*;
+use std::fs;
+
+fn cmd() -> Command {
+    assert_cmd::cargo::cargo_bin_cmd!("agent-chat")
+}
+
+fn init_project(tmp: &TempDir) {
+    cmd().args(["init", "--project"]).current_dir(tmp.path()).assert().success();
+}
+
+#[test]
+fn uninstall_requires_location_flag() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .arg("uninstall")
+        .current_dir(tmp.path())
+        .assert()
+        .stderr(predicate::str::contains("Specify --project, --user, or --both"));
+}
+
+#[test]
+fn uninstall_project_preserves_foreign_entries_and_is_idempotent() {
+    let tmp = TempDir::new().unwrap();
+    let claude_dir = tmp.path().join(".claude");
+    fs::create_dir_all(&claude_dir).unwrap();
+    fs::write(
+        claude_dir.join("settings.local.json"),
+        r#"{"permissions":{"allow":["Bash(cargo *)"]},"hooks":{"Stop":[{"hooks":[{"type":"command","command":"my-linter"}]}]}}"#,
+    )
+    .unwrap();
+    fs::write(tmp.path().join("CLAUDE.md"), "# Project notes\n").unwrap();
+    init_project(&tmp);
+
+    for _ in 0..2 {
+        cmd()
+            .args(["uninstall", "--project"])
+            .current_dir(tmp.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Removed agent-chat hooks and guidance (project)"));
+    }
+
+    let settings = fs::read_to_string(claude_dir.join("settings.local.json")).unwrap();
+    assert!(!settings.contains("agent-chat"), "agent-chat entries remain: {}", settings);
+    assert!(!settings.contains("check-lock"));
+    assert!(settings.contains("Bash(cargo *)"));
+    assert!(settings.contains("my-linter"));
+
+    let claude_md = fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap();
+    assert_eq!(claude_md, "# Project notes\n");
+
+    // Message data is kept without --purge
+    assert!(tmp.path().join(".agent-chat/log").exists());
+}
+
+#[test]
+fn uninstall_survives_invalid_config() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    let config_path = tmp.path().join(".agent-chat/config.toml");
+    let config = fs::read_to_string(&config_path).unwrap();
+    fs::write(&config_path, config.replace("lock_ttl_secs = 300", "lock_ttl_secs = 0")).unwrap();
+
+    cmd()
+        .args(["uninstall", "--project"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed agent-chat hooks and guidance (project)"))
+        .stderr(predicate::str::contains("removing the unlabeled agent-chat sections"));
+
+    let settings = fs::read_to_string(tmp.path().join(".claude/settings.local.json")).unwrap_or_default();
+    assert!(!settings.contains("check-lock"), "hooks remain: {}", settings);
+    assert!(!fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap_or_default().contains("agent-chat"));
+}
+
+#[test]
+fn uninstall_purge_removes_data_dir() {
+    let tmp = TempDir::new().unwrap();
+    init_project(&tmp);
+
+    cmd()
+        .args(["uninstall", "--project", "--purge"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("purged .agent-chat/"));
+
+    assert!(!tmp.path().join(".agent-chat").exists());
+    assert!(!tmp.path().join(".claude/settings.local.json").exists());
+    assert!(!tmp.path().join("CLAUDE.md").exists());
+
+    cmd()
+        .args(["uninstall", "--project", "--purge"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn uninstall_tool_codex_keeps_claude() {
+    let tmp = TempDir::new().unwrap();
+    cmd().args(["init", "--project", "--both-tools"]).current_dir(tmp.path()).assert().success();
+
+    cmd()
+        .args(["uninstall-tool", "codex", "--project"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed Codex integration (project)"));
+
+    assert!(!tmp.path().join("AGENTS.md").exists());
+    let claude_md = fs::read_to_string(tmp.path().join("CLAUDE.md")).unwrap();
+    assert!(claude_md.contains("<!-- agent-chat:start -->"));
+    let settings = fs::read_to_string(tmp.path().join(".claude/settings.local.json")).unwrap();
+    assert!(settings.contains("check-lock"));
+}
+
+#[test]
+fn uninstall_tool_claude_keeps_codex() {
+    let tmp = TempDir::new().unwrap();
+    cmd().args(["init", "--project", "--both-tools"]).current_dir(tmp.path()).assert().success();
+
+    cmd()
+        .args(["uninstall-tool", "claude", "--project"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed Claude integration (project)"));
+
+    assert!(!tmp.path().join("CLAUDE.md").exists());
+    assert!(!tmp.path().join(".claude/settings.local.json").exists());
+    let agents_md = fs::read_to_string(tmp.path().join("AGENTS.md")).unwrap();
+    assert!(agents_md.contains("<!-- agent-chat-codex:start -->"));
+}